@@ -172,6 +172,27 @@ where
     }
 }
 
+impl Writer<bgzf::MultithreadedWriter> {
+    /// Attempts to finish the output stream.
+    ///
+    /// This shuts down the compression workers and appends the final BGZF EOF block. It is
+    /// typically only manually called to handle errors that would otherwise be ignored when the
+    /// writer is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// let mut writer = bam::writer::Builder::default().build_multithreaded_from_writer(io::sink());
+    /// writer.try_finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        self.inner.finish()
+    }
+}
+
 impl<W> From<W> for Writer<W> {
     fn from(inner: W) -> Self {
         Self {
@@ -235,6 +256,63 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_multithreaded_from_writer() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{
+            num::NonZeroUsize,
+            sync::{Arc, Mutex},
+        };
+
+        use noodles_bgzf::writer::CompressionLevel;
+
+        struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for SharedBuf {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let header = sam::Header::default();
+        let records: Vec<_> = (0..1024)
+            .map(|i| {
+                Ok(Record::builder()
+                    .set_read_name(format!("r{i}").parse()?)
+                    .set_sequence("ACGT".repeat(64).parse()?)
+                    .build())
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        let buf = Arc::new(Mutex::new(Vec::new()));
+
+        let mut writer = Builder::default()
+            .set_compression_level(CompressionLevel::fast())
+            .set_worker_count(NonZeroUsize::new(4).unwrap())
+            .build_multithreaded_from_writer(SharedBuf(buf.clone()));
+
+        writer.write_header(&header)?;
+
+        for record in &records {
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = buf.lock().unwrap();
+        let mut reader = Reader::new(&data[..]);
+        let actual_header = reader.read_header()?;
+        assert_eq!(actual_header, header);
+
+        let actual_records: Vec<_> = reader.records(&header).collect::<io::Result<_>>()?;
+        assert_eq!(actual_records, records);
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_alignment_record_with_sequence_length_less_than_quality_scores_length(
     ) -> Result<(), Box<dyn std::error::Error>> {
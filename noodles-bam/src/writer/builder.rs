@@ -1,14 +1,63 @@
-use std::{fs::File, io, path::Path};
+use std::{
+    fs::File,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::Path,
+};
 
-use noodles_bgzf as bgzf;
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
 
 use super::Writer;
 
 /// A BAM writer builder.
-#[derive(Debug, Default)]
-pub struct Builder;
+#[derive(Debug)]
+pub struct Builder {
+    compression_level: CompressionLevel,
+    worker_count: NonZeroUsize,
+}
 
 impl Builder {
+    /// Sets the compression level.
+    ///
+    /// By default, the compression level is set to level 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::writer::CompressionLevel;
+    ///
+    /// let builder = bam::writer::Builder::default()
+    ///     .set_compression_level(CompressionLevel::fast());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Sets the worker count.
+    ///
+    /// This is the number of threads used to compress blocks. It only applies to multithreaded
+    /// writers, i.e., those built using [`Self::build_multithreaded_from_path`] or
+    /// [`Self::build_multithreaded_from_writer`].
+    ///
+    /// By default, the worker count is set to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    ///
+    /// let builder = bam::writer::Builder::default()
+    ///     .set_worker_count(NonZeroUsize::try_from(8)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
     /// Builds a BAM writer from a path.
     ///
     /// # Examples
@@ -22,6 +71,83 @@ impl Builder {
     where
         P: AsRef<Path>,
     {
-        File::create(dst).map(Writer::new)
+        File::create(dst).map(|file| self.build_from_writer(file))
+    }
+
+    /// Builds a BAM writer from a writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// let writer = bam::writer::Builder::default().build_from_writer(io::sink());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> Writer<bgzf::Writer<W>>
+    where
+        W: Write,
+    {
+        let inner = bgzf::writer::Builder::default()
+            .set_compression_level(self.compression_level)
+            .build_with_writer(writer);
+
+        Writer::from(inner)
+    }
+
+    /// Builds a multithreaded BAM writer from a path.
+    ///
+    /// Blocks are compressed using a pool of [`Self::set_worker_count`] threads.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    ///
+    /// let writer = bam::writer::Builder::default()
+    ///     .set_worker_count(NonZeroUsize::try_from(8)?)
+    ///     .build_multithreaded_from_path("out.bam")?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn build_multithreaded_from_path<P>(
+        self,
+        dst: P,
+    ) -> io::Result<Writer<bgzf::MultithreadedWriter>>
+    where
+        P: AsRef<Path>,
+    {
+        File::create(dst).map(|file| self.build_multithreaded_from_writer(file))
+    }
+
+    /// Builds a multithreaded BAM writer from a writer.
+    ///
+    /// Blocks are compressed using a pool of [`Self::set_worker_count`] threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// let writer = bam::writer::Builder::default().build_multithreaded_from_writer(io::sink());
+    /// ```
+    pub fn build_multithreaded_from_writer<W>(self, writer: W) -> Writer<bgzf::MultithreadedWriter>
+    where
+        W: Write + Send + 'static,
+    {
+        let inner = bgzf::multithreaded_writer::Builder::default()
+            .set_compression_level(self.compression_level)
+            .set_worker_count(self.worker_count)
+            .build_with_writer(writer);
+
+        Writer::from(inner)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            compression_level: CompressionLevel::default(),
+            worker_count: NonZeroUsize::new(1).unwrap(),
+        }
     }
 }
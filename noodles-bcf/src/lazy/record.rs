@@ -236,7 +236,17 @@ impl Record {
         &self.genotypes
     }
 
-    pub(crate) fn genotypes_mut(&mut self) -> &mut Genotypes {
+    /// Returns a mutable reference to the genotypes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::{self as bcf, lazy::record::Genotypes};
+    /// let mut record = bcf::lazy::Record::default();
+    /// *record.genotypes_mut() = Genotypes::default();
+    /// assert!(record.genotypes().is_empty());
+    /// ```
+    pub fn genotypes_mut(&mut self) -> &mut Genotypes {
         &mut self.genotypes
     }
 }
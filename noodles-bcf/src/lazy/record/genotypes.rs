@@ -112,6 +112,89 @@ impl Genotypes {
         self.format_count
     }
 
+    /// Returns the genotypes of the given samples, in the given order.
+    ///
+    /// The samples are selected by index. Sample values are copied without being decoded. An index
+    /// may be repeated, but each must be less than the number of samples. If no samples are
+    /// selected, the genotypes are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::lazy::record::Genotypes;
+    ///
+    /// let genotypes = Genotypes::default();
+    ///
+    /// let selected_genotypes = genotypes.select(&[])?;
+    /// assert!(selected_genotypes.is_empty());
+    ///
+    /// assert!(genotypes.select(&[0]).is_err());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn select(&self, sample_indices: &[usize]) -> io::Result<Self> {
+        use crate::{
+            lazy::record::value::Type,
+            reader::{string_map::read_string_map_index, value::read_type},
+        };
+
+        if let Some(&i) = sample_indices.iter().find(|&&i| i >= self.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid sample index: {i}"),
+            ));
+        }
+
+        if sample_indices.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut buf = Vec::with_capacity(self.buf.len());
+        let mut src = &self.buf[..];
+
+        for _ in 0..self.format_count() {
+            let field_start = self.buf.len() - src.len();
+
+            read_string_map_index(&mut src)?;
+
+            let value_len = match read_type(&mut src)? {
+                Some(Type::Int8(n) | Type::String(n)) => n,
+                Some(Type::Int16(n)) => 2 * n,
+                Some(Type::Int32(n) | Type::Float(n)) => 4 * n,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "missing genotype field type",
+                    ))
+                }
+            };
+
+            let values_start = self.buf.len() - src.len();
+            buf.extend(&self.buf[field_start..values_start]);
+
+            let values_len = value_len * self.len();
+
+            if src.len() < values_len {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+
+            let (values, rest) = src.split_at(values_len);
+
+            for &i in sample_indices {
+                let start = i * value_len;
+                buf.extend(&values[start..start + value_len]);
+            }
+
+            src = rest;
+        }
+
+        Ok(Self {
+            buf,
+            format_count: self.format_count(),
+            sample_count: sample_indices.len(),
+        })
+    }
+
     pub(crate) fn set_format_count(&mut self, format_count: usize) {
         self.format_count = format_count;
     }
@@ -132,3 +215,63 @@ impl AsMut<Vec<u8>> for Genotypes {
         &mut self.buf
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select() -> io::Result<()> {
+        let mut genotypes = Genotypes {
+            buf: vec![
+                0x11, 0x00, // string map index 0 (GT)
+                0x21, // Int8[2]
+                0x02, 0x04, // 0/1
+                0x02, 0x02, // 0/0
+                0x04, 0x04, // 1/1
+                0x11, 0x01, // string map index 1 (DP)
+                0x12, // Int16[1]
+                0x05, 0x00, // 5
+                0x08, 0x00, // 8
+                0x0d, 0x00, // 13
+            ],
+            format_count: 2,
+            sample_count: 3,
+        };
+
+        let actual = genotypes.select(&[2, 0])?;
+
+        let expected = Genotypes {
+            buf: vec![
+                0x11, 0x00, // string map index 0 (GT)
+                0x21, // Int8[2]
+                0x04, 0x04, // 1/1
+                0x02, 0x04, // 0/1
+                0x11, 0x01, // string map index 1 (DP)
+                0x12, // Int16[1]
+                0x0d, 0x00, // 13
+                0x05, 0x00, // 5
+            ],
+            format_count: 2,
+            sample_count: 2,
+        };
+
+        assert_eq!(actual, expected);
+
+        assert_eq!(genotypes.select(&[])?, Genotypes::default());
+
+        assert!(matches!(
+            genotypes.select(&[3]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        genotypes.buf.truncate(16);
+
+        assert!(matches!(
+            genotypes.select(&[0]),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! Indexed BED reader.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    io::{self, Read, Seek},
+    str::FromStr,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi as csi;
+
+use super::{
+    reader::Query,
+    record::{self, BedN, ParseError},
+    Reader, Record,
+};
+
+/// An indexed BED reader.
+pub struct IndexedReader<R> {
+    inner: Reader<bgzf::Reader<R>>,
+    index: csi::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed BED reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    /// use noodles_csi as csi;
+    /// let reader = bed::IndexedReader::new(io::empty(), csi::Index::default());
+    /// ```
+    pub fn new(inner: R, index: csi::Index) -> Self {
+        Self {
+            inner: Reader::new(bgzf::Reader::new(inner)),
+            index,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &bgzf::Reader<R> {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut bgzf::Reader<R> {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> bgzf::Reader<R> {
+        self.inner.into_inner()
+    }
+
+    /// Reads a raw BED line.
+    pub fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.inner.read_line(buf)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    pub fn records<const N: u8>(&mut self) -> impl Iterator<Item = io::Result<Record<N>>> + '_
+    where
+        Record<N>: FromStr<Err = record::ParseError>,
+    {
+        self.inner.records()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &csi::Index {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bed as bed;
+    ///
+    /// let mut reader = bed::indexed_reader::Builder::default().build_from_path("sample.bed.gz")?;
+    ///
+    /// let region = "sq0:8-13".parse()?;
+    ///
+    /// for result in reader.query::<3>(&region)? {
+    ///     let record = result?;
+    ///     println!("{}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<const N: u8>(&mut self, region: &Region) -> io::Result<Query<'_, R, N>>
+    where
+        Record<N>: BedN<3> + FromStr<Err = ParseError>,
+    {
+        self.inner.query(&self.index, region)
+    }
+}
@@ -2,8 +2,148 @@
 
 //! **noodles-bed** handles the reading and writing of the BED (Browser Extensible Data) format.
 
-mod reader;
+pub mod indexed_reader;
+pub mod reader;
 pub mod record;
 mod writer;
 
-pub use self::{reader::Reader, record::Record, writer::Writer};
+pub use self::{indexed_reader::IndexedReader, reader::Reader, record::Record, writer::Writer};
+
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_tabix as tabix;
+
+/// Indexes a bgzipped BED file.
+///
+/// The input is expected to be coordinate-sorted. The resulting index uses the tabix BED preset,
+/// i.e., positions are 0-based, and lines starting with `#` are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bed as bed;
+/// let index = bed::index("sample.bed.gz")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<csi::Index>
+where
+    P: AsRef<Path>,
+{
+    File::open(src).and_then(index_from_reader)
+}
+
+/// Indexes a bgzipped BED stream.
+///
+/// This is the same as [`index`] but reads from a stream of BGZF-compressed data.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bed as bed;
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"sq0\t7\t13\n")?;
+/// let data = writer.finish()?;
+///
+/// let index = bed::index_from_reader(&data[..])?;
+/// assert_eq!(index.reference_sequences().len(), 1);
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index_from_reader<R>(reader: R) -> io::Result<csi::Index>
+where
+    R: Read,
+{
+    let mut reader = Reader::new(bgzf::Reader::new(reader));
+
+    let mut indexer = tabix::index::Indexer::default();
+    indexer.set_header(csi::index::header::Builder::bed().build());
+
+    let mut buf = String::new();
+    let mut start_position = reader.virtual_position();
+
+    while let Some(record) = reader::read_record::<_, 3>(reader.get_mut(), &mut buf)? {
+        let end_position = reader.virtual_position();
+        let chunk = Chunk::new(start_position, end_position);
+
+        let (start, end) = reader::resolve_interval(&record);
+        indexer.add_record(record.reference_sequence_name(), start, end, chunk)?;
+
+        start_position = end_position;
+    }
+
+    Ok(indexer.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+
+    #[test]
+    fn test_index_and_query() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+
+        writer.write_all(b"# noodles-bed\n")?;
+
+        for i in 0..4096 {
+            let start = i * 100;
+            writeln!(writer, "sq0\t{start}\t{}\tr{i}", start + 150)?;
+        }
+
+        writer.write_all(b"sq1\t7\t13\tr4096\n")?;
+        writer.write_all(b"sq1\t21\t21\tr4097\n")?;
+
+        let data = writer.finish()?;
+
+        let index = index_from_reader(&data[..])?;
+
+        let header = index.header().expect("missing tabix header");
+        assert_eq!(
+            header.format(),
+            csi::index::header::Builder::bed().build().format()
+        );
+
+        let names: Vec<_> = header.reference_sequence_names().iter().collect();
+        assert_eq!(names, ["sq0", "sq1"]);
+
+        let mut reader = IndexedReader::new(Cursor::new(data), index);
+
+        let names = |reader: &mut IndexedReader<_>, region: &str| {
+            reader
+                .query::<4>(&region.parse()?)?
+                .map(|result| result.map(|record| record.name().map(|name| name.to_string())))
+                .collect::<io::Result<Vec<_>>>()
+                .map_err(Box::<dyn std::error::Error>::from)
+        };
+
+        assert_eq!(
+            names(&mut reader, "sq0:301-350")?,
+            [Some(String::from("r2")), Some(String::from("r3"))]
+        );
+
+        assert_eq!(
+            names(&mut reader, "sq0:409601-409650")?,
+            [Some(String::from("r4095"))]
+        );
+
+        assert_eq!(
+            names(&mut reader, "sq1:13-22")?,
+            [Some(String::from("r4096")), Some(String::from("r4097"))]
+        );
+
+        assert!(names(&mut reader, "sq1:23-34")?.is_empty());
+        assert!(reader.query::<3>(&"sq2:1-10".parse()?).is_err());
+
+        Ok(())
+    }
+}
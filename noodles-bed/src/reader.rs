@@ -1,10 +1,23 @@
+//! BED reader.
+
+mod query;
+
+pub use self::query::Query;
+
 use std::{
-    io::{self, BufRead},
+    io::{self, BufRead, Read, Seek},
     iter,
     str::FromStr,
 };
 
-use super::Record;
+use noodles_bgzf as bgzf;
+use noodles_core::{Position, Region};
+use noodles_csi as csi;
+
+use super::{
+    record::{BedN, ParseError},
+    Record,
+};
 
 /// A BED reader.
 pub struct Reader<R> {
@@ -114,30 +127,129 @@ where
     /// ```
     pub fn records<const N: u8>(&mut self) -> impl Iterator<Item = io::Result<Record<N>>> + '_
     where
-        Record<N>: FromStr<Err = super::record::ParseError>,
+        Record<N>: FromStr<Err = ParseError>,
     {
-        const COMMENT_PREFIX: &str = "#";
-
         let mut buf = String::new();
+        iter::from_fn(move || read_record(&mut self.inner, &mut buf).transpose())
+    }
+}
 
-        iter::from_fn(move || loop {
-            buf.clear();
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = Vec::new();
+    /// let reader = bed::Reader::new(bgzf::Reader::new(&data[..]));
+    ///
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
 
-            match self.read_line(&mut buf) {
-                Ok(0) => return None,
-                Ok(_) => {
-                    if buf.starts_with(COMMENT_PREFIX) {
-                        continue;
-                    } else {
-                        return Some(
-                            buf.parse()
-                                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
-                        );
-                    }
-                }
-                Err(e) => return Some(Err(e)),
-            }
-        })
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF reader to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = Cursor::new(Vec::new());
+    /// let mut reader = bed::Reader::new(bgzf::Reader::new(data));
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::default();
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("sample.bed.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(bed::Reader::new)?;
+    ///
+    /// let index = tabix::read("sample.bed.gz.tbi")?;
+    /// let region = "sq0:8-13".parse()?;
+    /// let query = reader.query::<3>(&index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<const N: u8>(
+        &mut self,
+        index: &csi::Index,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, N>>
+    where
+        Record<N>: BedN<3> + FromStr<Err = ParseError>,
+    {
+        let (reference_sequence_id, reference_sequence_name) = resolve_region(index, region)?;
+        let chunks = index.query(reference_sequence_id, region.interval())?;
+
+        Ok(Query::new(
+            self,
+            chunks,
+            reference_sequence_name,
+            region.interval(),
+        ))
+    }
+}
+
+// Reads lines until a record is parsed, skipping comment lines.
+pub(crate) fn read_record<R, const N: u8>(
+    reader: &mut R,
+    buf: &mut String,
+) -> io::Result<Option<Record<N>>>
+where
+    R: BufRead,
+    Record<N>: FromStr<Err = ParseError>,
+{
+    const COMMENT_PREFIX: &str = "#";
+
+    loop {
+        buf.clear();
+
+        if read_line(reader, buf)? == 0 {
+            return Ok(None);
+        } else if buf.starts_with(COMMENT_PREFIX) {
+            continue;
+        } else {
+            return buf
+                .parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+        }
     }
 }
 
@@ -165,6 +277,39 @@ where
     }
 }
 
+pub(crate) fn resolve_region(index: &csi::Index, region: &Region) -> io::Result<(usize, String)> {
+    let header = index
+        .header()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing tabix header"))?;
+
+    let i = header
+        .reference_sequence_names()
+        .get_index_of(region.name())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in reference sequences: {region:?}"
+                ),
+            )
+        })?;
+
+    Ok((i, region.name().into()))
+}
+
+// Returns the 1-based closed interval of a record.
+//
+// A zero-length feature, e.g., an insertion point, where the start and end positions are equal in
+// BED coordinates, is treated as covering the position after the start.
+pub(crate) fn resolve_interval<const N: u8>(record: &Record<N>) -> (Position, Position)
+where
+    Record<N>: BedN<3>,
+{
+    let start = record.start_position();
+    let end = record.end_position().max(start);
+    (start, end)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,124 @@
+use std::{
+    io::{self, Read, Seek},
+    str::FromStr,
+    vec,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::region::Interval;
+use noodles_csi::index::reference_sequence::bin::Chunk;
+
+use super::Reader;
+use crate::{
+    record::{BedN, ParseError},
+    Record,
+};
+
+enum State {
+    Seek,
+    Read(bgzf::VirtualPosition),
+    Done,
+}
+
+/// An iterator over records of a BED reader that intersects a given region.
+///
+/// This is created by calling [`Reader::query`].
+pub struct Query<'r, R, const N: u8>
+where
+    R: Read + Seek,
+{
+    reader: &'r mut Reader<bgzf::Reader<R>>,
+
+    chunks: vec::IntoIter<Chunk>,
+
+    reference_sequence_name: String,
+    interval: Interval,
+
+    state: State,
+    buf: String,
+}
+
+impl<'r, R, const N: u8> Query<'r, R, N>
+where
+    R: Read + Seek,
+    Record<N>: BedN<3> + FromStr<Err = ParseError>,
+{
+    pub(super) fn new(
+        reader: &'r mut Reader<bgzf::Reader<R>>,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: String,
+        interval: Interval,
+    ) -> Self {
+        Self {
+            reader,
+
+            chunks: chunks.into_iter(),
+
+            reference_sequence_name,
+            interval,
+
+            state: State::Seek,
+            buf: String::new(),
+        }
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record<N>>> {
+        super::read_record(&mut self.reader.inner, &mut self.buf)
+    }
+}
+
+impl<'r, R, const N: u8> Iterator for Query<'r, R, N>
+where
+    R: Read + Seek,
+    Record<N>: BedN<3> + FromStr<Err = ParseError>,
+{
+    type Item = io::Result<Record<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.chunks.next() {
+                        Some(chunk) => {
+                            if let Err(e) = self.reader.seek(chunk.start()) {
+                                return Some(Err(e));
+                            }
+
+                            State::Read(chunk.end())
+                        }
+                        None => State::Done,
+                    }
+                }
+                State::Read(chunk_end) => match self.read_record() {
+                    Ok(Some(record)) => {
+                        if self.reader.virtual_position() >= chunk_end {
+                            self.state = State::Seek;
+                        }
+
+                        if intersects(&record, &self.reference_sequence_name, self.interval) {
+                            return Some(Ok(record));
+                        }
+                    }
+                    Ok(None) => self.state = State::Seek,
+                    Err(e) => return Some(Err(e)),
+                },
+                State::Done => return None,
+            }
+        }
+    }
+}
+
+fn intersects<const N: u8>(
+    record: &Record<N>,
+    reference_sequence_name: &str,
+    region_interval: Interval,
+) -> bool
+where
+    Record<N>: BedN<3>,
+{
+    let (start, end) = super::resolve_interval(record);
+    let record_interval = Interval::from(start..=end);
+
+    record.reference_sequence_name() == reference_sequence_name
+        && record_interval.intersects(region_interval)
+}
@@ -41,7 +41,7 @@ mod block;
 mod gz;
 pub mod gzi;
 pub mod indexed_reader;
-mod multithreaded_writer;
+pub mod multithreaded_writer;
 pub mod reader;
 pub mod virtual_position;
 pub mod writer;
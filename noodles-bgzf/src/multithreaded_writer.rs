@@ -1,30 +1,45 @@
+//! Multithreaded BGZF writer.
+
+mod builder;
+mod deferred_virtual_position;
+
+pub use self::{builder::Builder, deferred_virtual_position::DeferredVirtualPosition};
+
 use std::{
     io::{self, Write},
     num::NonZeroUsize,
+    sync::{Arc, Mutex},
     thread::{self, JoinHandle},
 };
 
 use bytes::{BufMut, Bytes, BytesMut};
 use crossbeam_channel::{Receiver, Sender};
 
-use super::gz;
+use super::{gz, gzi, writer::CompressionLevelImpl, VirtualPosition};
 
 type BufferedTx = Sender<io::Result<Vec<u8>>>;
 type BufferedRx = Receiver<io::Result<Vec<u8>>>;
 type DeflateTx = Sender<(Bytes, BufferedTx)>;
 type DeflateRx = Receiver<(Bytes, BufferedTx)>;
-type WriteTx = Sender<BufferedRx>;
-type WriteRx = Receiver<BufferedRx>;
+type WriteTx = Sender<(BufferedRx, usize)>;
+type WriteRx = Receiver<(BufferedRx, usize)>;
+
+// The compressed start positions of each written block, indexed by block number. The last entry
+// is the position of the next block to be written.
+type BlockPositions = Arc<Mutex<Vec<u64>>>;
 
 /// A multithreaded BGZF writer.
 ///
 /// This is much more basic than [`super::Writer`] but uses a thread pool to compress block data.
 pub struct MultithreadedWriter {
-    writer_handle: Option<JoinHandle<io::Result<()>>>,
+    writer_handle: Option<JoinHandle<io::Result<Option<gzi::Index>>>>,
     deflater_handles: Vec<JoinHandle<()>>,
     buf: BytesMut,
     write_tx: Option<WriteTx>,
     deflate_tx: Option<DeflateTx>,
+    gzi_index: Option<gzi::Index>,
+    block_count: u64,
+    block_positions: BlockPositions,
 }
 
 impl MultithreadedWriter {
@@ -33,19 +48,105 @@ impl MultithreadedWriter {
     where
         W: Write + Send + 'static,
     {
-        let (write_tx, write_rx) = crossbeam_channel::bounded(worker_count.get());
-        let (deflate_tx, deflate_rx) = crossbeam_channel::bounded(worker_count.get());
-
-        let writer_handle = spawn_writer(inner, write_rx);
-        let deflater_handles = spawn_deflaters(worker_count, deflate_rx);
-
-        Self {
-            writer_handle: Some(writer_handle),
-            deflater_handles,
-            buf: BytesMut::new(),
-            write_tx: Some(write_tx),
-            deflate_tx: Some(deflate_tx),
-        }
+        Builder::default()
+            .set_worker_count(worker_count)
+            .build_with_writer(inner)
+    }
+
+    /// Returns the current deferred virtual position of the stream.
+    ///
+    /// The compressed position of the current block is unknown until all prior blocks are
+    /// compressed and written. The returned position can be resolved later using
+    /// [`Self::resolve_virtual_position`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::multithreaded_writer::Builder::default().build_with_writer(io::sink());
+    ///
+    /// writer.write_all(b"noodles")?;
+    ///
+    /// let position = writer.virtual_position();
+    /// assert_eq!(position.block_number(), 0);
+    /// assert_eq!(position.uncompressed(), 7);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn virtual_position(&self) -> DeferredVirtualPosition {
+        // SAFETY: The uncompressed buffer is guaranteed to be < `MAX_BUF_SIZE`.
+        let uncompressed_position = self.buf.len() as u16;
+        DeferredVirtualPosition::new(self.block_count, uncompressed_position)
+    }
+
+    /// Resolves a deferred virtual position.
+    ///
+    /// This returns `None` if the blocks prior to the block of the given position have not yet
+    /// been written. All positions taken from this writer are resolvable after the stream is
+    /// finished.
+    ///
+    /// Resolved positions are equivalent to those given by [`super::Writer::virtual_position`],
+    /// i.e., the position at the end of a block is the start of the next block.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the stream wrote >= 256 TiB of compressed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::multithreaded_writer::Builder::default().build_with_writer(io::sink());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// let position = writer.virtual_position();
+    ///
+    /// writer.finish()?;
+    ///
+    /// assert_eq!(
+    ///     writer.resolve_virtual_position(position),
+    ///     Some(bgzf::VirtualPosition::try_from((0, 7))?),
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn resolve_virtual_position(
+        &self,
+        position: DeferredVirtualPosition,
+    ) -> Option<VirtualPosition> {
+        let block_positions = self.block_positions.lock().unwrap();
+
+        let i = usize::try_from(position.block_number()).ok()?;
+        let compressed_position = block_positions.get(i).copied()?;
+
+        Some(VirtualPosition::try_from((compressed_position, position.uncompressed())).unwrap())
+    }
+
+    /// Returns the gzip index (GZI) of the written blocks.
+    ///
+    /// This is only available when the writer is built with [`Builder::set_build_gzi_index`] and
+    /// after the output stream is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::multithreaded_writer::Builder::default()
+    ///     .set_build_gzi_index(true)
+    ///     .build_with_writer(io::sink());
+    ///
+    /// writer.write_all(b"noodles-bgzf")?;
+    /// writer.finish()?;
+    ///
+    /// assert_eq!(writer.gzi_index(), Some(&vec![(0, 0)]));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_index.as_ref()
     }
 
     /// Finishes the output stream by flushing any remaining buffers.
@@ -63,7 +164,7 @@ impl MultithreadedWriter {
         self.write_tx.take();
 
         if let Some(handle) = self.writer_handle.take() {
-            handle.join().unwrap()?;
+            self.gzi_index = handle.join().unwrap()?;
         }
 
         Ok(())
@@ -72,12 +173,20 @@ impl MultithreadedWriter {
     fn send(&mut self) -> io::Result<()> {
         let (buffered_tx, buffered_rx) = crossbeam_channel::bounded(1);
 
-        self.write_tx.as_ref().unwrap().send(buffered_rx).unwrap();
+        let uncompressed_len = self.buf.len();
+
+        self.write_tx
+            .as_ref()
+            .unwrap()
+            .send((buffered_rx, uncompressed_len))
+            .unwrap();
 
         let src = self.buf.split().freeze();
         let message = (src, buffered_tx);
         self.deflate_tx.as_ref().unwrap().send(message).unwrap();
 
+        self.block_count += 1;
+
         Ok(())
     }
 }
@@ -115,34 +224,59 @@ impl Write for MultithreadedWriter {
     }
 }
 
-fn spawn_writer<W>(mut writer: W, write_rx: WriteRx) -> JoinHandle<io::Result<()>>
+fn spawn_writer<W>(
+    mut writer: W,
+    write_rx: WriteRx,
+    block_positions: BlockPositions,
+    build_gzi_index: bool,
+) -> JoinHandle<io::Result<Option<gzi::Index>>>
 where
     W: Write + Send + 'static,
 {
     use super::writer::BGZF_EOF;
 
     thread::spawn(move || {
-        while let Ok(buffered_rx) = write_rx.recv() {
+        let mut gzi_index = build_gzi_index.then(|| vec![(0, 0)]);
+        let mut position = 0;
+        let mut uncompressed_position = 0;
+
+        while let Ok((buffered_rx, uncompressed_len)) = write_rx.recv() {
             if let Ok(result) = buffered_rx.recv() {
                 let buf = result?;
+
+                if let Some(index) = gzi_index.as_mut() {
+                    if position > 0 {
+                        index.push((position, uncompressed_position));
+                    }
+                }
+
                 writer.write_all(&buf[..])?;
+
+                position += buf.len() as u64;
+                uncompressed_position += uncompressed_len as u64;
+
+                block_positions.lock().unwrap().push(position);
             }
         }
 
         writer.write_all(BGZF_EOF)?;
 
-        Ok(())
+        Ok(gzi_index)
     })
 }
 
-fn spawn_deflaters(worker_count: NonZeroUsize, deflate_rx: DeflateRx) -> Vec<JoinHandle<()>> {
+fn spawn_deflaters(
+    compression_level: CompressionLevelImpl,
+    worker_count: NonZeroUsize,
+    deflate_rx: DeflateRx,
+) -> Vec<JoinHandle<()>> {
     (0..worker_count.get())
         .map(|_| {
             let deflate_rx = deflate_rx.clone();
 
             thread::spawn(move || {
                 while let Ok((src, buffered_tx)) = deflate_rx.recv() {
-                    let result = compress(&src);
+                    let result = compress(&src, compression_level);
                     buffered_tx.send(result).ok();
                 }
             })
@@ -150,12 +284,12 @@ fn spawn_deflaters(worker_count: NonZeroUsize, deflate_rx: DeflateRx) -> Vec<Joi
         .collect()
 }
 
-fn compress(src: &[u8]) -> io::Result<Vec<u8>> {
+fn compress(src: &[u8], compression_level: CompressionLevelImpl) -> io::Result<Vec<u8>> {
     use super::{writer::deflate_data, BGZF_HEADER_SIZE};
 
     let mut dst = Vec::new();
 
-    let (cdata, crc32, _) = deflate_data(src, Default::default())?;
+    let (cdata, crc32, _) = deflate_data(src, compression_level)?;
 
     let block_size = BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE;
     put_header(&mut dst, block_size)?;
@@ -210,3 +344,140 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gzi_index() -> io::Result<()> {
+        use crate::{writer, Writer};
+
+        let data = vec![b'n'; 3 * writer::MAX_BUF_SIZE / 2];
+
+        let mut writer = Builder::default()
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .set_build_gzi_index(true)
+            .build_with_writer(io::sink());
+
+        writer.write_all(&data)?;
+        writer.finish()?;
+
+        let mut expected_writer = writer::Builder::default()
+            .set_build_gzi_index(true)
+            .build_with_writer(Vec::new());
+
+        expected_writer.write_all(&data)?;
+        Writer::try_finish(&mut expected_writer)?;
+
+        assert_eq!(writer.gzi_index(), expected_writer.gzi_index());
+        assert_eq!(writer.gzi_index().map(|index| index.len()), Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compression_level() -> io::Result<()> {
+        use crate::writer::{self, CompressionLevel};
+
+        struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for SharedBuf {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let data = b"noodles-bgzf".repeat(1024);
+
+        for compression_level in [CompressionLevel::none(), CompressionLevel::best()] {
+            let buf = Arc::new(Mutex::new(Vec::new()));
+
+            let mut writer = Builder::default()
+                .set_compression_level(compression_level)
+                .build_with_writer(SharedBuf(buf.clone()));
+            writer.write_all(&data)?;
+            writer.finish()?;
+
+            let mut expected_writer = writer::Builder::default()
+                .set_compression_level(compression_level)
+                .build_with_writer(Vec::new());
+            expected_writer.write_all(&data)?;
+            let expected = expected_writer.finish()?;
+
+            assert_eq!(*buf.lock().unwrap(), expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_virtual_position() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{writer, Writer};
+
+        let records: Vec<_> = (0..4096)
+            .map(|i| format!("noodles-bgzf-{i}\n").repeat(i % 16 + 1))
+            .collect();
+
+        let mut writer = Builder::default()
+            .set_worker_count(NonZeroUsize::new(4).unwrap())
+            .build_with_writer(io::sink());
+
+        let mut expected_writer = writer::Builder::default().build_with_writer(io::sink());
+
+        let mut positions = Vec::new();
+        let mut expected_positions = Vec::new();
+
+        for record in &records {
+            positions.push(writer.virtual_position());
+            expected_positions.push(expected_writer.virtual_position());
+
+            writer.write_all(record.as_bytes())?;
+            expected_writer.write_all(record.as_bytes())?;
+        }
+
+        writer.flush()?;
+        positions.push(writer.virtual_position());
+
+        Writer::flush(&mut expected_writer)?;
+        expected_positions.push(expected_writer.virtual_position());
+
+        writer.finish()?;
+
+        let actual_positions: Vec<_> = positions
+            .into_iter()
+            .map(|position| writer.resolve_virtual_position(position))
+            .collect::<Option<_>>()
+            .unwrap();
+
+        assert_eq!(actual_positions, expected_positions);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_virtual_position_with_unwritten_block() -> io::Result<()> {
+        let mut writer = Builder::default().build_with_writer(io::sink());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+
+        let position = writer.virtual_position();
+        assert_eq!(position, DeferredVirtualPosition::new(1, 4));
+
+        writer.finish()?;
+
+        let position = DeferredVirtualPosition::new(2, 0);
+        assert!(writer.resolve_virtual_position(position).is_some());
+
+        let position = DeferredVirtualPosition::new(3, 0);
+        assert!(writer.resolve_virtual_position(position).is_none());
+
+        Ok(())
+    }
+}
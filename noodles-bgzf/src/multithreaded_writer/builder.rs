@@ -0,0 +1,126 @@
+use std::{
+    io::Write,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use bytes::BytesMut;
+
+use super::{spawn_deflaters, spawn_writer, MultithreadedWriter};
+use crate::writer::CompressionLevel;
+
+/// A multithreaded BGZF writer builder.
+#[derive(Debug)]
+pub struct Builder {
+    compression_level: CompressionLevel,
+    worker_count: NonZeroUsize,
+    build_gzi_index: bool,
+}
+
+impl Builder {
+    /// Sets a compression level.
+    ///
+    /// By default, the compression level is set to level 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let builder = bgzf::multithreaded_writer::Builder::default()
+    ///     .set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Sets the worker count.
+    ///
+    /// By default, the worker count is set to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let builder = bgzf::multithreaded_writer::Builder::default()
+    ///     .set_worker_count(NonZeroUsize::try_from(4)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// When enabled, the compressed and uncompressed start positions of each block are recorded
+    /// as blocks are written. The resulting index is available from
+    /// [`MultithreadedWriter::gzi_index`] after the stream is finished.
+    ///
+    /// By default, a gzip index is not built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::multithreaded_writer::Builder::default().set_build_gzi_index(true);
+    /// ```
+    pub fn set_build_gzi_index(mut self, build_gzi_index: bool) -> Self {
+        self.build_gzi_index = build_gzi_index;
+        self
+    }
+
+    /// Builds a multithreaded BGZF writer from a writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::multithreaded_writer::Builder::default().build_with_writer(io::sink());
+    /// ```
+    pub fn build_with_writer<W>(self, writer: W) -> MultithreadedWriter
+    where
+        W: Write + Send + 'static,
+    {
+        let worker_count = self.worker_count;
+
+        let (write_tx, write_rx) = crossbeam_channel::bounded(worker_count.get());
+        let (deflate_tx, deflate_rx) = crossbeam_channel::bounded(worker_count.get());
+
+        let block_positions = Arc::new(Mutex::new(vec![0]));
+
+        let writer_handle = spawn_writer(
+            writer,
+            write_rx,
+            block_positions.clone(),
+            self.build_gzi_index,
+        );
+        let deflater_handles =
+            spawn_deflaters(self.compression_level.into(), worker_count, deflate_rx);
+
+        MultithreadedWriter {
+            writer_handle: Some(writer_handle),
+            deflater_handles,
+            buf: BytesMut::new(),
+            write_tx: Some(write_tx),
+            deflate_tx: Some(deflate_tx),
+            gzi_index: None,
+            block_count: 0,
+            block_positions,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            compression_level: CompressionLevel::default(),
+            worker_count: NonZeroUsize::new(1).unwrap(),
+            build_gzi_index: false,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+/// A deferred BGZF virtual position.
+///
+/// A multithreaded writer compresses blocks concurrently, so the compressed position of the
+/// block that is currently being buffered is not known until all prior blocks are written. A
+/// deferred virtual position records the block number and the position in the uncompressed block
+/// data, which can later be resolved to a [`crate::VirtualPosition`] using
+/// [`super::MultithreadedWriter::resolve_virtual_position`].
+///
+/// Deferred virtual positions are ordered in the same way as the virtual positions they resolve
+/// to.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DeferredVirtualPosition {
+    block_number: u64,
+    uncompressed_position: u16,
+}
+
+impl DeferredVirtualPosition {
+    pub(super) fn new(block_number: u64, uncompressed_position: u16) -> Self {
+        Self {
+            block_number,
+            uncompressed_position,
+        }
+    }
+
+    /// Returns the zero-based number of the block the position is in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::multithreaded_writer::DeferredVirtualPosition;
+    /// let position = DeferredVirtualPosition::default();
+    /// assert_eq!(position.block_number(), 0);
+    /// ```
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Returns the position in the uncompressed block data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::multithreaded_writer::DeferredVirtualPosition;
+    /// let position = DeferredVirtualPosition::default();
+    /// assert_eq!(position.uncompressed(), 0);
+    /// ```
+    pub fn uncompressed(&self) -> u16 {
+        self.uncompressed_position
+    }
+}
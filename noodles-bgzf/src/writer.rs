@@ -13,7 +13,7 @@ use std::{
 
 use flate2::Crc;
 
-use super::{gz, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
+use super::{gz, gzi, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
 
 // The max DEFLATE overhead for 65536 bytes of data at compression level 0.
 //
@@ -46,9 +46,9 @@ pub(crate) static BGZF_EOF: &[u8] = &[
 ];
 
 #[cfg(feature = "libdeflate")]
-type CompressionLevelImpl = libdeflater::CompressionLvl;
+pub(crate) type CompressionLevelImpl = libdeflater::CompressionLvl;
 #[cfg(not(feature = "libdeflate"))]
-type CompressionLevelImpl = flate2::Compression;
+pub(crate) type CompressionLevelImpl = flate2::Compression;
 
 /// A BZGF writer.
 ///
@@ -73,8 +73,10 @@ where
 {
     inner: Option<W>,
     position: u64,
+    uncompressed_position: u64,
     buf: Vec<u8>,
     compression_level: CompressionLevelImpl,
+    gzi_index: Option<gzi::Index>,
 }
 
 impl<W> Writer<W>
@@ -151,9 +153,45 @@ where
         VirtualPosition::try_from((self.position, uncompressed_position)).unwrap()
     }
 
+    /// Returns the gzip index (GZI) built from the blocks written so far.
+    ///
+    /// This is only available when the writer is built with
+    /// [`Builder::set_build_gzi_index`]. The index is complete once the output stream is finished,
+    /// e.g., after calling [`Self::try_finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::writer::Builder::default()
+    ///     .set_build_gzi_index(true)
+    ///     .build_with_writer(Vec::new());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// let position = writer.position();
+    ///
+    /// writer.write_all(b"-bgzf")?;
+    /// writer.try_finish()?;
+    ///
+    /// assert_eq!(writer.gzi_index(), Some(&vec![(0, 0), (position, 7)]));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_index.as_ref()
+    }
+
     fn flush_block(&mut self) -> io::Result<()> {
         use self::frame::{write_header, write_trailer};
 
+        if let Some(index) = self.gzi_index.as_mut() {
+            if self.position > 0 {
+                index.push((self.position, self.uncompressed_position));
+            }
+        }
+
         let (cdata, crc32, r#isize) = deflate_data(&self.buf, self.compression_level)?;
 
         let inner = self.inner.as_mut().unwrap();
@@ -164,6 +202,7 @@ where
         write_trailer(inner, crc32, r#isize)?;
 
         self.position += block_size as u64;
+        self.uncompressed_position += self.buf.len() as u64;
 
         self.buf.clear();
 
@@ -336,4 +375,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_gzi_index() -> io::Result<()> {
+        use crate::{IndexedReader, Reader};
+        use std::io::{Read, Seek, SeekFrom};
+
+        let writer = Writer::new(Vec::new());
+        assert!(writer.gzi_index().is_none());
+
+        let mut writer = Builder::default()
+            .set_build_gzi_index(true)
+            .build_with_writer(Vec::new());
+
+        assert_eq!(writer.gzi_index(), Some(&vec![(0, 0)]));
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let block_1_start = writer.position();
+        writer.write_all(b"-")?;
+        writer.flush()?;
+        let block_2_start = writer.position();
+        writer.write_all(b"bgzf")?;
+        writer.try_finish()?;
+
+        let index = writer.gzi_index().cloned().unwrap();
+        assert_eq!(index, [(0, 0), (block_1_start, 7), (block_2_start, 8)]);
+
+        let data = writer.into_inner();
+
+        let mut buf = Vec::new();
+        Reader::new(&data[..]).read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles-bgzf");
+
+        let mut reader = IndexedReader::new(io::Cursor::new(data), index);
+        reader.seek(SeekFrom::Start(8))?;
+        buf.clear();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"bgzf");
+
+        Ok(())
+    }
 }
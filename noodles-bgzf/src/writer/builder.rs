@@ -6,6 +6,7 @@ use super::{CompressionLevel, Writer, MAX_BUF_SIZE};
 #[derive(Debug, Default)]
 pub struct Builder {
     compression_level: CompressionLevel,
+    build_gzi_index: bool,
 }
 
 impl Builder {
@@ -26,6 +27,24 @@ impl Builder {
         self
     }
 
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// When enabled, the compressed and uncompressed start positions of each block are recorded
+    /// as blocks are flushed. The resulting index is available from [`Writer::gzi_index`].
+    ///
+    /// By default, a gzip index is not built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::writer::Builder::default().set_build_gzi_index(true);
+    /// ```
+    pub fn set_build_gzi_index(mut self, build_gzi_index: bool) -> Self {
+        self.build_gzi_index = build_gzi_index;
+        self
+    }
+
     /// Builds a BGZF writer from a writer.
     ///
     /// # Examples
@@ -42,8 +61,10 @@ impl Builder {
         Writer {
             inner: Some(writer),
             position: 0,
+            uncompressed_position: 0,
             buf: Vec::with_capacity(MAX_BUF_SIZE),
             compression_level: self.compression_level.into(),
+            gzi_index: self.build_gzi_index.then(|| vec![(0, 0)]),
         }
     }
 }
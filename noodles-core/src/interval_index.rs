@@ -0,0 +1,580 @@
+//! In-memory interval index.
+//!
+//! An interval index holds closed intervals grouped by reference sequence name, each with an
+//! associated value, e.g., a record. It supports querying intervals that overlap a region, finding
+//! the nearest intervals to a region, and merging overlapping intervals.
+//!
+//! Each reference sequence is indexed using a static augmented interval tree over the intervals
+//! sorted by start position. Queries run in O(log n + k) time, where k is the number of results.
+
+mod builder;
+mod entry;
+
+pub use self::{builder::Builder, entry::Entry};
+
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use super::{region::Interval, Position, Region};
+
+/// An in-memory interval index.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{IntervalIndex, Position, Region};
+///
+/// let index: IntervalIndex<_> = [
+///     ("sq0", Position::try_from(8)?..=Position::try_from(13)?, "r0"),
+///     ("sq0", Position::try_from(21)?..=Position::try_from(34)?, "r1"),
+///     ("sq1", Position::try_from(5)?..=Position::try_from(8)?, "r2"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let region = "sq0:10-15".parse()?;
+/// let values: Vec<_> = index.overlapping(&region).map(|entry| *entry.value()).collect();
+/// assert_eq!(values, ["r0"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntervalIndex<T> {
+    reference_sequences: HashMap<String, Intervals<T>>,
+}
+
+impl<T> IntervalIndex<T> {
+    /// Returns a builder to create an interval index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalIndex;
+    /// let builder = IntervalIndex::<()>::builder();
+    /// ```
+    pub fn builder() -> Builder<T> {
+        Builder::default()
+    }
+
+    /// Returns the number of entries in the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalIndex;
+    /// let index = IntervalIndex::<()>::default();
+    /// assert_eq!(index.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.reference_sequences
+            .values()
+            .map(|intervals| intervals.entries.len())
+            .sum()
+    }
+
+    /// Returns whether there are any entries in the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalIndex;
+    /// let index = IntervalIndex::<()>::default();
+    /// assert!(index.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the reference sequence names in the index.
+    ///
+    /// The names are in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let index: IntervalIndex<_> = [("sq0", Position::MIN..=Position::MIN, ())]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let names: Vec<_> = index.reference_sequence_names().collect();
+    /// assert_eq!(names, ["sq0"]);
+    /// ```
+    pub fn reference_sequence_names(&self) -> impl Iterator<Item = &str> {
+        self.reference_sequences.keys().map(|name| name.as_str())
+    }
+
+    /// Returns the entries of a reference sequence.
+    ///
+    /// The entries are sorted by start position and then by end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let index: IntervalIndex<_> = [("sq0", Position::MIN..=Position::MIN, ())]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(index.get("sq0").map(|entries| entries.len()), Some(1));
+    /// assert!(index.get("sq1").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&[Entry<T>]> {
+        self.reference_sequences
+            .get(name)
+            .map(|intervals| &intervals.entries[..])
+    }
+
+    /// Returns an iterator over the entries that intersect the given region.
+    ///
+    /// The entries are sorted by start position and then by end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position, Region};
+    ///
+    /// let index: IntervalIndex<_> = [
+    ///     ("sq0", Position::try_from(8)?..=Position::try_from(13)?, "r0"),
+    ///     ("sq0", Position::try_from(21)?..=Position::try_from(34)?, "r1"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let region = "sq0:13-21".parse()?;
+    /// let values: Vec<_> = index.overlapping(&region).map(|entry| *entry.value()).collect();
+    /// assert_eq!(values, ["r0", "r1"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn overlapping(&self, region: &Region) -> Overlapping<'_, T> {
+        match self.reference_sequences.get(region.name()) {
+            Some(intervals) => {
+                let (start, end) = resolve_interval(region.interval());
+                intervals.overlapping(start, end)
+            }
+            None => Overlapping::empty(),
+        }
+    }
+
+    /// Returns the entries nearest to the given region.
+    ///
+    /// If any entries intersect the region, these are returned. Otherwise, the entries with the
+    /// smallest distance to the region, either upstream or downstream, are returned. Ties are all
+    /// included and sorted by start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position, Region};
+    ///
+    /// let index: IntervalIndex<_> = [
+    ///     ("sq0", Position::try_from(8)?..=Position::try_from(13)?, "r0"),
+    ///     ("sq0", Position::try_from(21)?..=Position::try_from(34)?, "r1"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let region = "sq0:15-16".parse()?;
+    /// let values: Vec<_> = index.nearest(&region).into_iter().map(|e| *e.value()).collect();
+    /// assert_eq!(values, ["r0"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn nearest(&self, region: &Region) -> Vec<&Entry<T>> {
+        match self.reference_sequences.get(region.name()) {
+            Some(intervals) => {
+                let (start, end) = resolve_interval(region.interval());
+                intervals.nearest(start, end)
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the union of the intervals of a reference sequence.
+    ///
+    /// Overlapping and adjacent (book-ended) intervals are merged. The resulting intervals are
+    /// sorted and disjoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::Interval, IntervalIndex, Position};
+    ///
+    /// let index: IntervalIndex<_> = [
+    ///     ("sq0", Position::try_from(8)?..=Position::try_from(13)?, ()),
+    ///     ("sq0", Position::try_from(10)?..=Position::try_from(15)?, ()),
+    ///     ("sq0", Position::try_from(16)?..=Position::try_from(21)?, ()),
+    ///     ("sq0", Position::try_from(34)?..=Position::try_from(55)?, ()),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(
+    ///     index.merged_intervals("sq0"),
+    ///     [
+    ///         Interval::from(Position::try_from(8)?..=Position::try_from(21)?),
+    ///         Interval::from(Position::try_from(34)?..=Position::try_from(55)?),
+    ///     ]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn merged_intervals(&self, name: &str) -> Vec<Interval> {
+        let entries = match self.get(name) {
+            Some(entries) => entries,
+            None => return Vec::new(),
+        };
+
+        let mut intervals: Vec<RangeInclusive<Position>> = Vec::new();
+
+        for entry in entries {
+            if let Some(last) = intervals.last_mut() {
+                let is_mergeable = match last.end().checked_add(1) {
+                    Some(next_position) => entry.start() <= next_position,
+                    None => true,
+                };
+
+                if is_mergeable {
+                    if entry.end() > *last.end() {
+                        *last = *last.start()..=entry.end();
+                    }
+
+                    continue;
+                }
+            }
+
+            intervals.push(entry.start()..=entry.end());
+        }
+
+        intervals.into_iter().map(Interval::from).collect()
+    }
+}
+
+impl<T> Default for IntervalIndex<T> {
+    fn default() -> Self {
+        Self {
+            reference_sequences: HashMap::new(),
+        }
+    }
+}
+
+impl<N, T> FromIterator<(N, RangeInclusive<Position>, T)> for IntervalIndex<T>
+where
+    N: Into<String>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (N, RangeInclusive<Position>, T)>,
+    {
+        let mut builder = Builder::default();
+
+        for (name, interval, value) in iter {
+            builder.add_interval(name, interval, value);
+        }
+
+        builder.build()
+    }
+}
+
+/// An iterator over entries that intersect a region.
+///
+/// This is created by calling [`IntervalIndex::overlapping`].
+pub struct Overlapping<'a, T> {
+    intervals: Option<&'a Intervals<T>>,
+    // (node, first leaf index, leaf count)
+    stack: Vec<(usize, usize, usize)>,
+    // Only entries in [0, end_index) are considered.
+    end_index: usize,
+    // Only entries with an end >= min_end are considered.
+    min_end: Position,
+}
+
+impl<'a, T> Overlapping<'a, T> {
+    fn empty() -> Self {
+        Self {
+            intervals: None,
+            stack: Vec::new(),
+            end_index: 0,
+            min_end: Position::MIN,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Overlapping<'a, T> {
+    type Item = &'a Entry<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let intervals = self.intervals?;
+
+        while let Some((node, i, len)) = self.stack.pop() {
+            if i >= self.end_index || intervals.max_ends[node] < self.min_end {
+                continue;
+            }
+
+            if len == 1 {
+                return Some(&intervals.entries[i]);
+            }
+
+            let half = len / 2;
+            self.stack.push((2 * node + 1, i + half, half));
+            self.stack.push((2 * node, i, half));
+        }
+
+        None
+    }
+}
+
+// A static augmented interval tree.
+//
+// Entries are sorted by start position. `max_ends` is a complete binary tree (1-indexed) over the
+// entries, where each node holds the max end position of the entries in its subtree.
+#[derive(Clone, Debug)]
+struct Intervals<T> {
+    entries: Vec<Entry<T>>,
+    max_ends: Vec<Position>,
+    leaf_count: usize,
+}
+
+impl<T> Intervals<T> {
+    fn new(mut entries: Vec<Entry<T>>) -> Self {
+        entries.sort_by_key(|entry| (entry.start, entry.end));
+
+        let leaf_count = entries.len().next_power_of_two();
+        let mut max_ends = vec![Position::MIN; 2 * leaf_count];
+
+        for (i, entry) in entries.iter().enumerate() {
+            max_ends[leaf_count + i] = entry.end;
+        }
+
+        for i in (1..leaf_count).rev() {
+            max_ends[i] = max_ends[2 * i].max(max_ends[2 * i + 1]);
+        }
+
+        Self {
+            entries,
+            max_ends,
+            leaf_count,
+        }
+    }
+
+    // Returns the index of the first entry with a start > the given position.
+    fn partition_point(&self, position: Position) -> usize {
+        self.entries
+            .partition_point(|entry| entry.start <= position)
+    }
+
+    fn query(&self, end_index: usize, min_end: Position) -> Overlapping<'_, T> {
+        Overlapping {
+            intervals: Some(self),
+            stack: vec![(1, 0, self.leaf_count)],
+            end_index,
+            min_end,
+        }
+    }
+
+    fn overlapping(&self, start: Position, end: Position) -> Overlapping<'_, T> {
+        self.query(self.partition_point(end), start)
+    }
+
+    // Returns the max end position of the entries in [0, end_index).
+    fn max_end(&self, end_index: usize) -> Option<Position> {
+        let mut max_end = None;
+        let mut stack = vec![(1, 0, self.leaf_count)];
+
+        while let Some((node, i, len)) = stack.pop() {
+            if i >= end_index {
+                continue;
+            }
+
+            if i + len <= end_index || len == 1 {
+                max_end = max_end.max(Some(self.max_ends[node]));
+                continue;
+            }
+
+            let half = len / 2;
+            stack.push((2 * node, i, half));
+            stack.push((2 * node + 1, i + half, half));
+        }
+
+        max_end
+    }
+
+    fn nearest(&self, start: Position, end: Position) -> Vec<&Entry<T>> {
+        let overlapping: Vec<_> = self.overlapping(start, end).collect();
+
+        if !overlapping.is_empty() {
+            return overlapping;
+        }
+
+        // Since no entries intersect the region, the entries in [0, i) are all upstream of the
+        // region; and the entries in [i, n), downstream.
+        let i = self.partition_point(end);
+
+        let upstream = self.max_end(i).map(|max_end| {
+            let distance = usize::from(start) - usize::from(max_end);
+            (distance, self.query(i, max_end).collect::<Vec<_>>())
+        });
+
+        let downstream = self.entries.get(i).map(|first_entry| {
+            let distance = usize::from(first_entry.start) - usize::from(end);
+
+            let entries = self.entries[i..]
+                .iter()
+                .take_while(|entry| entry.start == first_entry.start)
+                .collect::<Vec<_>>();
+
+            (distance, entries)
+        });
+
+        match (upstream, downstream) {
+            (Some((a, mut upstream)), Some((b, downstream))) => {
+                if a < b {
+                    upstream
+                } else if b < a {
+                    downstream
+                } else {
+                    upstream.extend(downstream);
+                    upstream
+                }
+            }
+            (Some((_, upstream)), None) => upstream,
+            (None, Some((_, downstream))) => downstream,
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+fn resolve_interval(interval: Interval) -> (Position, Position) {
+    (
+        interval.start().unwrap_or(Position::MIN),
+        interval.end().unwrap_or(Position::MAX),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_index(intervals: &[(&str, usize, usize)]) -> IntervalIndex<usize> {
+        intervals
+            .iter()
+            .enumerate()
+            .map(|(i, &(name, start, end))| {
+                let start = Position::try_from(start).unwrap();
+                let end = Position::try_from(end).unwrap();
+                (name, start..=end, i)
+            })
+            .collect()
+    }
+
+    fn values<'a, I>(entries: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = &'a Entry<usize>>,
+    {
+        entries.into_iter().map(|entry| *entry.value()).collect()
+    }
+
+    #[test]
+    fn test_overlapping() -> Result<(), Box<dyn std::error::Error>> {
+        let index = build_index(&[
+            ("sq0", 21, 34),
+            ("sq0", 8, 13),
+            ("sq0", 1, 55),
+            ("sq0", 13, 13),
+            ("sq1", 8, 13),
+        ]);
+
+        assert_eq!(values(index.overlapping(&"sq0".parse()?)), [2, 1, 3, 0]);
+        assert_eq!(values(index.overlapping(&"sq0:13-20".parse()?)), [2, 1, 3]);
+        assert_eq!(values(index.overlapping(&"sq0:14-20".parse()?)), [2]);
+        assert_eq!(values(index.overlapping(&"sq0:34".parse()?)), [2, 0]);
+        assert_eq!(values(index.overlapping(&"sq0:56".parse()?)), []);
+        assert_eq!(values(index.overlapping(&"sq1:1-8".parse()?)), [4]);
+        assert_eq!(values(index.overlapping(&"sq2".parse()?)), []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overlapping_matches_linear_scan() -> Result<(), Box<dyn std::error::Error>> {
+        let intervals: Vec<_> = (0..257)
+            .map(|i| {
+                let start = (i * 7919) % 1000 + 1;
+                let end = start + (i * 104729) % 50;
+                ("sq0", start, end)
+            })
+            .collect();
+
+        let index = build_index(&intervals);
+
+        for query_start in (1..1100).step_by(13) {
+            let query_end = query_start + 20;
+            let region = format!("sq0:{query_start}-{query_end}").parse()?;
+
+            let mut actual = values(index.overlapping(&region));
+            actual.sort_unstable();
+
+            let expected: Vec<_> = intervals
+                .iter()
+                .enumerate()
+                .filter(|(_, &(_, start, end))| start <= query_end && end >= query_start)
+                .map(|(i, _)| i)
+                .collect();
+
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nearest() -> Result<(), Box<dyn std::error::Error>> {
+        let index = build_index(&[
+            ("sq0", 8, 13),
+            ("sq0", 5, 13),
+            ("sq0", 21, 34),
+            ("sq0", 21, 25),
+            ("sq0", 55, 89),
+        ]);
+
+        assert_eq!(values(index.nearest(&"sq0:13-14".parse()?)), [1, 0]);
+        assert_eq!(values(index.nearest(&"sq0:15-16".parse()?)), [1, 0]);
+        assert_eq!(values(index.nearest(&"sq0:18-19".parse()?)), [3, 2]);
+        assert_eq!(values(index.nearest(&"sq0:17-17".parse()?)), [1, 0, 3, 2]);
+        assert_eq!(values(index.nearest(&"sq0:1-2".parse()?)), [1]);
+        assert_eq!(values(index.nearest(&"sq0:144-233".parse()?)), [4]);
+        assert_eq!(values(index.nearest(&"sq1:1-2".parse()?)), []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merged_intervals() -> Result<(), Box<dyn std::error::Error>> {
+        let index = build_index(&[
+            ("sq0", 21, 34),
+            ("sq0", 8, 13),
+            ("sq0", 5, 8),
+            ("sq0", 14, 15),
+            ("sq0", 22, 23),
+            ("sq0", 55, 89),
+        ]);
+
+        let expected = [
+            Interval::from(Position::try_from(5)?..=Position::try_from(15)?),
+            Interval::from(Position::try_from(21)?..=Position::try_from(34)?),
+            Interval::from(Position::try_from(55)?..=Position::try_from(89)?),
+        ];
+
+        assert_eq!(index.merged_intervals("sq0"), expected);
+        assert!(index.merged_intervals("sq1").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_interval_with_empty_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Builder::default();
+        builder.add_interval("sq0", Position::try_from(13)?..=Position::try_from(8)?, ());
+        let index = builder.build();
+        assert!(index.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use super::{Entry, IntervalIndex, Intervals};
+use crate::Position;
+
+/// An interval index builder.
+#[derive(Debug)]
+pub struct Builder<T> {
+    entries: HashMap<String, Vec<Entry<T>>>,
+}
+
+impl<T> Builder<T> {
+    /// Adds an interval with an associated value.
+    ///
+    /// The interval is closed, i.e., [start, end], and positions are 1-based. Empty intervals,
+    /// i.e., those where start > end, are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_index, Position};
+    ///
+    /// let mut builder = interval_index::Builder::default();
+    /// builder.add_interval("sq0", Position::try_from(8)?..=Position::try_from(13)?, "r0");
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn add_interval<N>(&mut self, name: N, interval: RangeInclusive<Position>, value: T)
+    where
+        N: Into<String>,
+    {
+        let (start, end) = interval.into_inner();
+
+        if start > end {
+            return;
+        }
+
+        self.entries
+            .entry(name.into())
+            .or_default()
+            .push(Entry { start, end, value });
+    }
+
+    /// Builds an interval index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_index;
+    /// let index = interval_index::Builder::<()>::default().build();
+    /// assert!(index.is_empty());
+    /// ```
+    pub fn build(self) -> IntervalIndex<T> {
+        let reference_sequences = self
+            .entries
+            .into_iter()
+            .map(|(name, entries)| (name, Intervals::new(entries)))
+            .collect();
+
+        IntervalIndex {
+            reference_sequences,
+        }
+    }
+}
+
+impl<T> Default for Builder<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
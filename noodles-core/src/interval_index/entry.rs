@@ -0,0 +1,89 @@
+use crate::{region::Interval, Position};
+
+/// An interval index entry.
+///
+/// An entry is a closed interval [start, end] associated with a value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry<T> {
+    pub(super) start: Position,
+    pub(super) end: Position,
+    pub(super) value: T,
+}
+
+impl<T> Entry<T> {
+    /// Returns the start position (1-based).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    /// let index: IntervalIndex<_> = [("sq0", start..=end, "r0")].into_iter().collect();
+    ///
+    /// let entry = index.get("sq0").and_then(|entries| entries.first());
+    /// assert_eq!(entry.map(|e| e.start()), Some(start));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end position (1-based).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    /// let index: IntervalIndex<_> = [("sq0", start..=end, "r0")].into_iter().collect();
+    ///
+    /// let entry = index.get("sq0").and_then(|entries| entries.first());
+    /// assert_eq!(entry.map(|e| e.end()), Some(end));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the start and end positions as an interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::Interval, IntervalIndex, Position};
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    /// let index: IntervalIndex<_> = [("sq0", start..=end, "r0")].into_iter().collect();
+    ///
+    /// let entry = index.get("sq0").and_then(|entries| entries.first());
+    /// assert_eq!(entry.map(|e| e.interval()), Some(Interval::from(start..=end)));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn interval(&self) -> Interval {
+        Interval::from(self.start..=self.end)
+    }
+
+    /// Returns the associated value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    /// let index: IntervalIndex<_> = [("sq0", start..=end, "r0")].into_iter().collect();
+    ///
+    /// let entry = index.get("sq0").and_then(|entries| entries.first());
+    /// assert_eq!(entry.map(|e| e.value()), Some(&"r0"));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
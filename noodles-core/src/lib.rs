@@ -3,10 +3,11 @@
 //! **noodles-core** contains shared structures and behavior among noodles libraries.
 
 pub mod error;
+pub mod interval_index;
 pub mod position;
 pub mod region;
 
-pub use self::{error::Error, position::Position, region::Region};
+pub use self::{error::Error, interval_index::IntervalIndex, position::Position, region::Region};
 
 /// A specialized [`std::result::Result`] type for results in noodles.
 pub type Result<T> = std::result::Result<T, error::Error>;
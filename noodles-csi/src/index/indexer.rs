@@ -129,9 +129,7 @@ impl Indexer {
                 .build();
         }
 
-        // SAFETY: `reference_sequence_count` is > 0.
-        let last_reference_sequence_id = reference_sequence_count - 1;
-        self.add_reference_sequences_builders_until(last_reference_sequence_id);
+        self.add_reference_sequences_builders_until(reference_sequence_count);
 
         let mut builder = Index::builder()
             .set_reference_sequences(self.reference_sequences)
@@ -184,4 +182,36 @@ mod tests {
         assert!(indexer.reference_sequences.is_empty());
         assert_eq!(indexer.unplaced_unmapped_record_count, 0);
     }
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bgzf as bgzf;
+
+        let mut indexer = Indexer::default();
+
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(13)?;
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::from(144),
+            bgzf::VirtualPosition::from(233),
+        );
+        indexer.add_record(Some((0, start, end, true)), chunk)?;
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::from(233),
+            bgzf::VirtualPosition::from(377),
+        );
+        indexer.add_record(Some((1, start, end, true)), chunk)?;
+
+        let index = indexer.build(3);
+        let reference_sequences = index.reference_sequences();
+
+        assert_eq!(reference_sequences.len(), 3);
+        assert!(reference_sequences[0].metadata().is_some());
+        assert!(reference_sequences[1].metadata().is_some());
+        assert!(reference_sequences[2].metadata().is_none());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,28 @@
+//! Queries a bgzipped GFF file with a given region.
+//!
+//! The input GFF must have an associated index in the same directory.
+//!
+//! The result matches the output of `tabix <src> <region>`.
+
+use std::env;
+
+use noodles_gff as gff;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args();
+
+    let src = args.nth(1).expect("missing src");
+    let raw_region = args.next().expect("missing region");
+
+    let mut reader = gff::indexed_reader::Builder::default().build_from_path(src)?;
+
+    let region = raw_region.parse()?;
+    let query = reader.query(&region)?;
+
+    for result in query {
+        let record = result?;
+        println!("{record}");
+    }
+
+    Ok(())
+}
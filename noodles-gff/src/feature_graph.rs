@@ -0,0 +1,325 @@
+//! GFF feature graph.
+//!
+//! A feature graph links features by their `ID` and `Parent` attributes, e.g., a gene to its
+//! transcripts and a transcript to its exons and CDS.
+
+mod builder;
+mod feature;
+
+pub use self::{
+    builder::{BuildError, Builder},
+    feature::Feature,
+};
+
+use std::collections::HashMap;
+
+const GENE: &str = "gene";
+const EXON: &str = "exon";
+const CDS: &str = "CDS";
+
+/// A GFF feature graph.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeatureGraph {
+    features: Vec<Feature>,
+    ids: HashMap<String, usize>,
+    roots: Vec<usize>,
+}
+
+impl FeatureGraph {
+    /// Returns a builder to create a feature graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    /// let builder = gff::FeatureGraph::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the number of features in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    /// let graph = gff::FeatureGraph::default();
+    /// assert_eq!(graph.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    /// Returns whether there are any features in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    /// let graph = gff::FeatureGraph::default();
+    /// assert!(graph.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// Returns an iterator over all features in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    /// let graph = gff::FeatureGraph::default();
+    /// assert!(graph.features().next().is_none());
+    /// ```
+    pub fn features(&self) -> impl Iterator<Item = &Feature> {
+        self.features.iter()
+    }
+
+    /// Returns the feature with the given ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// assert_eq!(graph.get("gene0").map(|feature| feature.ty()), Some("gene"));
+    /// assert!(graph.get("gene1").is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get(&self, id: &str) -> Option<&Feature> {
+        self.ids.get(id).map(|&i| &self.features[i])
+    }
+
+    /// Returns an iterator over features with no parents in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let ids: Vec<_> = graph.roots().filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(ids, ["gene0"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn roots(&self) -> impl Iterator<Item = &Feature> {
+        self.roots.iter().map(move |&i| &self.features[i])
+    }
+
+    /// Returns an iterator over the parents of the given feature.
+    ///
+    /// Parents are in the order they are listed in the `Parent` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx1".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tID=exon0;Parent=tx0,tx1".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let exon = graph.get("exon0").expect("missing exon");
+    /// let ids: Vec<_> = graph.parents(exon).filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(ids, ["tx0", "tx1"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parents<'g>(&'g self, feature: &'g Feature) -> impl Iterator<Item = &'g Feature> {
+        feature.parents.iter().map(move |&i| &self.features[i])
+    }
+
+    /// Returns an iterator over the children of the given feature.
+    ///
+    /// Children are sorted by start position, then end position, then input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t55\t.\t+\t.\tID=tx0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\texon\t34\t55\t.\t+\t.\tID=exon1;Parent=tx0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tID=exon0;Parent=tx0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let transcript = graph.get("tx0").expect("missing transcript");
+    /// let ids: Vec<_> = graph.children(transcript).filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(ids, ["exon0", "exon1"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn children<'g>(&'g self, feature: &'g Feature) -> impl Iterator<Item = &'g Feature> {
+        feature.children.iter().map(move |&i| &self.features[i])
+    }
+
+    /// Returns an iterator over genes (features with the type `gene`) in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let ids: Vec<_> = graph.genes().filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(ids, ["gene0"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn genes(&self) -> impl Iterator<Item = &Feature> {
+        self.features().filter(|feature| feature.ty() == GENE)
+    }
+
+    /// Returns an iterator over the transcripts of the given gene.
+    ///
+    /// Transcript types vary (e.g., `mRNA`, `ncRNA`, `transcript`), so these are all children of
+    /// the gene, in the same order as [`Self::children`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let gene = graph.get("gene0").expect("missing gene");
+    /// let ids: Vec<_> = graph.transcripts(gene).filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(ids, ["tx0"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn transcripts<'g>(&'g self, gene: &'g Feature) -> impl Iterator<Item = &'g Feature> {
+        self.children(gene)
+    }
+
+    /// Returns an iterator over the exons (children with the type `exon`) of the given
+    /// transcript.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tID=exon0;Parent=tx0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0;Parent=tx0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let transcript = graph.get("tx0").expect("missing transcript");
+    /// let ids: Vec<_> = graph.exons(transcript).filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(ids, ["exon0"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn exons<'g>(&'g self, transcript: &'g Feature) -> impl Iterator<Item = &'g Feature> {
+        self.children(transcript)
+            .filter(|feature| feature.ty() == EXON)
+    }
+
+    /// Returns an iterator over the coding sequences (children with the type `CDS`) of the given
+    /// transcript.
+    ///
+    /// A CDS is typically a single discontiguous feature, where each segment is a record sharing
+    /// the same ID (see [`Feature::records`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tmRNA\t8\t55\t.\t+\t.\tID=tx0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t34\t55\t.\t+\t0\tID=cds0;Parent=tx0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0;Parent=tx0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let transcript = graph.get("tx0").expect("missing transcript");
+    /// let cds: Vec<_> = graph.cds(transcript).collect();
+    ///
+    /// assert_eq!(cds.len(), 1);
+    /// assert_eq!(cds[0].records().len(), 2);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn cds<'g>(&'g self, transcript: &'g Feature) -> impl Iterator<Item = &'g Feature> {
+        self.children(transcript)
+            .filter(|feature| feature.ty() == CDS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gene_model() -> Result<(), Box<dyn std::error::Error>> {
+        let data = "\
+sq0\tNOODLES\tgene\t1\t100\t.\t+\t.\tID=gene0
+sq0\tNOODLES\tmRNA\t1\t100\t.\t+\t.\tID=tx0;Parent=gene0
+sq0\tNOODLES\tmRNA\t1\t80\t.\t+\t.\tID=tx1;Parent=gene0
+sq0\tNOODLES\texon\t61\t100\t.\t+\t.\tID=exon2;Parent=tx0
+sq0\tNOODLES\texon\t1\t20\t.\t+\t.\tID=exon0;Parent=tx0,tx1
+sq0\tNOODLES\texon\t41\t80\t.\t+\t.\tID=exon1;Parent=tx1
+sq0\tNOODLES\tCDS\t61\t90\t.\t+\t2\tID=cds0;Parent=tx0
+sq0\tNOODLES\tCDS\t11\t20\t.\t+\t0\tID=cds0;Parent=tx0
+";
+
+        let mut builder = FeatureGraph::builder();
+
+        for line in data.lines() {
+            builder.add_record(line.parse()?)?;
+        }
+
+        let graph = builder.build()?;
+
+        assert_eq!(graph.len(), 7);
+
+        let genes: Vec<_> = graph.genes().collect();
+        assert_eq!(genes.len(), 1);
+
+        let transcripts: Vec<_> = graph.transcripts(genes[0]).collect();
+        let ids: Vec<_> = transcripts.iter().filter_map(|t| t.id()).collect();
+        assert_eq!(ids, ["tx1", "tx0"]);
+
+        let ids: Vec<_> = graph.exons(transcripts[0]).filter_map(|f| f.id()).collect();
+        assert_eq!(ids, ["exon0", "exon1"]);
+
+        let ids: Vec<_> = graph.exons(transcripts[1]).filter_map(|f| f.id()).collect();
+        assert_eq!(ids, ["exon0", "exon2"]);
+
+        let cds: Vec<_> = graph.cds(transcripts[1]).collect();
+        assert_eq!(cds.len(), 1);
+
+        let starts: Vec<_> = cds[0]
+            .records()
+            .iter()
+            .map(|r| usize::from(r.start()))
+            .collect();
+        assert_eq!(starts, [11, 61]);
+        assert_eq!(usize::from(cds[0].start()), 11);
+        assert_eq!(usize::from(cds[0].end()), 90);
+
+        let exon = graph.get("exon0").expect("missing exon0");
+        let ids: Vec<_> = graph.parents(exon).filter_map(|f| f.id()).collect();
+        assert_eq!(ids, ["tx0", "tx1"]);
+
+        assert!(graph.cds(transcripts[0]).next().is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+use std::{collections::HashMap, error, fmt};
+
+use super::{Feature, FeatureGraph};
+use crate::Record;
+
+/// A GFF feature graph builder.
+///
+/// Records are added in any order. Parent links (`Parent`) are resolved when the graph is built,
+/// so parents may be added after their children.
+#[derive(Debug, Default)]
+pub struct Builder {
+    features: Vec<Feature>,
+    ids: HashMap<String, usize>,
+}
+
+/// An error returned when a GFF feature graph fails to build.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BuildError {
+    /// Records sharing an ID have different reference sequence names or types.
+    InconsistentFeature(String),
+    /// A parent ID does not refer to a feature in the graph.
+    MissingParent(String),
+    /// A feature is its own ancestor.
+    Cycle(String),
+}
+
+impl error::Error for BuildError {}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InconsistentFeature(id) => write!(f, "inconsistent feature: {id}"),
+            Self::MissingParent(id) => write!(f, "missing parent: {id}"),
+            Self::Cycle(id) => write!(f, "cycle at feature: {id}"),
+        }
+    }
+}
+
+impl Builder {
+    /// Adds a record to the graph.
+    ///
+    /// A record with an ID that was already added is appended as a segment to the existing
+    /// feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::{self as gff, feature_graph};
+    ///
+    /// let record: gff::Record = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?;
+    ///
+    /// let mut builder = feature_graph::Builder::default();
+    /// builder.add_record(record)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_record(&mut self, record: Record) -> Result<(), BuildError> {
+        let id = record.attributes().id().map(String::from);
+
+        if let Some(id) = id {
+            if let Some(&i) = self.ids.get(&id) {
+                let feature = &mut self.features[i];
+
+                if feature.reference_sequence_name() != record.reference_sequence_name()
+                    || feature.ty() != record.ty()
+                {
+                    return Err(BuildError::InconsistentFeature(id));
+                }
+
+                feature.records.push(record);
+            } else {
+                self.ids.insert(id.clone(), self.features.len());
+                self.features.push(Feature::new(Some(id), record));
+            }
+        } else {
+            self.features.push(Feature::new(None, record));
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether any records were added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature_graph;
+    /// let builder = feature_graph::Builder::default();
+    /// assert!(builder.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// Builds a GFF feature graph.
+    ///
+    /// This resolves parent links. It fails if a parent ID is not defined by any added record or
+    /// if a feature is its own ancestor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature_graph;
+    /// let graph = feature_graph::Builder::default().build()?;
+    /// assert!(graph.is_empty());
+    /// # Ok::<_, feature_graph::BuildError>(())
+    /// ```
+    pub fn build(self) -> Result<FeatureGraph, BuildError> {
+        let Self { mut features, ids } = self;
+
+        for feature in &mut features {
+            feature.records.sort_by_key(|record| record.start());
+        }
+
+        for i in 0..features.len() {
+            let mut parents = Vec::new();
+
+            for record in &features[i].records {
+                for parent_id in record.attributes().parent().into_iter().flatten() {
+                    let j = ids
+                        .get(parent_id)
+                        .copied()
+                        .ok_or_else(|| BuildError::MissingParent(parent_id.into()))?;
+
+                    if !parents.contains(&j) {
+                        parents.push(j);
+                    }
+                }
+            }
+
+            for &j in &parents {
+                features[j].children.push(i);
+            }
+
+            features[i].parents = parents;
+        }
+
+        detect_cycles(&features)?;
+
+        for i in 0..features.len() {
+            let mut children = std::mem::take(&mut features[i].children);
+            children.sort_by_key(|&j| (features[j].start(), features[j].end(), j));
+            features[i].children = children;
+        }
+
+        let roots = features
+            .iter()
+            .enumerate()
+            .filter(|(_, feature)| feature.parents.is_empty())
+            .map(|(i, _)| i)
+            .collect();
+
+        Ok(FeatureGraph {
+            features,
+            ids,
+            roots,
+        })
+    }
+}
+
+fn detect_cycles(features: &[Feature]) -> Result<(), BuildError> {
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    enum Mark {
+        Unvisited,
+        Visiting,
+        Visited,
+    }
+
+    let mut marks = vec![Mark::Unvisited; features.len()];
+    let mut stack = Vec::new();
+
+    for start in 0..features.len() {
+        if marks[start] != Mark::Unvisited {
+            continue;
+        }
+
+        // Each stack entry is a feature index and the position of the next parent to visit.
+        marks[start] = Mark::Visiting;
+        stack.push((start, 0));
+
+        while let Some((i, next)) = stack.last_mut() {
+            let i = *i;
+
+            match features[i].parents.get(*next) {
+                Some(&j) => {
+                    *next += 1;
+
+                    match marks[j] {
+                        Mark::Unvisited => {
+                            marks[j] = Mark::Visiting;
+                            stack.push((j, 0));
+                        }
+                        Mark::Visiting => {
+                            let id = features[j].id().unwrap_or_default().into();
+                            return Err(BuildError::Cycle(id));
+                        }
+                        Mark::Visited => {}
+                    }
+                }
+                None => {
+                    marks[i] = Mark::Visited;
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(s: &str) -> Result<FeatureGraph, Box<dyn std::error::Error>> {
+        let mut builder = Builder::default();
+
+        for line in s.lines() {
+            builder.add_record(line.parse()?)?;
+        }
+
+        builder.build().map_err(|e| e.into())
+    }
+
+    #[test]
+    fn test_build_with_forward_references() -> Result<(), Box<dyn std::error::Error>> {
+        let graph = build(
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=tx0
+sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0
+sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0",
+        )?;
+
+        assert_eq!(graph.len(), 3);
+
+        let roots: Vec<_> = graph.roots().filter_map(|f| f.id()).collect();
+        assert_eq!(roots, ["gene0"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_inconsistent_feature() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Builder::default();
+        builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+
+        assert_eq!(
+            builder.add_record("sq0\tNOODLES\texon\t21\t34\t.\t+\t.\tID=cds0".parse()?),
+            Err(BuildError::InconsistentFeature(String::from("cds0")))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_missing_parent() {
+        let result = build("sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tID=exon0;Parent=tx0");
+        assert!(matches!(
+            result.map_err(|e| e.downcast::<BuildError>().map(|e| *e)),
+            Err(Ok(BuildError::MissingParent(id))) if id == "tx0"
+        ));
+    }
+
+    #[test]
+    fn test_build_with_cycle() {
+        let result = build(
+            "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0;Parent=tx0
+sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0",
+        );
+
+        assert!(matches!(
+            result.map_err(|e| e.downcast::<BuildError>().map(|e| *e)),
+            Err(Ok(BuildError::Cycle(_)))
+        ));
+
+        let result = build("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0;Parent=gene0");
+
+        assert!(matches!(
+            result.map_err(|e| e.downcast::<BuildError>().map(|e| *e)),
+            Err(Ok(BuildError::Cycle(id))) if id == "gene0"
+        ));
+    }
+}
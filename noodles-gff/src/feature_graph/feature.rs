@@ -0,0 +1,175 @@
+use noodles_core::Position;
+
+use crate::{record::Strand, Record};
+
+/// A feature in a GFF feature graph.
+///
+/// A feature is made of one or more records. Records that share the same `ID` are segments of a
+/// single discontiguous feature, e.g., the CDS of a transcript. Segments are sorted by their
+/// start positions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Feature {
+    pub(super) id: Option<String>,
+    pub(super) records: Vec<Record>,
+    pub(super) parents: Vec<usize>,
+    pub(super) children: Vec<usize>,
+}
+
+impl Feature {
+    pub(super) fn new(id: Option<String>, record: Record) -> Self {
+        Self {
+            id,
+            records: vec![record],
+            parents: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the feature ID (`ID`), if set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let feature = graph.get("cds0").expect("missing feature");
+    /// assert_eq!(feature.id(), Some("cds0"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the records that make up the feature.
+    ///
+    /// This is never empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let feature = graph.get("cds0").expect("missing feature");
+    /// assert_eq!(feature.records().len(), 2);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Returns the reference sequence name of the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let feature = graph.get("cds0").expect("missing feature");
+    /// assert_eq!(feature.reference_sequence_name(), "sq0");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        self.first().reference_sequence_name()
+    }
+
+    /// Returns the feature type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let feature = graph.get("cds0").expect("missing feature");
+    /// assert_eq!(feature.ty(), "CDS");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn ty(&self) -> &str {
+        self.first().ty()
+    }
+
+    /// Returns the start position of the first segment of the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let feature = graph.get("cds0").expect("missing feature");
+    /// assert_eq!(usize::from(feature.start()), 8);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn start(&self) -> Position {
+        self.first().start()
+    }
+
+    /// Returns the largest end position of all segments of the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let feature = graph.get("cds0").expect("missing feature");
+    /// assert_eq!(usize::from(feature.end()), 34);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn end(&self) -> Position {
+        // SAFETY: `records` is never empty.
+        self.records.iter().map(|r| r.end()).max().unwrap()
+    }
+
+    /// Returns the strand of the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let mut builder = gff::FeatureGraph::builder();
+    /// builder.add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)?;
+    /// builder.add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)?;
+    /// let graph = builder.build()?;
+    ///
+    /// let feature = graph.get("cds0").expect("missing feature");
+    /// assert_eq!(feature.strand(), gff::record::Strand::Forward);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.first().strand()
+    }
+
+    fn first(&self) -> &Record {
+        // SAFETY: `records` is never empty.
+        &self.records[0]
+    }
+}
@@ -1,11 +1,20 @@
 //! GFF reader and iterators.
 
+mod feature_graphs;
 mod lines;
+mod query;
 mod records;
 
-pub use self::{lines::Lines, records::Records};
+pub use self::{feature_graphs::FeatureGraphs, lines::Lines, query::Query, records::Records};
 
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi as csi;
+use noodles_fasta as fasta;
+
+use super::{Directive, Line, Record};
 
 const LINE_FEED: char = '\n';
 const CARRIAGE_RETURN: char = '\r';
@@ -48,6 +57,22 @@ where
         &self.inner
     }
 
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff as gff;
+    ///
+    /// let data = b"##gff-version 3\n";
+    /// let mut reader = gff::Reader::new(&data[..]);
+    ///
+    /// let _ = reader.get_mut();
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
     /// Unwraps and returns the underlying reader.
     ///
     /// # Examples
@@ -157,6 +182,185 @@ where
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self.lines())
     }
+
+    /// Converts the GFF reader into a FASTA reader.
+    ///
+    /// This is used to read the sequences of the embedded FASTA section. It should be called
+    /// after the `FASTA` directive is read, e.g., after [`Self::records`] is exhausted, so that
+    /// the underlying stream is positioned at the first FASTA definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_gff as gff;
+    ///
+    /// let data = b"##gff-version 3
+    /// sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0
+    /// ###FASTA
+    /// >sq0
+    /// ACGTACGTACGTACGT
+    /// ";
+    /// let mut reader = gff::Reader::new(&data[..]);
+    ///
+    /// for result in reader.records() {
+    ///     let _record = result?;
+    /// }
+    ///
+    /// let mut reader = reader.into_fasta_reader();
+    /// let mut records = reader.records();
+    ///
+    /// let record = records.next().transpose()?.expect("missing FASTA record");
+    /// assert_eq!(record.name(), "sq0");
+    /// assert_eq!(record.sequence().len(), 16);
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn into_fasta_reader(self) -> fasta::Reader<R> {
+        fasta::Reader::new(self.inner)
+    }
+
+    /// Returns an iterator over feature graphs starting from the current stream position.
+    ///
+    /// Each graph is built from the records read until either the `###` directive, the `FASTA`
+    /// directive, or EOF, whichever comes first. Parent links are resolved within each graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_gff as gff;
+    ///
+    /// let data = b"##gff-version 3
+    /// sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0
+    /// sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0
+    /// ####
+    /// sq0\tNOODLES\tgene\t21\t34\t.\t+\t.\tID=gene1
+    /// ";
+    /// let mut reader = gff::Reader::new(&data[..]);
+    /// let mut graphs = reader.feature_graphs();
+    ///
+    /// let graph = graphs.next().transpose()?.expect("missing graph");
+    /// assert_eq!(graph.len(), 2);
+    ///
+    /// let graph = graphs.next().transpose()?.expect("missing graph");
+    /// assert_eq!(graph.len(), 1);
+    ///
+    /// assert!(graphs.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn feature_graphs(&mut self) -> FeatureGraphs<'_, R> {
+        FeatureGraphs::new(self.lines())
+    }
+}
+
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_gff as gff;
+    ///
+    /// let data = Vec::new();
+    /// let reader = gff::Reader::new(bgzf::Reader::new(&data[..]));
+    ///
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF reader to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_gff as gff;
+    ///
+    /// let data = Cursor::new(Vec::new());
+    /// let mut reader = gff::Reader::new(bgzf::Reader::new(data));
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::default();
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_gff as gff;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("annotations.gff3.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(gff::Reader::new)?;
+    ///
+    /// let index = tabix::read("annotations.gff3.gz.tbi")?;
+    /// let region = "sq0:8-13".parse()?;
+    /// let query = reader.query(&index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&mut self, index: &csi::Index, region: &Region) -> io::Result<Query<'_, R>> {
+        let (reference_sequence_id, reference_sequence_name) = resolve_region(index, region)?;
+        let chunks = index.query(reference_sequence_id, region.interval())?;
+
+        Ok(Query::new(
+            self,
+            chunks,
+            reference_sequence_name,
+            region.interval(),
+        ))
+    }
+}
+
+// Reads lines until a record is parsed, skipping directives and comments.
+//
+// This returns `None` at either EOF or when the `FASTA` directive is read, whichever comes first.
+pub(crate) fn read_record<R>(reader: &mut R, buf: &mut String) -> io::Result<Option<Record>>
+where
+    R: BufRead,
+{
+    loop {
+        buf.clear();
+
+        if read_line(reader, buf)? == 0 {
+            return Ok(None);
+        }
+
+        match buf.parse() {
+            Ok(Line::Directive(Directive::StartOfFasta)) => return Ok(None),
+            Ok(Line::Record(record)) => return Ok(Some(record)),
+            Ok(_) => {}
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
 }
 
 fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
@@ -180,6 +384,26 @@ where
     }
 }
 
+pub(crate) fn resolve_region(index: &csi::Index, region: &Region) -> io::Result<(usize, String)> {
+    let header = index
+        .header()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing tabix header"))?;
+
+    let i = header
+        .reference_sequence_names()
+        .get_index_of(region.name())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in reference sequences: {region:?}"
+                ),
+            )
+        })?;
+
+    Ok((i, region.name().into()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,6 +451,48 @@ ACGT
         Ok(())
     }
 
+    #[test]
+    fn test_feature_graphs_with_missing_forward_reference() {
+        let data = b"\
+##gff-version 3
+sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0
+###
+sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0
+";
+
+        let mut reader = Reader::new(&data[..]);
+        let mut graphs = reader.feature_graphs();
+
+        assert!(matches!(graphs.next(), Some(Ok(_))));
+        assert!(matches!(
+            graphs.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+        assert!(graphs.next().is_none());
+    }
+
+    #[test]
+    fn test_read_record() -> io::Result<()> {
+        let data = b"\
+##gff-version 3
+# noodles
+sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id=ndls0
+##FASTA
+>sq0
+ACGT
+";
+
+        let mut reader = &data[..];
+        let mut buf = String::new();
+
+        let record = read_record(&mut reader, &mut buf)?;
+        assert_eq!(record.map(|r| r.start()), noodles_core::Position::new(8));
+
+        assert!(read_record(&mut reader, &mut buf)?.is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         fn t(buf: &mut String, mut reader: &[u8], expected: &str) -> io::Result<()> {
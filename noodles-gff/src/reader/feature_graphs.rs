@@ -0,0 +1,84 @@
+use std::io::{self, BufRead};
+
+use super::Lines;
+use crate::{feature_graph, Directive, FeatureGraph, Line};
+
+/// An iterator over feature graphs of a GFF reader.
+///
+/// Records are collected until either the `###` (forward references are resolved) directive, the
+/// `FASTA` directive, or EOF is read, at which point the collected records are built into a
+/// feature graph. A parent that is not defined in the same section is an error.
+///
+/// This is created by calling [`crate::Reader::feature_graphs`].
+pub struct FeatureGraphs<'a, R> {
+    lines: Lines<'a, R>,
+    is_eof: bool,
+}
+
+impl<'a, R> FeatureGraphs<'a, R>
+where
+    R: BufRead,
+{
+    pub(crate) fn new(lines: Lines<'a, R>) -> Self {
+        Self {
+            lines,
+            is_eof: false,
+        }
+    }
+
+    fn read_graph(&mut self) -> io::Result<Option<FeatureGraph>> {
+        let mut builder = feature_graph::Builder::default();
+
+        loop {
+            match self.lines.next().transpose()? {
+                Some(Line::Directive(Directive::ForwardReferencesAreResolved)) => {
+                    if builder.is_empty() {
+                        continue;
+                    }
+
+                    break;
+                }
+                Some(Line::Directive(Directive::StartOfFasta)) | None => {
+                    self.is_eof = true;
+
+                    if builder.is_empty() {
+                        return Ok(None);
+                    }
+
+                    break;
+                }
+                Some(Line::Record(record)) => builder
+                    .add_record(record)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                Some(_) => {}
+            }
+        }
+
+        builder
+            .build()
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<'a, R> Iterator for FeatureGraphs<'a, R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<FeatureGraph>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_eof {
+            return None;
+        }
+
+        match self.read_graph() {
+            Ok(Some(graph)) => Some(Ok(graph)),
+            Ok(None) => None,
+            Err(e) => {
+                self.is_eof = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
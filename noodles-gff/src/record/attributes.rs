@@ -1,17 +1,215 @@
 //! GFF record attributes and entry.
 
 pub mod entry;
+pub mod gap;
+pub mod tag;
+pub mod target;
 
-pub use self::entry::Entry;
+pub use self::{entry::Entry, gap::Gap, target::Target};
 
 use std::{error, fmt, ops::Deref, str::FromStr};
 
+use self::entry::{value, Value};
+
 const DELIMITER: char = ';';
 
 /// GFF record attributes.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Attributes(Vec<Entry>);
 
+impl Attributes {
+    /// Returns the value of the first entry with the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::{attributes::{entry::Value, Entry}, Attributes};
+    ///
+    /// let attributes = Attributes::from(vec![Entry::new("gene_name", "gene0")]);
+    ///
+    /// assert_eq!(attributes.get("gene_name"), Some(&Value::from("gene0")));
+    /// assert!(attributes.get("gene_id").is_none());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.iter()
+            .find(|entry| entry.key() == key)
+            .map(|entry| entry.value())
+    }
+
+    /// Returns the feature ID (`ID`).
+    ///
+    /// This is `None` if the tag is missing or has multiple values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=gene0;Name=NDLS".parse()?;
+    /// assert_eq!(attributes.id(), Some("gene0"));
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn id(&self) -> Option<&str> {
+        self.get(tag::ID).and_then(|value| value.as_string())
+    }
+
+    /// Returns the display name (`Name`).
+    ///
+    /// This is `None` if the tag is missing or has multiple values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=gene0;Name=NDLS".parse()?;
+    /// assert_eq!(attributes.name(), Some("NDLS"));
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn name(&self) -> Option<&str> {
+        self.get(tag::NAME).and_then(|value| value.as_string())
+    }
+
+    /// Returns an iterator over secondary names (`Alias`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=gene0;Alias=ndls0,ndls1".parse()?;
+    /// let aliases: Option<Vec<_>> = attributes.alias().map(|values| values.collect());
+    /// assert_eq!(aliases, Some(vec!["ndls0", "ndls1"]));
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn alias(&self) -> Option<value::Iter<'_>> {
+        self.get(tag::ALIAS).map(|value| value.iter())
+    }
+
+    /// Returns an iterator over parent feature IDs (`Parent`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=exon0;Parent=tx0,tx1".parse()?;
+    /// let parents: Option<Vec<_>> = attributes.parent().map(|values| values.collect());
+    /// assert_eq!(parents, Some(vec!["tx0", "tx1"]));
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn parent(&self) -> Option<value::Iter<'_>> {
+        self.get(tag::PARENT).map(|value| value.iter())
+    }
+
+    /// Returns the alignment target (`Target`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_gff::record::Attributes;
+    ///
+    /// let attributes: Attributes = "ID=match0;Target=EST23 8 13".parse()?;
+    /// let target = attributes.target().transpose()?.expect("missing Target");
+    ///
+    /// assert_eq!(target.id(), "EST23");
+    /// assert_eq!(target.start(), Position::try_from(8)?);
+    /// assert_eq!(target.end(), Position::try_from(13)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn target(&self) -> Option<Result<Target, target::ParseError>> {
+        self.get(tag::TARGET).map(|value| match value {
+            Value::String(s) => s.parse(),
+            Value::Array(_) => Err(target::ParseError::Invalid),
+        })
+    }
+
+    /// Returns the alignment of the feature to the target (`Gap`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=match0;Gap=M8 D3 M6".parse()?;
+    /// let gap = attributes.gap().transpose()?.expect("missing Gap");
+    /// assert_eq!(gap.len(), 3);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn gap(&self) -> Option<Result<Gap, gap::ParseError>> {
+        self.get(tag::GAP).map(|value| match value {
+            Value::String(s) => s.parse(),
+            Value::Array(_) => Err(gap::ParseError::Invalid),
+        })
+    }
+
+    /// Returns the ID of the feature this feature derives from (`Derives_from`).
+    ///
+    /// This is `None` if the tag is missing or has multiple values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=protein0;Derives_from=tx0".parse()?;
+    /// assert_eq!(attributes.derives_from(), Some("tx0"));
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn derives_from(&self) -> Option<&str> {
+        self.get(tag::DERIVES_FROM)
+            .and_then(|value| value.as_string())
+    }
+
+    /// Returns whether the sequence is circular (`Is_circular`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    ///
+    /// let attributes: Attributes = "ID=sq0;Is_circular=true".parse()?;
+    /// assert_eq!(attributes.is_circular(), Some(Ok(true)));
+    ///
+    /// let attributes: Attributes = "ID=sq0".parse()?;
+    /// assert!(attributes.is_circular().is_none());
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn is_circular(&self) -> Option<Result<bool, IsCircularError>> {
+        self.get(tag::IS_CIRCULAR)
+            .map(|value| match value.as_string() {
+                Some("true") => Ok(true),
+                Some("false") => Ok(false),
+                _ => Err(IsCircularError(value.to_string())),
+            })
+    }
+
+    /// Returns an iterator over ontology term cross references (`Ontology_term`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=gene0;Ontology_term=GO:0046703,GO:0046704".parse()?;
+    /// let terms: Option<Vec<_>> = attributes.ontology_term().map(|values| values.collect());
+    /// assert_eq!(terms, Some(vec!["GO:0046703", "GO:0046704"]));
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn ontology_term(&self) -> Option<value::Iter<'_>> {
+        self.get(tag::ONTOLOGY_TERM).map(|value| value.iter())
+    }
+
+    /// Returns an iterator over database cross references (`Dbxref`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::Attributes;
+    /// let attributes: Attributes = "ID=gene0;Dbxref=EMBL:AA816246,NCBI_gi:10727410".parse()?;
+    /// let xrefs: Option<Vec<_>> = attributes.dbxref().map(|values| values.collect());
+    /// assert_eq!(xrefs, Some(vec!["EMBL:AA816246", "NCBI_gi:10727410"]));
+    /// # Ok::<_, noodles_gff::record::attributes::ParseError>(())
+    /// ```
+    pub fn dbxref(&self) -> Option<value::Iter<'_>> {
+        self.get(tag::DBXREF).map(|value| value.iter())
+    }
+}
+
 impl Deref for Attributes {
     type Target = [Entry];
 
@@ -34,6 +232,22 @@ impl fmt::Display for Attributes {
     }
 }
 
+/// An error returned when an `Is_circular` value is not a boolean.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IsCircularError(String);
+
+impl error::Error for IsCircularError {}
+
+impl fmt::Display for IsCircularError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid Is_circular: expected {{true, false}}, got {}",
+            self.0
+        )
+    }
+}
+
 /// An error returned when raw attributes fail to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -97,7 +311,18 @@ mod tests {
             Entry::new("gene_name", "gene0"),
         ]);
 
-        assert_eq!(attributes.to_string(), "gene_id=ndls0;gene_name=gene0")
+        assert_eq!(attributes.to_string(), "gene_id=ndls0;gene_name=gene0");
+
+        let attributes = Attributes::from(vec![
+            Entry::new("ID", "exon0"),
+            Entry::new("Parent", vec![String::from("tx0"), String::from("tx1")]),
+            Entry::new("Note", "A, B"),
+        ]);
+
+        assert_eq!(
+            attributes.to_string(),
+            "ID=exon0;Parent=tx0,tx1;Note=A%2C B"
+        )
     }
 
     #[test]
@@ -118,10 +343,62 @@ mod tests {
         )]);
         assert_eq!(actual, expected);
 
+        let s = "ID=exon0;Parent=tx0,tx1";
+        let actual = s.parse::<Attributes>()?;
+        let expected = Attributes::from(vec![
+            Entry::new("ID", "exon0"),
+            Entry::new("Parent", vec![String::from("tx0"), String::from("tx1")]),
+        ]);
+        assert_eq!(actual, expected);
+
         let actual = "".parse::<Attributes>()?;
         let expected = Attributes::default();
         assert_eq!(actual, expected);
 
         Ok(())
     }
+
+    #[test]
+    fn test_reserved_tags() -> Result<(), Box<dyn std::error::Error>> {
+        let attributes: Attributes =
+            "ID=match0;Name=m0;Alias=a0;Parent=tx0,tx1;Target=EST23 8 13 +;Gap=M8 D3 M6;Derives_from=tx2;Is_circular=false;Ontology_term=GO:0046703;Dbxref=EMBL:AA816246,NCBI_gi:10727410"
+                .parse()?;
+
+        assert_eq!(attributes.id(), Some("match0"));
+        assert_eq!(attributes.name(), Some("m0"));
+        assert_eq!(
+            attributes.alias().map(|v| v.collect::<Vec<_>>()),
+            Some(vec!["a0"])
+        );
+        assert_eq!(
+            attributes.parent().map(|v| v.collect::<Vec<_>>()),
+            Some(vec!["tx0", "tx1"])
+        );
+        assert_eq!(
+            attributes.target().transpose()?.map(|t| t.to_string()),
+            Some(String::from("EST23 8 13 +"))
+        );
+        assert_eq!(attributes.gap().transpose()?.map(|g| g.len()), Some(3));
+        assert_eq!(attributes.derives_from(), Some("tx2"));
+        assert_eq!(attributes.is_circular(), Some(Ok(false)));
+        assert_eq!(
+            attributes.ontology_term().map(|v| v.collect::<Vec<_>>()),
+            Some(vec!["GO:0046703"])
+        );
+        assert_eq!(
+            attributes.dbxref().map(|v| v.collect::<Vec<_>>()),
+            Some(vec!["EMBL:AA816246", "NCBI_gi:10727410"])
+        );
+
+        let attributes = Attributes::default();
+        assert!(attributes.id().is_none());
+        assert!(attributes.parent().is_none());
+        assert!(attributes.target().is_none());
+        assert!(attributes.is_circular().is_none());
+
+        let attributes: Attributes = "Is_circular=yes".parse()?;
+        assert!(matches!(attributes.is_circular(), Some(Err(_))));
+
+        Ok(())
+    }
 }
@@ -1,4 +1,8 @@
-//! GFF record attribute entry.
+//! GFF record attribute entry and value.
+
+pub mod value;
+
+pub use self::value::Value;
 
 use std::{
     borrow::Cow,
@@ -24,7 +28,7 @@ const SEPARATOR: char = '=';
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Entry {
     key: String,
-    value: String,
+    value: Value,
 }
 
 impl Entry {
@@ -39,7 +43,7 @@ impl Entry {
     pub fn new<K, V>(key: K, value: V) -> Self
     where
         K: Into<String>,
-        V: Into<String>,
+        V: Into<Value>,
     {
         Self {
             key: key.into(),
@@ -65,11 +69,11 @@ impl Entry {
     /// # Examples
     ///
     /// ```
-    /// use noodles_gff::record::attributes::Entry;
+    /// use noodles_gff::record::attributes::{entry::Value, Entry};
     /// let entry = Entry::new("gene_name", "gene0");
-    /// assert_eq!(entry.value(), "gene0");
+    /// assert_eq!(entry.value(), &Value::from("gene0"));
     /// ```
-    pub fn value(&self) -> &str {
+    pub fn value(&self) -> &Value {
         &self.value
     }
 }
@@ -81,7 +85,7 @@ impl fmt::Display for Entry {
             "{}{}{}",
             percent_encode(self.key()),
             SEPARATOR,
-            percent_encode(self.value())
+            self.value()
         )
     }
 }
@@ -152,11 +156,26 @@ fn parse_key(s: &str) -> Result<Cow<'_, str>, ParseError> {
     }
 }
 
-fn parse_value(s: &str) -> Result<Cow<'_, str>, ParseError> {
+fn parse_value(s: &str) -> Result<Value, ParseError> {
     if s.is_empty() {
-        Err(ParseError::MissingValue)
+        return Err(ParseError::MissingValue);
+    }
+
+    // Values are split before being decoded so that percent-encoded commas (`%2C`) are kept as
+    // part of a value.
+    if s.contains(value::DELIMITER) {
+        s.split(value::DELIMITER)
+            .map(|t| {
+                percent_decode(t)
+                    .map(String::from)
+                    .map_err(ParseError::InvalidValue)
+            })
+            .collect::<Result<_, _>>()
+            .map(Value::Array)
     } else {
-        percent_decode(s).map_err(ParseError::InvalidValue)
+        percent_decode(s)
+            .map(|t| Value::String(t.into()))
+            .map_err(ParseError::InvalidValue)
     }
 }
 
@@ -179,6 +198,9 @@ mod tests {
 
         let entry = Entry::new("%s", "13,21");
         assert_eq!(entry.to_string(), "%25s=13%2C21");
+
+        let entry = Entry::new("Parent", vec![String::from("tx0"), String::from("tx1")]);
+        assert_eq!(entry.to_string(), "Parent=tx0,tx1");
     }
 
     #[test]
@@ -188,6 +210,13 @@ mod tests {
             Ok(Entry::new("gene_name", "gene0"))
         );
         assert_eq!("%25s=13%2C21".parse(), Ok(Entry::new("%s", "13,21")));
+        assert_eq!(
+            "Parent=tx0,tx%2C1".parse(),
+            Ok(Entry::new(
+                "Parent",
+                vec![String::from("tx0"), String::from("tx,1")]
+            ))
+        );
 
         assert_eq!("".parse::<Entry>(), Err(ParseError::Empty));
         assert_eq!("gene_name".parse::<Entry>(), Err(ParseError::Invalid));
@@ -0,0 +1,145 @@
+//! GFF record attribute entry value.
+
+use std::{fmt, slice};
+
+use super::percent_encode;
+
+pub(super) const DELIMITER: char = ',';
+
+/// A GFF record attribute entry value.
+///
+/// A value is either a single string or a list of strings. In the raw form, multiple values are
+/// separated by commas (`,`), e.g., `Parent=tx0,tx1`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    /// A single string.
+    String(String),
+    /// A list of strings.
+    Array(Vec<String>),
+}
+
+impl Value {
+    /// Returns the value as a string, if it is a single string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::attributes::entry::Value;
+    ///
+    /// let value = Value::from("gene0");
+    /// assert_eq!(value.as_string(), Some("gene0"));
+    ///
+    /// let value = Value::from(vec![String::from("tx0"), String::from("tx1")]);
+    /// assert!(value.as_string().is_none());
+    /// ```
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Array(_) => None,
+        }
+    }
+
+    /// Returns the value as a list of strings, if it is an array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::attributes::entry::Value;
+    ///
+    /// let value = Value::from(vec![String::from("tx0"), String::from("tx1")]);
+    /// assert_eq!(value.as_array(), Some(&[String::from("tx0"), String::from("tx1")][..]));
+    ///
+    /// let value = Value::from("gene0");
+    /// assert!(value.as_array().is_none());
+    /// ```
+    pub fn as_array(&self) -> Option<&[String]> {
+        match self {
+            Self::String(_) => None,
+            Self::Array(values) => Some(values),
+        }
+    }
+
+    /// Returns an iterator over the values.
+    ///
+    /// A single string is treated as a list with one value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::attributes::entry::Value;
+    ///
+    /// let value = Value::from("gene0");
+    /// assert_eq!(value.iter().collect::<Vec<_>>(), ["gene0"]);
+    ///
+    /// let value = Value::from(vec![String::from("tx0"), String::from("tx1")]);
+    /// assert_eq!(value.iter().collect::<Vec<_>>(), ["tx0", "tx1"]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_> {
+        let values = match self {
+            Self::String(s) => slice::from_ref(s),
+            Self::Array(values) => values,
+        };
+
+        Iter(values.iter())
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{DELIMITER}")?;
+            }
+
+            write!(f, "{}", percent_encode(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Self::String(s.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<Vec<String>> for Value {
+    fn from(values: Vec<String>) -> Self {
+        Self::Array(values)
+    }
+}
+
+/// An iterator over the values of a GFF record attribute entry value.
+///
+/// This is created by calling [`Value::iter`].
+pub struct Iter<'a>(slice::Iter<'a, String>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Value::from("gene0").to_string(), "gene0");
+        assert_eq!(Value::from("13,21").to_string(), "13%2C21");
+        assert_eq!(
+            Value::from(vec![String::from("tx0"), String::from("tx,1")]).to_string(),
+            "tx0,tx%2C1"
+        );
+    }
+}
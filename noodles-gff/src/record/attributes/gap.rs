@@ -0,0 +1,138 @@
+//! GFF record attribute gap.
+
+pub mod operation;
+
+pub use self::operation::Operation;
+
+use std::{error, fmt, ops::Deref, str::FromStr};
+
+use super::entry::Value;
+
+const DELIMITER: char = ' ';
+
+/// A GFF record attribute gap (`Gap`).
+///
+/// This is a list of alignment operations in the CIGAR-like format described by the GFF3
+/// specification, e.g., `M8 D3 M6 I1 M6`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Gap(Vec<Operation>);
+
+impl Deref for Gap {
+    type Target = [Operation];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for Gap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, op) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{DELIMITER}")?;
+            }
+
+            write!(f, "{op}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Vec<Operation>> for Gap {
+    fn from(ops: Vec<Operation>) -> Self {
+        Self(ops)
+    }
+}
+
+impl From<Gap> for Value {
+    fn from(gap: Gap) -> Self {
+        Self::String(gap.to_string())
+    }
+}
+
+/// An error returned when a raw GFF record attribute gap fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// An operation is invalid.
+    InvalidOperation(operation::ParseError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidOperation(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::InvalidOperation(_) => f.write_str("invalid operation"),
+        }
+    }
+}
+
+impl FromStr for Gap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        s.split(DELIMITER)
+            .map(|t| t.parse())
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::from)
+            .map_err(ParseError::InvalidOperation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        use operation::Kind;
+
+        let gap = Gap::from(vec![
+            Operation::new(Kind::Match, 8),
+            Operation::new(Kind::Deletion, 3),
+            Operation::new(Kind::Match, 6),
+        ]);
+
+        assert_eq!(gap.to_string(), "M8 D3 M6");
+    }
+
+    #[test]
+    fn test_from_str() {
+        use operation::Kind;
+
+        assert_eq!(
+            "M8 D3 M6 I1 M6".parse(),
+            Ok(Gap::from(vec![
+                Operation::new(Kind::Match, 8),
+                Operation::new(Kind::Deletion, 3),
+                Operation::new(Kind::Match, 6),
+                Operation::new(Kind::Insertion, 1),
+                Operation::new(Kind::Match, 6),
+            ]))
+        );
+
+        assert_eq!("".parse::<Gap>(), Err(ParseError::Empty));
+        assert!(matches!(
+            "M8 X3".parse::<Gap>(),
+            Err(ParseError::InvalidOperation(_))
+        ));
+    }
+}
@@ -0,0 +1,36 @@
+//! GFF record attribute reserved tags.
+//!
+//! Tags beginning with an uppercase letter are reserved by the GFF3 specification.
+
+/// Feature ID (`ID`).
+pub const ID: &str = "ID";
+
+/// Display name (`Name`).
+pub const NAME: &str = "Name";
+
+/// Secondary names (`Alias`).
+pub const ALIAS: &str = "Alias";
+
+/// Parent feature IDs (`Parent`).
+pub const PARENT: &str = "Parent";
+
+/// Alignment target (`Target`).
+pub const TARGET: &str = "Target";
+
+/// Alignment of a feature to a target (`Gap`).
+pub const GAP: &str = "Gap";
+
+/// Temporal relationship with another feature (`Derives_from`).
+pub const DERIVES_FROM: &str = "Derives_from";
+
+/// Free text notes (`Note`).
+pub const NOTE: &str = "Note";
+
+/// Database cross references (`Dbxref`).
+pub const DBXREF: &str = "Dbxref";
+
+/// Ontology term cross references (`Ontology_term`).
+pub const ONTOLOGY_TERM: &str = "Ontology_term";
+
+/// Whether the sequence is circular (`Is_circular`).
+pub const IS_CIRCULAR: &str = "Is_circular";
@@ -0,0 +1,278 @@
+//! GFF record attribute target.
+
+use std::{error, fmt, str::FromStr};
+
+use noodles_core::{position, Position};
+
+use super::entry::Value;
+use crate::record::{strand, Strand};
+
+const DELIMITER: char = ' ';
+
+/// A GFF record attribute target (`Target`).
+///
+/// This indicates the target of a nucleotide-to-nucleotide or protein-to-nucleotide alignment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Target {
+    id: String,
+    start: Position,
+    end: Position,
+    strand: Option<Strand>,
+}
+
+impl Target {
+    /// Creates a GFF record attribute target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_gff::record::attributes::Target;
+    ///
+    /// let target = Target::new(
+    ///     String::from("EST23"),
+    ///     Position::try_from(8)?,
+    ///     Position::try_from(13)?,
+    ///     None,
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new(id: String, start: Position, end: Position, strand: Option<Strand>) -> Self {
+        Self {
+            id,
+            start,
+            end,
+            strand,
+        }
+    }
+
+    /// Returns the target ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::attributes::Target;
+    /// let target: Target = "EST23 8 13".parse()?;
+    /// assert_eq!(target.id(), "EST23");
+    /// # Ok::<_, noodles_gff::record::attributes::target::ParseError>(())
+    /// ```
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_gff::record::attributes::Target;
+    /// let target: Target = "EST23 8 13".parse()?;
+    /// assert_eq!(target.start(), Position::try_from(8)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_gff::record::attributes::Target;
+    /// let target: Target = "EST23 8 13".parse()?;
+    /// assert_eq!(target.end(), Position::try_from(13)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the strand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::{attributes::Target, Strand};
+    ///
+    /// let target: Target = "EST23 8 13".parse()?;
+    /// assert!(target.strand().is_none());
+    ///
+    /// let target: Target = "EST23 8 13 -".parse()?;
+    /// assert_eq!(target.strand(), Some(Strand::Reverse));
+    /// # Ok::<_, noodles_gff::record::attributes::target::ParseError>(())
+    /// ```
+    pub fn strand(&self) -> Option<Strand> {
+        self.strand
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{DELIMITER}{}{DELIMITER}{}",
+            self.id, self.start, self.end
+        )?;
+
+        if let Some(strand) = self.strand {
+            write!(f, "{DELIMITER}{strand}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Target> for Value {
+    fn from(target: Target) -> Self {
+        Self::String(target.to_string())
+    }
+}
+
+/// An error returned when a raw GFF record attribute target fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// The ID is missing.
+    MissingId,
+    /// The start is missing.
+    MissingStart,
+    /// The start is invalid.
+    InvalidStart(position::ParseError),
+    /// The end is missing.
+    MissingEnd,
+    /// The end is invalid.
+    InvalidEnd(position::ParseError),
+    /// The strand is invalid.
+    InvalidStrand(strand::ParseError),
+    /// The input has unexpected trailing data.
+    ExpectedEof,
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidStart(e) | Self::InvalidEnd(e) => Some(e),
+            Self::InvalidStrand(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::MissingId => f.write_str("missing ID"),
+            Self::MissingStart => f.write_str("missing start"),
+            Self::InvalidStart(_) => f.write_str("invalid start"),
+            Self::MissingEnd => f.write_str("missing end"),
+            Self::InvalidEnd(_) => f.write_str("invalid end"),
+            Self::InvalidStrand(_) => f.write_str("invalid strand"),
+            Self::ExpectedEof => f.write_str("expected EOF"),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut fields = s.split(DELIMITER);
+
+        let id = fields
+            .next()
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .ok_or(ParseError::MissingId)?;
+
+        let start = fields
+            .next()
+            .ok_or(ParseError::MissingStart)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidStart))?;
+
+        let end = fields
+            .next()
+            .ok_or(ParseError::MissingEnd)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidEnd))?;
+
+        let strand = fields
+            .next()
+            .map(|t| t.parse().map_err(ParseError::InvalidStrand))
+            .transpose()?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::ExpectedEof);
+        }
+
+        Ok(Self::new(id, start, end, strand))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), noodles_core::position::TryFromIntError> {
+        let target = Target::new(
+            String::from("EST23"),
+            Position::try_from(8)?,
+            Position::try_from(13)?,
+            None,
+        );
+        assert_eq!(target.to_string(), "EST23 8 13");
+
+        let target = Target::new(
+            String::from("EST23"),
+            Position::try_from(8)?,
+            Position::try_from(13)?,
+            Some(Strand::Forward),
+        );
+        assert_eq!(target.to_string(), "EST23 8 13 +");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), noodles_core::position::TryFromIntError> {
+        assert_eq!(
+            "EST23 8 13 +".parse(),
+            Ok(Target::new(
+                String::from("EST23"),
+                Position::try_from(8)?,
+                Position::try_from(13)?,
+                Some(Strand::Forward),
+            ))
+        );
+
+        assert_eq!("".parse::<Target>(), Err(ParseError::Empty));
+        assert_eq!(" 8 13".parse::<Target>(), Err(ParseError::MissingId));
+        assert_eq!("EST23".parse::<Target>(), Err(ParseError::MissingStart));
+        assert!(matches!(
+            "EST23 0 13".parse::<Target>(),
+            Err(ParseError::InvalidStart(_))
+        ));
+        assert_eq!("EST23 8".parse::<Target>(), Err(ParseError::MissingEnd));
+        assert!(matches!(
+            "EST23 8 13 !".parse::<Target>(),
+            Err(ParseError::InvalidStrand(_))
+        ));
+        assert_eq!(
+            "EST23 8 13 + x".parse::<Target>(),
+            Err(ParseError::ExpectedEof)
+        );
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use std::io::{self, Write};
 
+use noodles_fasta as fasta;
+
 use super::{Directive, Line, Record};
 
 /// A GFF writer.
@@ -113,4 +115,96 @@ where
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
         writeln!(self.inner, "{record}")
     }
+
+    /// Writes an embedded FASTA section.
+    ///
+    /// This writes the `FASTA` directive followed by the given FASTA records. It should be the
+    /// last section written, as no other GFF lines are allowed after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+    /// use noodles_gff as gff;
+    ///
+    /// let mut writer = gff::Writer::new(Vec::new());
+    ///
+    /// let records = [fasta::Record::new(
+    ///     Definition::new("sq0", None),
+    ///     Sequence::from(b"ACGT".to_vec()),
+    /// )];
+    ///
+    /// writer.write_fasta(&records)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"##FASTA\n>sq0\nACGT\n");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_fasta(&mut self, records: &[fasta::Record]) -> io::Result<()> {
+        self.write_directive(&Directive::StartOfFasta)?;
+
+        let mut writer = fasta::Writer::new(&mut self.inner);
+
+        for record in records {
+            writer.write_record(record)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_record_with_multi_valued_attributes() -> Result<(), Box<dyn std::error::Error>> {
+        let line = "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tID=exon0;Parent=tx0,tx1;Note=A%2C B";
+        let record: Record = line.parse()?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_record(&record)?;
+
+        let expected = format!("{line}\n");
+        assert_eq!(writer.get_ref(), expected.as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_fasta_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_fasta::record::{Definition, Sequence};
+
+        use crate::Reader;
+
+        let record: Record = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?;
+
+        let fasta_records = vec![
+            fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"ACGTACGT".to_vec()),
+            ),
+            fasta::Record::new(
+                Definition::new("sq1", Some(String::from("noodles"))),
+                Sequence::from(b"NNNN".to_vec()),
+            ),
+        ];
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_directive(&Directive::GffVersion(Default::default()))?;
+        writer.write_record(&record)?;
+        writer.write_fasta(&fasta_records)?;
+
+        let data = writer.get_ref();
+        let mut reader = Reader::new(&data[..]);
+
+        let records = reader.records().collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(records, [record]);
+
+        let mut reader = reader.into_fasta_reader();
+        let actual = reader.records().collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(actual, fasta_records);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,26 @@
+//! Builds and writes a tabix index from a bgzipped GTF file.
+//!
+//! This writes the output to stdout rather than `<src>.tbi`.
+//!
+//! The output is similar to the output of `tabix --preset gff <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_gtf as gtf;
+use noodles_tabix as tabix;
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let index = gtf::index(src)?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = tabix::Writer::new(BufWriter::new(stdout));
+
+    writer.write_index(&index)?;
+
+    Ok(())
+}
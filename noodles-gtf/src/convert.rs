@@ -0,0 +1,189 @@
+//! GTF and GFF3 conversion.
+//!
+//! GTF groups features by the `gene_id` and `transcript_id` attributes, whereas GFF3 links
+//! features using the `ID` and `Parent` attributes. The converters in this module map between the
+//! two representations.
+//!
+//! # Unmappable data
+//!
+//! Some records and attributes cannot be represented in the other format. How these are handled
+//! is set by an [`UnmappablePolicy`].
+//!
+//! When converting from GTF to GFF3,
+//!
+//!   * records missing a `gene_id` are unmappable;
+//!   * genes with records on more than one reference sequence are unmappable; and
+//!   * attributes with the keys `ID` or `Parent` are unmappable, as these are set by the
+//!     converter.
+//!
+//! When converting from GFF3 to GTF,
+//!
+//!   * records that cannot be placed in a gene (root) → transcript → feature hierarchy are
+//!     unmappable, e.g., a root feature with no children or a gene or transcript without an ID;
+//!     and
+//!   * attributes with keys or values that cannot be written in GTF (e.g., containing `"` or `;`)
+//!     are unmappable.
+
+mod from_gff;
+mod to_gff;
+
+pub use self::{from_gff::from_gff, to_gff::to_gff};
+
+use std::{error, fmt};
+
+use noodles_gff as gff;
+
+use crate::record::{Frame, Strand};
+
+/// The policy for handling records and attributes that cannot be converted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UnmappablePolicy {
+    /// Return an error.
+    #[default]
+    Error,
+    /// Silently drop the record or attribute.
+    Discard,
+}
+
+/// An error returned when a conversion fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConvertError {
+    /// A GTF record is missing a gene ID (`gene_id`).
+    MissingGeneId,
+    /// A GTF gene has records on more than one reference sequence.
+    ///
+    /// This includes the gene ID.
+    MultipleReferenceSequences(String),
+    /// A record cannot be placed in a gene hierarchy.
+    ///
+    /// This includes the feature type.
+    UnmappableRecord(String),
+    /// An attribute cannot be converted.
+    ///
+    /// This includes the attribute key.
+    UnmappableAttribute(String),
+    /// The GFF3 records do not form a valid feature graph.
+    InvalidFeatureGraph(gff::feature_graph::BuildError),
+}
+
+impl error::Error for ConvertError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidFeatureGraph(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingGeneId => f.write_str("missing gene ID"),
+            Self::MultipleReferenceSequences(id) => {
+                write!(f, "gene on multiple reference sequences: {id}")
+            }
+            Self::UnmappableRecord(ty) => write!(f, "unmappable record: {ty}"),
+            Self::UnmappableAttribute(key) => write!(f, "unmappable attribute: {key}"),
+            Self::InvalidFeatureGraph(_) => f.write_str("invalid feature graph"),
+        }
+    }
+}
+
+impl UnmappablePolicy {
+    // Returns `Ok(())` if the unmappable data is to be discarded.
+    fn handle(self, error: ConvertError) -> Result<(), ConvertError> {
+        match self {
+            Self::Error => Err(error),
+            Self::Discard => Ok(()),
+        }
+    }
+}
+
+fn frame_to_phase(frame: Frame) -> gff::record::Phase {
+    use gff::record::Phase;
+
+    match u8::from(frame) {
+        0 => Phase::Zero,
+        1 => Phase::One,
+        _ => Phase::Two,
+    }
+}
+
+fn phase_to_frame(phase: gff::record::Phase) -> Frame {
+    use gff::record::Phase;
+
+    let n = match phase {
+        Phase::Zero => 0,
+        Phase::One => 1,
+        Phase::Two => 2,
+    };
+
+    // SAFETY: `n` is in 0..=2.
+    Frame::try_from(n).unwrap()
+}
+
+fn strand_to_gff(strand: Option<Strand>) -> gff::record::Strand {
+    match strand {
+        Some(Strand::Forward) => gff::record::Strand::Forward,
+        Some(Strand::Reverse) => gff::record::Strand::Reverse,
+        None => gff::record::Strand::None,
+    }
+}
+
+fn strand_from_gff(strand: gff::record::Strand) -> Option<Strand> {
+    match strand {
+        gff::record::Strand::Forward => Some(Strand::Forward),
+        gff::record::Strand::Reverse => Some(Strand::Reverse),
+        gff::record::Strand::None | gff::record::Strand::Unknown => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let data = "sq0\tNOODLES\tgene\t8\t55\t.\t+\t.\tgene_id \"g0\"; gene_name \"NDLS\";
+sq0\tNOODLES\ttranscript\t8\t55\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\"; tag \"basic\"; tag \"CCDS\";
+sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\texon\t34\t55\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\tCDS\t34\t55\t.\t+\t2\tgene_id \"g0\"; transcript_id \"t0\";";
+
+        let records: Vec<crate::Record> = data
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
+
+        let gff_records = to_gff(records.clone(), UnmappablePolicy::Error)?;
+        let actual = from_gff(gff_records, UnmappablePolicy::Error)?;
+
+        assert_eq!(actual, records);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip_with_duplicate_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let data = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\";
+sq0\tNOODLES\ttranscript\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\tgene\t21\t34\t.\t+\t.\tgene_id \"g1\";
+sq0\tNOODLES\ttranscript\t21\t34\t.\t+\t.\tgene_id \"g1\"; transcript_id \"t0\";
+sq0\tNOODLES\texon\t21\t34\t.\t+\t.\tgene_id \"g1\"; transcript_id \"t0\";";
+
+        let records: Vec<crate::Record> = data
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
+
+        let gff_records = to_gff(records.clone(), UnmappablePolicy::Error)?;
+        assert_eq!(gff_records[4].attributes().id(), Some("t0_1"));
+
+        let actual = from_gff(gff_records, UnmappablePolicy::Error)?;
+        assert_eq!(actual, records);
+
+        Ok(())
+    }
+}
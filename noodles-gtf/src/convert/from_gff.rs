@@ -0,0 +1,303 @@
+use noodles_gff::{self as gff, feature_graph::Feature, record::attributes::tag, FeatureGraph};
+
+use super::{phase_to_frame, strand_from_gff, ConvertError, UnmappablePolicy};
+use crate::{
+    record::{attributes::Entry, Attributes},
+    Record,
+};
+
+const GENE_ID: &str = "gene_id";
+const TRANSCRIPT_ID: &str = "transcript_id";
+
+/// Converts GFF3 records to GTF records.
+///
+/// The GFF3 records are first linked into a feature graph using their `ID` and `Parent`
+/// attributes. Root features with children are genes, their children are transcripts, and the
+/// children of transcripts are transcript features (e.g., exons and CDS). The `gene_id` of the gene
+/// and `transcript_id` of the transcript are used as the GTF `gene_id` and `transcript_id`,
+/// respectively. If a gene or transcript does not have this attribute, its `ID` is used instead.
+///
+/// Feature types are kept as is. A feature with multiple parents is written once per parent,
+/// and a discontiguous feature is written once per segment. Multi-valued attributes are written
+/// as repeated keys. The GFF3 phase is mapped to the GTF frame.
+///
+/// Features are written in the order they first appear in the input, and all records of a
+/// feature are written together. This means the output is not necessarily in input order, e.g.,
+/// when the segments of a discontiguous feature are not adjacent.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_gff as gff;
+/// use noodles_gtf::convert::{self, UnmappablePolicy};
+///
+/// let records: Vec<gff::Record> = [
+///     "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=g0",
+///     "sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=t0;Parent=g0",
+///     "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=t0",
+/// ]
+/// .iter()
+/// .map(|s| s.parse())
+/// .collect::<Result<_, _>>()?;
+///
+/// let records = convert::from_gff(records, UnmappablePolicy::Error)?;
+///
+/// assert_eq!(
+///     records[2].to_string(),
+///     "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";",
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn from_gff<I>(records: I, policy: UnmappablePolicy) -> Result<Vec<Record>, ConvertError>
+where
+    I: IntoIterator<Item = gff::Record>,
+{
+    let mut builder = FeatureGraph::builder();
+
+    for record in records {
+        builder
+            .add_record(record)
+            .map_err(ConvertError::InvalidFeatureGraph)?;
+    }
+
+    let graph = builder.build().map_err(ConvertError::InvalidFeatureGraph)?;
+
+    let mut gtf_records = Vec::new();
+
+    for feature in graph.features() {
+        let contexts = match resolve_contexts(&graph, feature) {
+            Some(contexts) => contexts,
+            None => {
+                policy.handle(ConvertError::UnmappableRecord(feature.ty().into()))?;
+                continue;
+            }
+        };
+
+        for (gene_id, transcript_id) in contexts {
+            for record in feature.records() {
+                gtf_records.push(convert_record(record, gene_id, transcript_id, policy)?);
+            }
+        }
+    }
+
+    Ok(gtf_records)
+}
+
+// Returns the (gene ID, transcript ID) pairs the feature belongs to.
+fn resolve_contexts<'g>(
+    graph: &'g FeatureGraph,
+    feature: &'g Feature,
+) -> Option<Vec<(&'g str, Option<&'g str>)>> {
+    let mut parents = graph.parents(feature).peekable();
+
+    if parents.peek().is_none() {
+        // Gene
+        graph.children(feature).next()?;
+
+        return gene_id(feature).map(|id| vec![(id, None)]);
+    }
+
+    let mut contexts = Vec::new();
+
+    for parent in parents {
+        let mut grandparents = graph.parents(parent).peekable();
+
+        if grandparents.peek().is_none() {
+            // Transcript
+            let gene_id = gene_id(parent)?;
+            let transcript_id = transcript_id(feature)?;
+            contexts.push((gene_id, Some(transcript_id)));
+        } else {
+            // Transcript feature
+            for grandparent in grandparents {
+                if graph.parents(grandparent).next().is_some() {
+                    return None;
+                }
+
+                let gene_id = gene_id(grandparent)?;
+                let transcript_id = transcript_id(parent)?;
+                contexts.push((gene_id, Some(transcript_id)));
+            }
+        }
+    }
+
+    Some(contexts)
+}
+
+fn gene_id(feature: &Feature) -> Option<&str> {
+    get(feature, GENE_ID).or_else(|| feature.id())
+}
+
+fn transcript_id(feature: &Feature) -> Option<&str> {
+    get(feature, TRANSCRIPT_ID).or_else(|| feature.id())
+}
+
+fn get<'f>(feature: &'f Feature, key: &str) -> Option<&'f str> {
+    feature
+        .records()
+        .iter()
+        .find_map(|record| record.attributes().get(key))
+        .and_then(|value| value.as_string())
+}
+
+fn convert_record(
+    record: &gff::Record,
+    gene_id: &str,
+    transcript_id: Option<&str>,
+    policy: UnmappablePolicy,
+) -> Result<Record, ConvertError> {
+    let mut entries = vec![Entry::new(GENE_ID, gene_id)];
+
+    if let Some(transcript_id) = transcript_id {
+        entries.push(Entry::new(TRANSCRIPT_ID, transcript_id));
+    }
+
+    for entry in record.attributes().iter() {
+        let key = entry.key();
+
+        if matches!(key, tag::ID | tag::PARENT | GENE_ID | TRANSCRIPT_ID) {
+            continue;
+        }
+
+        if !is_valid_key(key) {
+            policy.handle(ConvertError::UnmappableAttribute(key.into()))?;
+            continue;
+        }
+
+        for value in entry.value().iter() {
+            if is_valid_value(value) {
+                entries.push(Entry::new(key, value));
+            } else {
+                policy.handle(ConvertError::UnmappableAttribute(key.into()))?;
+            }
+        }
+    }
+
+    let mut builder = Record::builder()
+        .set_reference_sequence_name(record.reference_sequence_name())
+        .set_source(record.source())
+        .set_type(record.ty())
+        .set_start(record.start())
+        .set_end(record.end())
+        .set_attributes(Attributes::from(entries));
+
+    if let Some(score) = record.score() {
+        builder = builder.set_score(score);
+    }
+
+    if let Some(strand) = strand_from_gff(record.strand()) {
+        builder = builder.set_strand(strand);
+    }
+
+    if let Some(phase) = record.phase() {
+        builder = builder.set_frame(phase_to_frame(phase));
+    }
+
+    Ok(builder.build())
+}
+
+fn is_valid_key(s: &str) -> bool {
+    !s.is_empty() && !s.contains(|c: char| c.is_ascii_whitespace() || matches!(c, '"' | ';'))
+}
+
+fn is_valid_value(s: &str) -> bool {
+    !s.contains(['"', ';', '\n'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Vec<gff::Record>, gff::record::ParseError> {
+        s.lines().map(|line| line.parse()).collect()
+    }
+
+    #[test]
+    fn test_from_gff() -> Result<(), Box<dyn std::error::Error>> {
+        let records = parse(
+            "sq0\tNOODLES\tgene\t8\t55\t.\t+\t.\tID=g0;Name=NDLS
+sq0\tNOODLES\tmRNA\t8\t55\t.\t+\t.\tID=t0;Parent=g0
+sq0\tNOODLES\tmRNA\t8\t34\t.\t+\t.\tID=t1;Parent=g0
+sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=t0,t1;Dbxref=A:1,B:2
+sq0\tNOODLES\tCDS\t34\t55\t.\t+\t1\tID=cds0;Parent=t0
+sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0;Parent=t0",
+        )?;
+
+        let actual = from_gff(records, UnmappablePolicy::Error)?;
+        let actual: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+
+        let expected = [
+            "sq0\tNOODLES\tgene\t8\t55\t.\t+\t.\tgene_id \"g0\"; Name \"NDLS\";",
+            "sq0\tNOODLES\tmRNA\t8\t55\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";",
+            "sq0\tNOODLES\tmRNA\t8\t34\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t1\";",
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\"; Dbxref \"A:1\"; Dbxref \"B:2\";",
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t1\"; Dbxref \"A:1\"; Dbxref \"B:2\";",
+            "sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tgene_id \"g0\"; transcript_id \"t0\";",
+            "sq0\tNOODLES\tCDS\t34\t55\t.\t+\t1\tgene_id \"g0\"; transcript_id \"t0\";",
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_gff_with_gtf_id_attributes() -> Result<(), Box<dyn std::error::Error>> {
+        let records = parse(
+            "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene:g0;gene_id=g0
+sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=transcript:t0;Parent=gene:g0;transcript_id=t0
+sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=transcript:t0",
+        )?;
+
+        let actual = from_gff(records, UnmappablePolicy::Error)?;
+        let actual: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+
+        let expected = [
+            "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\";",
+            "sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";",
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";",
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_gff_with_unmappable_data() -> Result<(), Box<dyn std::error::Error>> {
+        let records = parse(
+            "sq0\tNOODLES\tregion\t1\t100\t.\t.\t.\tID=sq0
+sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=g0;Note=A%3B B
+sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=t0;Parent=g0",
+        )?;
+
+        assert_eq!(
+            from_gff(records.clone(), UnmappablePolicy::Error),
+            Err(ConvertError::UnmappableRecord(String::from("region")))
+        );
+
+        assert_eq!(
+            from_gff(records[1..].to_vec(), UnmappablePolicy::Error),
+            Err(ConvertError::UnmappableAttribute(String::from("Note")))
+        );
+
+        let actual = from_gff(records, UnmappablePolicy::Discard)?;
+        let actual: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+
+        assert_eq!(
+            actual,
+            [
+                "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\";",
+                "sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";",
+            ]
+        );
+
+        let records = parse("sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=t0")?;
+        assert!(matches!(
+            from_gff(records, UnmappablePolicy::Discard),
+            Err(ConvertError::InvalidFeatureGraph(_))
+        ));
+
+        Ok(())
+    }
+}
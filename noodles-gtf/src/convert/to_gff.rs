@@ -0,0 +1,436 @@
+use std::collections::{HashMap, HashSet};
+
+use noodles_core::Position;
+use noodles_gff::{
+    self as gff,
+    record::attributes::{entry::Value, tag, Entry},
+};
+
+use super::{frame_to_phase, strand_to_gff, ConvertError, UnmappablePolicy};
+use crate::Record;
+
+const GENE_ID: &str = "gene_id";
+const TRANSCRIPT_ID: &str = "transcript_id";
+
+const GENE: &str = "gene";
+const TRANSCRIPT: &str = "transcript";
+
+#[derive(Default)]
+struct Transcript {
+    record: Option<Record>,
+    children: Vec<Record>,
+}
+
+#[derive(Default)]
+struct Gene {
+    record: Option<Record>,
+    children: Vec<Record>,
+    transcripts: Vec<(String, Transcript)>,
+    transcript_indices: HashMap<String, usize>,
+}
+
+impl Gene {
+    fn records(&self) -> impl Iterator<Item = &Record> {
+        self.record.iter().chain(self.children.iter()).chain(
+            self.transcripts.iter().flat_map(|(_, transcript)| {
+                transcript.record.iter().chain(transcript.children.iter())
+            }),
+        )
+    }
+}
+
+/// Converts GTF records to GFF3 records.
+///
+/// Records are grouped by gene (`gene_id`) and transcript (`transcript_id`), in the order they
+/// first appear. Each gene is written before its transcripts, and each transcript is written
+/// before its children.
+///
+/// A gene or transcript feature (i.e., a record with the type `gene` or `transcript`) is
+/// synthesized when it is not in the input. Its span covers all of its records. Genes and
+/// transcripts are given an `ID` of their respective GTF IDs, and children are linked using
+/// `Parent`. A record with a `gene_id` but no `transcript_id` is a child of the gene.
+///
+/// GFF3 IDs must be unique, but a GTF transcript ID can be the same as a gene ID or be used by
+/// more than one gene. Such a transcript is given an `ID` with a numeric suffix (e.g., `g0_1`).
+/// Its `transcript_id` attribute is kept as is.
+///
+/// All GTF attributes are kept. Repeated keys are merged into a single multi-valued attribute.
+/// The GTF frame is mapped to the GFF3 phase.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_gtf::{self as gtf, convert::{self, UnmappablePolicy}};
+///
+/// let record: gtf::Record =
+///     "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";".parse()?;
+///
+/// let records = convert::to_gff([record], UnmappablePolicy::Error)?;
+/// let types: Vec<_> = records.iter().map(|record| record.ty()).collect();
+/// assert_eq!(types, ["gene", "transcript", "exon"]);
+///
+/// assert_eq!(records[1].attributes().id(), Some("t0"));
+///
+/// let parents: Option<Vec<_>> = records[2].attributes().parent().map(|ids| ids.collect());
+/// assert_eq!(parents, Some(vec!["t0"]));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn to_gff<I>(records: I, policy: UnmappablePolicy) -> Result<Vec<gff::Record>, ConvertError>
+where
+    I: IntoIterator<Item = Record>,
+{
+    let mut genes: Vec<(String, Gene)> = Vec::new();
+    let mut gene_indices: HashMap<String, usize> = HashMap::new();
+
+    for record in records {
+        let gene_id = match get(&record, GENE_ID) {
+            Some(id) => id.to_string(),
+            None => {
+                policy.handle(ConvertError::MissingGeneId)?;
+                continue;
+            }
+        };
+
+        let i = *gene_indices.entry(gene_id.clone()).or_insert_with(|| {
+            genes.push((gene_id, Gene::default()));
+            genes.len() - 1
+        });
+
+        let gene = &mut genes[i].1;
+
+        match get(&record, TRANSCRIPT_ID).filter(|id| !id.is_empty()) {
+            Some(transcript_id) => {
+                let transcript_id = transcript_id.to_string();
+
+                let j = *gene
+                    .transcript_indices
+                    .entry(transcript_id.clone())
+                    .or_insert_with(|| {
+                        gene.transcripts
+                            .push((transcript_id, Transcript::default()));
+                        gene.transcripts.len() - 1
+                    });
+
+                let transcript = &mut gene.transcripts[j].1;
+
+                if record.ty() == TRANSCRIPT && transcript.record.is_none() {
+                    transcript.record = Some(record);
+                } else {
+                    transcript.children.push(record);
+                }
+            }
+            None => {
+                if record.ty() == GENE && gene.record.is_none() {
+                    gene.record = Some(record);
+                } else {
+                    gene.children.push(record);
+                }
+            }
+        }
+    }
+
+    let mut ids: HashSet<String> = genes.iter().map(|(id, _)| id.clone()).collect();
+    let mut gff_records = Vec::new();
+
+    for (gene_id, gene) in genes {
+        if !is_on_single_reference_sequence(&gene) {
+            policy.handle(ConvertError::MultipleReferenceSequences(gene_id))?;
+            continue;
+        }
+
+        let gene_record = match &gene.record {
+            Some(record) => convert_record(record, Some(&gene_id), None, policy)?,
+            None => synthesize_gene(&gene_id, &gene),
+        };
+
+        gff_records.push(gene_record);
+
+        for record in &gene.children {
+            gff_records.push(convert_record(record, None, Some(&gene_id), policy)?);
+        }
+
+        for (transcript_id, transcript) in &gene.transcripts {
+            let id = unique_id(&mut ids, transcript_id);
+
+            let transcript_record = match &transcript.record {
+                Some(record) => convert_record(record, Some(&id), Some(&gene_id), policy)?,
+                None => synthesize_transcript(&id, &gene_id, transcript_id, transcript),
+            };
+
+            gff_records.push(transcript_record);
+
+            for record in &transcript.children {
+                gff_records.push(convert_record(record, None, Some(&id), policy)?);
+            }
+        }
+    }
+
+    Ok(gff_records)
+}
+
+// Returns the given ID if it is not yet used. Otherwise, the first unused ID with a numeric
+// suffix is returned.
+fn unique_id(ids: &mut HashSet<String>, id: &str) -> String {
+    let mut candidate = id.to_string();
+    let mut n = 0;
+
+    while ids.contains(&candidate) {
+        n += 1;
+        candidate = format!("{id}_{n}");
+    }
+
+    ids.insert(candidate.clone());
+
+    candidate
+}
+
+fn is_on_single_reference_sequence(gene: &Gene) -> bool {
+    let mut reference_sequence_names = gene
+        .records()
+        .map(|record| record.reference_sequence_name());
+    let first = reference_sequence_names.next();
+    reference_sequence_names.all(|name| Some(name) == first)
+}
+
+fn get<'r>(record: &'r Record, key: &str) -> Option<&'r str> {
+    record
+        .attributes()
+        .iter()
+        .find(|entry| entry.key() == key)
+        .map(|entry| entry.value())
+}
+
+fn convert_record(
+    record: &Record,
+    id: Option<&str>,
+    parent: Option<&str>,
+    policy: UnmappablePolicy,
+) -> Result<gff::Record, ConvertError> {
+    let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+
+    if let Some(id) = id {
+        entries.push((tag::ID.into(), vec![id.into()]));
+    }
+
+    if let Some(parent) = parent {
+        entries.push((tag::PARENT.into(), vec![parent.into()]));
+    }
+
+    for entry in record.attributes().iter() {
+        let key = entry.key();
+
+        if key == tag::ID || key == tag::PARENT {
+            policy.handle(ConvertError::UnmappableAttribute(key.into()))?;
+            continue;
+        }
+
+        match entries.iter_mut().find(|(k, _)| k == key) {
+            Some((_, values)) => values.push(entry.value().into()),
+            None => entries.push((key.into(), vec![entry.value().into()])),
+        }
+    }
+
+    let attributes = build_attributes(entries);
+
+    let mut builder = gff::Record::builder()
+        .set_reference_sequence_name(record.reference_sequence_name().into())
+        .set_source(record.source().into())
+        .set_type(record.ty().into())
+        .set_start(record.start())
+        .set_end(record.end())
+        .set_strand(strand_to_gff(record.strand()))
+        .set_attributes(attributes);
+
+    if let Some(score) = record.score() {
+        builder = builder.set_score(score);
+    }
+
+    if let Some(frame) = record.frame() {
+        builder = builder.set_phase(frame_to_phase(frame));
+    }
+
+    Ok(builder.build())
+}
+
+fn build_attributes(entries: Vec<(String, Vec<String>)>) -> gff::record::Attributes {
+    entries
+        .into_iter()
+        .map(|(key, mut values)| {
+            let value = if values.len() == 1 {
+                Value::String(values.remove(0))
+            } else {
+                Value::Array(values)
+            };
+
+            Entry::new(key, value)
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn synthesize_gene(gene_id: &str, gene: &Gene) -> gff::Record {
+    let attributes = build_attributes(vec![
+        (tag::ID.into(), vec![gene_id.into()]),
+        (GENE_ID.into(), vec![gene_id.into()]),
+    ]);
+
+    synthesize(gene.records(), GENE, attributes)
+}
+
+fn synthesize_transcript(
+    id: &str,
+    gene_id: &str,
+    transcript_id: &str,
+    transcript: &Transcript,
+) -> gff::Record {
+    let attributes = build_attributes(vec![
+        (tag::ID.into(), vec![id.into()]),
+        (tag::PARENT.into(), vec![gene_id.into()]),
+        (GENE_ID.into(), vec![gene_id.into()]),
+        (TRANSCRIPT_ID.into(), vec![transcript_id.into()]),
+    ]);
+
+    synthesize(transcript.children.iter(), TRANSCRIPT, attributes)
+}
+
+// Builds a feature that spans the given records, using the first record as a template.
+fn synthesize<'r, I>(records: I, ty: &str, attributes: gff::record::Attributes) -> gff::Record
+where
+    I: IntoIterator<Item = &'r Record>,
+{
+    let mut records = records.into_iter();
+
+    // SAFETY: A synthesized feature always has at least one record.
+    let first = records.next().unwrap();
+
+    let (start, end) = records.fold((first.start(), first.end()), |(start, end), record| {
+        (start.min(record.start()), end.max(record.end()))
+    });
+
+    build_feature(first, ty, start, end, attributes)
+}
+
+fn build_feature(
+    template: &Record,
+    ty: &str,
+    start: Position,
+    end: Position,
+    attributes: gff::record::Attributes,
+) -> gff::Record {
+    gff::Record::builder()
+        .set_reference_sequence_name(template.reference_sequence_name().into())
+        .set_source(template.source().into())
+        .set_type(ty.into())
+        .set_start(start)
+        .set_end(end)
+        .set_strand(strand_to_gff(template.strand()))
+        .set_attributes(attributes)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Vec<Record>, crate::record::ParseError> {
+        s.lines().map(|line| line.parse()).collect()
+    }
+
+    #[test]
+    fn test_to_gff_synthesizes_gene_and_transcript() -> Result<(), Box<dyn std::error::Error>> {
+        let records = parse(
+            "sq0\tNOODLES\texon\t21\t34\t.\t-\t.\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\tCDS\t21\t30\t.\t-\t1\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\texon\t8\t13\t.\t-\t.\tgene_id \"g0\"; transcript_id \"t1\";",
+        )?;
+
+        let actual = to_gff(records, UnmappablePolicy::Error)?;
+
+        let expected = [
+            "sq0\tNOODLES\tgene\t8\t34\t.\t-\t.\tID=g0;gene_id=g0",
+            "sq0\tNOODLES\ttranscript\t21\t34\t.\t-\t.\tID=t0;Parent=g0;gene_id=g0;transcript_id=t0",
+            "sq0\tNOODLES\texon\t21\t34\t.\t-\t.\tParent=t0;gene_id=g0;transcript_id=t0",
+            "sq0\tNOODLES\tCDS\t21\t30\t.\t-\t1\tParent=t0;gene_id=g0;transcript_id=t0",
+            "sq0\tNOODLES\ttranscript\t8\t13\t.\t-\t.\tID=t1;Parent=g0;gene_id=g0;transcript_id=t1",
+            "sq0\tNOODLES\texon\t8\t13\t.\t-\t.\tParent=t1;gene_id=g0;transcript_id=t1",
+        ];
+
+        let actual: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_gff_with_duplicate_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let records = parse(
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"g0\";
+sq0\tNOODLES\texon\t21\t34\t.\t+\t.\tgene_id \"g1\"; transcript_id \"g0\";",
+        )?;
+
+        let actual = to_gff(records, UnmappablePolicy::Error)?;
+
+        let expected = [
+            "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=g0;gene_id=g0",
+            "sq0\tNOODLES\ttranscript\t8\t13\t.\t+\t.\tID=g0_1;Parent=g0;gene_id=g0;transcript_id=g0",
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=g0_1;gene_id=g0;transcript_id=g0",
+            "sq0\tNOODLES\tgene\t21\t34\t.\t+\t.\tID=g1;gene_id=g1",
+            "sq0\tNOODLES\ttranscript\t21\t34\t.\t+\t.\tID=g0_2;Parent=g1;gene_id=g1;transcript_id=g0",
+            "sq0\tNOODLES\texon\t21\t34\t.\t+\t.\tParent=g0_2;gene_id=g1;transcript_id=g0",
+        ];
+
+        let actual: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_gff_with_multiple_reference_sequences() -> Result<(), Box<dyn std::error::Error>> {
+        let records = parse(
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+sq1\tNOODLES\texon\t21\t34\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t1\";
+sq0\tNOODLES\texon\t55\t89\t.\t+\t.\tgene_id \"g1\"; transcript_id \"t2\";",
+        )?;
+
+        assert_eq!(
+            to_gff(records.clone(), UnmappablePolicy::Error),
+            Err(ConvertError::MultipleReferenceSequences(String::from("g0")))
+        );
+
+        let actual = to_gff(records, UnmappablePolicy::Discard)?;
+        let types: Vec<_> = actual.iter().map(|record| record.ty()).collect();
+        assert_eq!(types, ["gene", "transcript", "exon"]);
+        assert_eq!(actual[0].attributes().id(), Some("g1"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_gff_with_unmappable_data() -> Result<(), Box<dyn std::error::Error>> {
+        let records = parse(
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\ttranscript_id \"t0\";
+sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\"; ID \"ndls\";",
+        )?;
+
+        assert_eq!(
+            to_gff(records.clone(), UnmappablePolicy::Error),
+            Err(ConvertError::MissingGeneId)
+        );
+
+        let actual = to_gff(records[1..].to_vec(), UnmappablePolicy::Error);
+        assert_eq!(
+            actual,
+            Err(ConvertError::UnmappableAttribute(String::from("ID")))
+        );
+
+        let actual = to_gff(records, UnmappablePolicy::Discard)?;
+        let actual: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+        assert_eq!(
+            actual,
+            ["sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=g0;gene_id=g0"]
+        );
+
+        Ok(())
+    }
+}
@@ -2,9 +2,172 @@
 
 //! **noodles-gtf** handles the reading and writing of the Gene Transfer Format (GTF).
 
+pub mod convert;
+pub mod indexed_reader;
 pub mod line;
-mod reader;
+pub mod reader;
 pub mod record;
 mod writer;
 
-pub use self::{line::Line, reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader, line::Line, reader::Reader, record::Record, writer::Writer,
+};
+
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_tabix as tabix;
+
+/// Indexes a bgzipped GTF file.
+///
+/// The input is expected to be coordinate-sorted. The resulting index uses the tabix GFF preset,
+/// and comments are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_gtf as gtf;
+/// let index = gtf::index("annotations.gtf.gz")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<csi::Index>
+where
+    P: AsRef<Path>,
+{
+    File::open(src).and_then(index_from_reader)
+}
+
+/// Indexes a bgzipped GTF stream.
+///
+/// This is the same as [`index`] but reads from a stream of BGZF-compressed data.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf as bgzf;
+/// use noodles_gtf as gtf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";\n")?;
+/// let data = writer.finish()?;
+///
+/// let index = gtf::index_from_reader(&data[..])?;
+/// assert_eq!(index.reference_sequences().len(), 1);
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index_from_reader<R>(reader: R) -> io::Result<csi::Index>
+where
+    R: Read,
+{
+    let mut reader = Reader::new(bgzf::Reader::new(reader));
+
+    let mut indexer = tabix::index::Indexer::default();
+    indexer.set_header(csi::index::header::Builder::gff().build());
+
+    let mut buf = String::new();
+    let mut start_position = reader.virtual_position();
+
+    while let Some(record) = reader::read_record(reader.get_mut(), &mut buf)? {
+        let end_position = reader.virtual_position();
+        let chunk = Chunk::new(start_position, end_position);
+
+        indexer.add_record(
+            record.reference_sequence_name(),
+            record.start(),
+            record.end(),
+            chunk,
+        )?;
+
+        start_position = end_position;
+    }
+
+    Ok(indexer.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+
+    #[test]
+    fn test_index_and_query() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+
+        writer.write_all(b"#format: gtf\n")?;
+
+        for i in 0..4096 {
+            let start = i * 100 + 1;
+            writeln!(
+                writer,
+                "sq0\tNOODLES\tgene\t{start}\t{}\t.\t+\t.\tgene_id \"g{i}\"; transcript_id \"t{i}\";",
+                start + 149
+            )?;
+        }
+
+        writer.write_all(
+            b"sq1\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g4096\"; transcript_id \"t4096\";\n",
+        )?;
+        writer.write_all(
+            b"sq1\tNOODLES\tgene\t21\t21\t.\t+\t.\tgene_id \"g4097\"; transcript_id \"t4097\";\n",
+        )?;
+
+        let data = writer.finish()?;
+
+        let index = index_from_reader(&data[..])?;
+
+        let header = index.header().expect("missing tabix header");
+        assert_eq!(
+            header.format(),
+            csi::index::header::Builder::gff().build().format()
+        );
+
+        let names: Vec<_> = header.reference_sequence_names().iter().collect();
+        assert_eq!(names, ["sq0", "sq1"]);
+
+        let mut reader = IndexedReader::new(Cursor::new(data), index);
+
+        let gene_ids = |reader: &mut IndexedReader<_>, region: &str| {
+            reader
+                .query(&region.parse()?)?
+                .map(|result| {
+                    result.map(|record| {
+                        record
+                            .attributes()
+                            .iter()
+                            .find(|entry| entry.key() == "gene_id")
+                            .map(|entry| entry.value().to_string())
+                    })
+                })
+                .collect::<io::Result<Vec<_>>>()
+                .map_err(Box::<dyn std::error::Error>::from)
+        };
+
+        assert_eq!(
+            gene_ids(&mut reader, "sq0:301-350")?,
+            [Some(String::from("g2")), Some(String::from("g3"))]
+        );
+
+        assert_eq!(
+            gene_ids(&mut reader, "sq0:409601-409650")?,
+            [Some(String::from("g4095"))]
+        );
+
+        assert_eq!(
+            gene_ids(&mut reader, "sq1:13-22")?,
+            [Some(String::from("g4096")), Some(String::from("g4097"))]
+        );
+
+        assert!(gene_ids(&mut reader, "sq1:22-34")?.is_empty());
+        assert!(reader.query(&"sq2:1-10".parse()?).is_err());
+
+        Ok(())
+    }
+}
@@ -1,8 +1,18 @@
+//! GTF reader.
+
+mod query;
+
+pub use self::query::Query;
+
 use std::{
-    io::{self, BufRead},
+    io::{self, BufRead, Read, Seek},
     iter,
 };
 
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi as csi;
+
 use super::{Line, Record};
 
 /// A GTF reader.
@@ -27,6 +37,48 @@ where
         Self { inner }
     }
 
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gtf as gtf;
+    /// let data = [];
+    /// let reader = gtf::Reader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gtf as gtf;
+    /// let data = [];
+    /// let mut reader = gtf::Reader::new(&data[..]);
+    /// assert!(reader.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps and returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gtf as gtf;
+    /// let data = [];
+    /// let reader = gtf::Reader::new(&data[..]);
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
     /// Reads a raw GTF line.
     ///
     /// # Examples
@@ -128,6 +180,131 @@ where
     }
 }
 
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_gtf as gtf;
+    ///
+    /// let data = Vec::new();
+    /// let reader = gtf::Reader::new(bgzf::Reader::new(&data[..]));
+    ///
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF reader to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_gtf as gtf;
+    ///
+    /// let data = Cursor::new(Vec::new());
+    /// let mut reader = gtf::Reader::new(bgzf::Reader::new(data));
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::default();
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_gtf as gtf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("annotations.gtf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(gtf::Reader::new)?;
+    ///
+    /// let index = tabix::read("annotations.gtf.gz.tbi")?;
+    /// let region = "sq0:8-13".parse()?;
+    /// let query = reader.query(&index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&mut self, index: &csi::Index, region: &Region) -> io::Result<Query<'_, R>> {
+        let (reference_sequence_id, reference_sequence_name) = resolve_region(index, region)?;
+        let chunks = index.query(reference_sequence_id, region.interval())?;
+
+        Ok(Query::new(
+            self,
+            chunks,
+            reference_sequence_name,
+            region.interval(),
+        ))
+    }
+}
+
+// Reads lines until a record is parsed, skipping comments.
+pub(crate) fn read_record<R>(reader: &mut R, buf: &mut String) -> io::Result<Option<Record>>
+where
+    R: BufRead,
+{
+    loop {
+        buf.clear();
+
+        if read_line(reader, buf)? == 0 {
+            return Ok(None);
+        }
+
+        match buf.parse() {
+            Ok(Line::Record(record)) => return Ok(Some(record)),
+            Ok(_) => {}
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+}
+
+pub(crate) fn resolve_region(index: &csi::Index, region: &Region) -> io::Result<(usize, String)> {
+    let header = index
+        .header()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing tabix header"))?;
+
+    let i = header
+        .reference_sequence_names()
+        .get_index_of(region.name())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in reference sequences: {region:?}"
+                ),
+            )
+        })?;
+
+    Ok((i, region.name().into()))
+}
+
 fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
 where
     R: BufRead,
@@ -156,6 +333,24 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_read_record() -> io::Result<()> {
+        let data = b"\
+##format: gtf
+sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+";
+
+        let mut reader = &data[..];
+        let mut buf = String::new();
+
+        let record = read_record(&mut reader, &mut buf)?;
+        assert_eq!(record.map(|r| r.start()), noodles_core::Position::new(8));
+
+        assert!(read_record(&mut reader, &mut buf)?.is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         let mut buf = String::new();
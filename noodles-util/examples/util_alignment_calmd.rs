@@ -0,0 +1,66 @@
+//! Calculates the MD and NM tags of records in an alignment file and prints the result as SAM.
+//!
+//! Records whose existing tags disagree with the calculated values are reported to stderr.
+//!
+//! This is similar to the output of `samtools calmd <src> <fasta-src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_sam::{self as sam, AlignmentWriter};
+use noodles_util::alignment::{self, calmd};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let fasta_src = args.next().expect("missing fasta-src");
+
+    let repository = fasta::indexed_reader::Builder::default()
+        .build_from_path(fasta_src)
+        .map(IndexedReader::new)
+        .map(fasta::Repository::new)?;
+
+    let mut reader = alignment::reader::Builder::default()
+        .set_reference_sequence_repository(repository.clone())
+        .build_from_path(src)?;
+
+    let header = reader.read_header()?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = sam::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let mut record = result?;
+
+        if let Some(reference_sequence) =
+            calmd::fetch_reference_sequence(&header, &repository, &record).transpose()?
+        {
+            if record.alignment_start().is_some() {
+                for discrepancy in calmd::validate(&record, &reference_sequence)? {
+                    eprintln!(
+                        "{}: {}: expected {}, got {}",
+                        record
+                            .read_name()
+                            .map(|name| name.to_string())
+                            .unwrap_or_default(),
+                        discrepancy.tag(),
+                        discrepancy.expected(),
+                        discrepancy.actual()
+                    );
+                }
+
+                calmd::update(&mut record, &reference_sequence)?;
+            }
+        }
+
+        writer.write_alignment_record(&header, &record)?;
+    }
+
+    Ok(())
+}
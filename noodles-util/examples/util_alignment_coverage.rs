@@ -0,0 +1,48 @@
+//! Prints the mean and median depths of BED3 targets in an indexed BAM file.
+//!
+//! Each line is a tab-delimited record with the following columns: reference sequence name,
+//! start (0-based), end, mean depth, and median depth.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+};
+
+use noodles_bam as bam;
+use noodles_bed as bed;
+use noodles_util::alignment::coverage;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let targets_src = args.next().expect("missing targets-src");
+
+    let mut reader = bam::indexed_reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let mut targets_reader = File::open(targets_src)
+        .map(BufReader::new)
+        .map(bed::Reader::new)?;
+
+    let targets = targets_reader
+        .records::<3>()
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let builder = coverage::Builder::default();
+    let coverages = coverage::target_coverage(&mut reader, &header, &builder, &targets)?;
+
+    for (target, coverage) in targets.iter().zip(coverages) {
+        println!(
+            "{}\t{}\t{}\t{:.2}\t{:.1}",
+            target.reference_sequence_name(),
+            usize::from(target.start_position()) - 1,
+            target.end_position(),
+            coverage.mean().unwrap_or_default(),
+            coverage.median().unwrap_or_default(),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+//! Prints index statistics of an indexed BAM file.
+//!
+//! The result matches the output of `samtools idxstats <src>`.
+
+use std::{env, io};
+
+use noodles_bam as bam;
+use noodles_util::alignment::idxstats;
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = bam::indexed_reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    for record in idxstats::read(&header, reader.index())? {
+        println!("{record}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Marks duplicates in a coordinate-sorted alignment file and prints the result in the SAM
+//! format.
+//!
+//! The result is similar to the output of `samtools markdup -O sam <src> -`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_sam::{self as sam, AlignmentWriter};
+use noodles_util::alignment::{self, markdup};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = alignment::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = sam::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in markdup::Builder::default().build(&header, reader.records(&header)) {
+        let record = result?;
+        writer.write_alignment_record(&header, &record)?;
+    }
+
+    Ok(())
+}
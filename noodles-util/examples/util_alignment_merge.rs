@@ -0,0 +1,46 @@
+//! Merges coordinate-sorted alignment files and prints the result in the SAM format.
+//!
+//! The result is similar to the output of `samtools merge -O sam - <srcs...>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_sam::{self as sam, AlignmentWriter};
+use noodles_util::alignment::{self, merge};
+
+fn main() -> io::Result<()> {
+    let srcs: Vec<_> = env::args().skip(1).collect();
+
+    let mut readers = Vec::with_capacity(srcs.len());
+    let mut headers = Vec::with_capacity(srcs.len());
+
+    for src in srcs {
+        let mut reader = alignment::reader::Builder::default().build_from_path(src)?;
+        headers.push(reader.read_header()?);
+        readers.push(reader);
+    }
+
+    let merger = merge::Builder::default().build(&headers)?;
+
+    let inputs = readers
+        .iter_mut()
+        .zip(&headers)
+        .map(|(reader, header)| reader.records(header))
+        .collect();
+
+    let header = merger.header();
+
+    let stdout = io::stdout().lock();
+    let mut writer = sam::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(header)?;
+
+    for result in merger.merge(inputs)? {
+        let record = result?;
+        writer.write_alignment_record(header, &record)?;
+    }
+
+    Ok(())
+}
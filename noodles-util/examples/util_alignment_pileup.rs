@@ -0,0 +1,57 @@
+//! Prints the pileup of a coordinate-sorted alignment file.
+//!
+//! Each line is a tab-delimited record with the following columns: reference sequence name,
+//! position, depth, and read bases. Deleted bases are printed as `*`, and lowercase bases are on
+//! the reverse strand.
+
+use std::{
+    env,
+    io::{self, BufWriter, Write},
+};
+
+use noodles_util::alignment::{self, pileup};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = alignment::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = BufWriter::new(stdout);
+
+    for result in pileup::Builder::default().build(reader.records(&header)) {
+        let column = result?;
+
+        let name = header
+            .reference_sequences()
+            .get_index(column.reference_sequence_id())
+            .map(|(name, _)| name)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+            })?;
+
+        let bases: String = column
+            .entries()
+            .iter()
+            .map(|entry| match entry.base() {
+                Some(base) if entry.is_reverse_complemented() => {
+                    char::from(base).to_ascii_lowercase()
+                }
+                Some(base) => char::from(base),
+                None => '*',
+            })
+            .collect();
+
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}",
+            name,
+            column.position(),
+            column.depth(),
+            bases
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! Sorts an alignment file by coordinate and prints the result in the SAM format.
+//!
+//! Pass `-n` as the second argument to sort by query name instead.
+//!
+//! The result is similar to the output of `samtools sort [-n] -O sam <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_sam::{self as sam, AlignmentWriter};
+use noodles_util::alignment::{
+    self,
+    sort::{self, Order},
+};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+
+    let order = match args.next().as_deref() {
+        Some("-n") => Order::QueryName,
+        _ => Order::Coordinate,
+    };
+
+    let mut reader = alignment::reader::Builder::default().build_from_path(src)?;
+    let mut header = reader.read_header()?;
+
+    let sorter = sort::Builder::default().set_order(order).build();
+    let records = sorter.sort(&header, reader.records(&header))?;
+
+    sorter.update_header(&mut header);
+
+    let stdout = io::stdout().lock();
+    let mut writer = sam::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in records {
+        let record = result?;
+        writer.write_alignment_record(&header, &record)?;
+    }
+
+    Ok(())
+}
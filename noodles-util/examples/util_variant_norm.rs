@@ -0,0 +1,53 @@
+//! Normalizes variants in a variant file.
+//!
+//! Multiallelic records are split into biallelic records, which are then left-aligned and trimmed
+//! against the given reference sequences. The result is similar to the output of `bcftools norm
+//! --multiallelics -any --fasta-ref <fasta-src> <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_util::variant::{self, norm};
+use noodles_vcf as vcf;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let fasta_src = args.next().expect("missing fasta-src");
+
+    let repository = fasta::indexed_reader::Builder::default()
+        .build_from_path(fasta_src)
+        .map(IndexedReader::new)
+        .map(fasta::Repository::new)?;
+
+    let builder = variant::reader::Builder::default();
+
+    let mut reader = if src == "-" {
+        let stdin = io::stdin().lock();
+        builder.build_from_reader(stdin)?
+    } else {
+        builder.build_from_path(src)?
+    };
+
+    let header = reader.read_header()?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = vcf::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let record = result?;
+
+        for mut record in norm::split(&header, &record)? {
+            norm::left_align(&repository, &mut record)?;
+            writer.write_record(&header, &record)?;
+        }
+    }
+
+    Ok(())
+}
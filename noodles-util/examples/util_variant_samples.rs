@@ -0,0 +1,54 @@
+//! Prints a variant file with a subset of samples in the VCF format.
+//!
+//! Samples are given as a comma-separated list, in output order. The allele counts (`AC`, `AN`,
+//! and `AF`) are recomputed, and sites where no selected sample has an alternate allele call are
+//! removed.
+//!
+//! The result is similar to the output of `bcftools view --samples <samples> --min-ac 1 <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_util::variant::{self, samples};
+use noodles_vcf as vcf;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let raw_samples = args.next().expect("missing samples");
+
+    let builder = variant::reader::Builder::default();
+
+    let mut reader = if src == "-" {
+        let stdin = io::stdin().lock();
+        builder.build_from_reader(stdin)?
+    } else {
+        builder.build_from_path(src)?
+    };
+
+    let header = reader.read_header()?;
+
+    let selection = samples::Builder::default()
+        .set_samples(raw_samples.split(',').map(String::from).collect())
+        .set_remove_sites_without_alternate_calls(true)
+        .set_update_allele_counts(true)
+        .build(&header)?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = vcf::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(selection.header())?;
+
+    for result in reader.records(&header) {
+        let mut record = result?;
+
+        if selection.apply(&mut record)? {
+            writer.write_record(selection.header(), &record)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,7 +1,20 @@
 //! I/O for alignment formats.
 
+pub mod calmd;
+pub mod cigar;
+pub mod clip;
+pub mod coverage;
+pub mod filter;
+pub mod fixmate;
+pub mod flagstat;
 mod format;
+pub mod idxstats;
+pub mod markdup;
+pub mod merge;
+pub mod pileup;
 pub mod reader;
+pub mod sort;
+pub mod supplementary_alignments;
 pub mod writer;
 
 pub use self::{format::Format, reader::Reader, writer::Writer};
@@ -0,0 +1,434 @@
+//! Alignment `MD` and `NM` tag calculation.
+//!
+//! This computes the mismatched positions (`MD`) and edit distance (`NM`) of an alignment record
+//! from its CIGAR, sequence, and reference sequence, similar to `samtools calmd`.
+
+use std::{fmt::Write, io};
+
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    record::{
+        cigar::op::Kind,
+        data::field::{Tag, Value},
+        sequence::Base,
+    },
+};
+
+const DELETION_PREFIX: char = '^';
+
+/// The calculated `MD` and `NM` tag values of a record.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Tags {
+    mismatched_positions: String,
+    edit_distance: u32,
+}
+
+impl Tags {
+    /// Returns the mismatched positions (`MD`).
+    pub fn mismatched_positions(&self) -> &str {
+        &self.mismatched_positions
+    }
+
+    /// Returns the edit distance (`NM`).
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+}
+
+/// A record data field whose value disagrees with the calculated value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Discrepancy {
+    tag: Tag,
+    actual: Value,
+    expected: Value,
+}
+
+impl Discrepancy {
+    /// Returns the data field tag.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the value in the record.
+    pub fn actual(&self) -> &Value {
+        &self.actual
+    }
+
+    /// Returns the calculated value.
+    pub fn expected(&self) -> &Value {
+        &self.expected
+    }
+}
+
+/// Fetches the reference sequence of a record from a repository.
+///
+/// This returns `None` if the record is unplaced or the reference sequence is not in the
+/// repository.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta as fasta;
+/// use noodles_sam as sam;
+/// use noodles_util::alignment::calmd;
+///
+/// let header = sam::Header::default();
+/// let repository = fasta::Repository::default();
+/// let record = sam::alignment::Record::default();
+///
+/// assert!(calmd::fetch_reference_sequence(&header, &repository, &record).is_none());
+/// ```
+pub fn fetch_reference_sequence(
+    header: &sam::Header,
+    repository: &fasta::Repository,
+    record: &Record,
+) -> Option<io::Result<fasta::record::Sequence>> {
+    match record.reference_sequence(header)? {
+        Ok((name, _)) => repository.get(name),
+        Err(e) => Some(Err(e)),
+    }
+}
+
+/// Calculates the `MD` and `NM` tag values of a record.
+///
+/// `reference_sequence` is the entire sequence of the reference the record is mapped to. Bases
+/// are compared case-insensitively, and an `=` read base always matches.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_fasta as fasta;
+/// use noodles_sam as sam;
+/// use noodles_util::alignment::calmd;
+///
+/// let reference_sequence = fasta::record::Sequence::from(b"ACGTACGT".to_vec());
+///
+/// let record = sam::alignment::Record::builder()
+///     .set_alignment_start(Position::try_from(2)?)
+///     .set_cigar("2M1I2M1D1M".parse()?)
+///     .set_sequence("CTTTAG".parse()?)
+///     .build();
+///
+/// let tags = calmd::calculate(&record, &reference_sequence)?;
+/// assert_eq!(tags.mismatched_positions(), "1G2^C1");
+/// assert_eq!(tags.edit_distance(), 3);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn calculate(
+    record: &Record,
+    reference_sequence: &fasta::record::Sequence,
+) -> io::Result<Tags> {
+    let alignment_start = record
+        .alignment_start()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing alignment start"))?;
+
+    let bases = record.sequence().as_ref();
+
+    if bases.is_empty() && record.cigar().read_length() > 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "missing sequence",
+        ));
+    }
+
+    let reference_base = |i: usize| {
+        alignment_start
+            .checked_add(i)
+            .and_then(|position| reference_sequence.get(position))
+            .map(|&b| char::from(b.to_ascii_uppercase()))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "alignment is out of reference sequence bounds",
+                )
+            })
+    };
+
+    let mut mismatched_positions = String::new();
+    let mut edit_distance = 0;
+    let mut match_count = 0;
+
+    let mut reference_offset = 0;
+    let mut read_position = 0;
+
+    for op in record.cigar().iter() {
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                for _ in 0..op.len() {
+                    let read_base = bases.get(read_position).copied().ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidData, "missing read base")
+                    })?;
+
+                    let ref_base = reference_base(reference_offset)?;
+
+                    if is_match(read_base, ref_base) {
+                        match_count += 1;
+                    } else {
+                        write!(mismatched_positions, "{match_count}{ref_base}")
+                            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                        match_count = 0;
+                        edit_distance += 1;
+                    }
+
+                    reference_offset += 1;
+                    read_position += 1;
+                }
+            }
+            Kind::Insertion => {
+                edit_distance += op.len();
+                read_position += op.len();
+            }
+            Kind::Deletion => {
+                write!(mismatched_positions, "{match_count}{DELETION_PREFIX}")
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+                for _ in 0..op.len() {
+                    mismatched_positions.push(reference_base(reference_offset)?);
+                    reference_offset += 1;
+                }
+
+                match_count = 0;
+                edit_distance += op.len();
+            }
+            Kind::Skip => reference_offset += op.len(),
+            Kind::SoftClip => read_position += op.len(),
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    write!(mismatched_positions, "{match_count}")
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    let edit_distance =
+        u32::try_from(edit_distance).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Tags {
+        mismatched_positions,
+        edit_distance,
+    })
+}
+
+/// Calculates and sets the `MD` and `NM` data fields of a record.
+///
+/// Existing `MD` and `NM` fields are overwritten.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_fasta as fasta;
+/// use noodles_sam::{self as sam, record::data::field::{Tag, Value}};
+/// use noodles_util::alignment::calmd;
+///
+/// let reference_sequence = fasta::record::Sequence::from(b"ACGT".to_vec());
+///
+/// let mut record = sam::alignment::Record::builder()
+///     .set_alignment_start(Position::try_from(1)?)
+///     .set_cigar("4M".parse()?)
+///     .set_sequence("ACTT".parse()?)
+///     .build();
+///
+/// calmd::update(&mut record, &reference_sequence)?;
+///
+/// let data = record.data();
+/// assert_eq!(data.get(Tag::MismatchedPositions), Some(&Value::String(String::from("2G1"))));
+/// assert_eq!(data.get(Tag::EditDistance), Some(&Value::from(1)));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn update(record: &mut Record, reference_sequence: &fasta::record::Sequence) -> io::Result<()> {
+    let tags = calculate(record, reference_sequence)?;
+
+    let data = record.data_mut();
+    data.insert(
+        Tag::MismatchedPositions,
+        Value::String(tags.mismatched_positions),
+    );
+    data.insert(Tag::EditDistance, Value::from(tags.edit_distance));
+
+    Ok(())
+}
+
+/// Validates the existing `MD` and `NM` data fields of a record.
+///
+/// This returns the fields whose values disagree with the calculated values. Missing fields are
+/// not reported.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_fasta as fasta;
+/// use noodles_sam::{self as sam, record::data::field::{Tag, Value}};
+/// use noodles_util::alignment::calmd;
+///
+/// let reference_sequence = fasta::record::Sequence::from(b"ACGT".to_vec());
+///
+/// let data = [(Tag::EditDistance, Value::from(0))].into_iter().collect();
+///
+/// let record = sam::alignment::Record::builder()
+///     .set_alignment_start(Position::try_from(1)?)
+///     .set_cigar("4M".parse()?)
+///     .set_sequence("ACTT".parse()?)
+///     .set_data(data)
+///     .build();
+///
+/// let discrepancies = calmd::validate(&record, &reference_sequence)?;
+/// assert_eq!(discrepancies.len(), 1);
+/// assert_eq!(discrepancies[0].tag(), Tag::EditDistance);
+/// assert_eq!(discrepancies[0].expected(), &Value::from(1));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate(
+    record: &Record,
+    reference_sequence: &fasta::record::Sequence,
+) -> io::Result<Vec<Discrepancy>> {
+    let tags = calculate(record, reference_sequence)?;
+    let data = record.data();
+
+    let mut discrepancies = Vec::new();
+
+    if let Some(actual) = data.get(Tag::MismatchedPositions) {
+        if actual.as_str() != Some(tags.mismatched_positions()) {
+            discrepancies.push(Discrepancy {
+                tag: Tag::MismatchedPositions,
+                actual: actual.clone(),
+                expected: Value::String(tags.mismatched_positions.clone()),
+            });
+        }
+    }
+
+    if let Some(actual) = data.get(Tag::EditDistance) {
+        if actual.as_int() != Some(i64::from(tags.edit_distance)) {
+            discrepancies.push(Discrepancy {
+                tag: Tag::EditDistance,
+                actual: actual.clone(),
+                expected: Value::from(tags.edit_distance),
+            });
+        }
+    }
+
+    Ok(discrepancies)
+}
+
+fn is_match(read_base: Base, reference_base: char) -> bool {
+    match read_base {
+        Base::Eq => true,
+        Base::N => false,
+        _ => char::from(read_base) == reference_base && reference_base != 'N',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn test_calculate() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = fasta::record::Sequence::from(b"acgtACGTNNGT".to_vec());
+
+        let t = |record: &Record, expected_md: &str, expected_nm: u32| {
+            let tags = calculate(record, &reference_sequence)?;
+            assert_eq!(tags.mismatched_positions(), expected_md);
+            assert_eq!(tags.edit_distance(), expected_nm);
+            Ok::<_, io::Error>(())
+        };
+
+        let record = Record::builder()
+            .set_alignment_start(Position::MIN)
+            .set_cigar("8M".parse()?)
+            .set_sequence("ACGTACGT".parse()?)
+            .build();
+        t(&record, "8", 0)?;
+
+        let record = Record::builder()
+            .set_alignment_start(Position::MIN)
+            .set_cigar("2S4M".parse()?)
+            .set_sequence("TTACTT".parse()?)
+            .build();
+        t(&record, "2G1", 1)?;
+
+        let record = Record::builder()
+            .set_alignment_start(Position::MIN)
+            .set_cigar("2M2D2M".parse()?)
+            .set_sequence("ACAC".parse()?)
+            .build();
+        t(&record, "2^GT2", 2)?;
+
+        let record = Record::builder()
+            .set_alignment_start(Position::MIN)
+            .set_cigar("2M2N2M1I".parse()?)
+            .set_sequence("ACACG".parse()?)
+            .build();
+        t(&record, "4", 1)?;
+
+        let record = Record::builder()
+            .set_alignment_start(Position::try_from(5)?)
+            .set_cigar("4M2H".parse()?)
+            .set_sequence("A=NT".parse()?)
+            .build();
+        t(&record, "2G1", 1)?;
+
+        let record = Record::builder()
+            .set_alignment_start(Position::try_from(7)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("GTNN".parse()?)
+            .build();
+        t(&record, "2N0N0", 2)?;
+
+        let record = Record::builder()
+            .set_alignment_start(Position::try_from(11)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("GTAC".parse()?)
+            .build();
+
+        assert!(matches!(
+            calculate(&record, &reference_sequence),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            calculate(&Record::default(), &reference_sequence),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = fasta::record::Sequence::from(b"ACGT".to_vec());
+
+        let mut record = Record::builder()
+            .set_alignment_start(Position::MIN)
+            .set_cigar("4M".parse()?)
+            .set_sequence("ACTT".parse()?)
+            .build();
+
+        assert!(validate(&record, &reference_sequence)?.is_empty());
+
+        update(&mut record, &reference_sequence)?;
+        assert!(validate(&record, &reference_sequence)?.is_empty());
+
+        record
+            .data_mut()
+            .insert(Tag::MismatchedPositions, Value::String(String::from("4")));
+
+        let discrepancies = validate(&record, &reference_sequence)?;
+
+        assert_eq!(
+            discrepancies,
+            [Discrepancy {
+                tag: Tag::MismatchedPositions,
+                actual: Value::String(String::from("4")),
+                expected: Value::String(String::from("2G1")),
+            }]
+        );
+
+        Ok(())
+    }
+}
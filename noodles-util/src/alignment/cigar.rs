@@ -0,0 +1,228 @@
+//! Alignment CIGAR utilities.
+//!
+//! This maps between read offsets and reference positions and normalizes CIGAR operations.
+
+use noodles_core::Position;
+use noodles_sam::record::{
+    cigar::{op::Kind, Op},
+    Cigar,
+};
+
+/// Normalizes a list of CIGAR operations.
+///
+/// This removes operations with a length of 0 and merges adjacent operations of the same kind.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::record::Cigar;
+/// use noodles_util::alignment::cigar;
+///
+/// let raw_cigar: Cigar = "2S0I3M1M0D4M".parse()?;
+/// assert_eq!(cigar::normalize(&raw_cigar).to_string(), "2S8M");
+/// # Ok::<_, noodles_sam::record::cigar::ParseError>(())
+/// ```
+pub fn normalize(cigar: &Cigar) -> Cigar {
+    let mut normalized_cigar = Cigar::default();
+    let ops: &mut Vec<Op> = normalized_cigar.as_mut();
+
+    for op in cigar.iter().filter(|op| !op.is_empty()) {
+        match ops.last_mut() {
+            Some(last) if last.kind() == op.kind() => {
+                *last = Op::new(last.kind(), last.len() + op.len());
+            }
+            _ => ops.push(*op),
+        }
+    }
+
+    normalized_cigar
+}
+
+/// Returns the reference position aligned to a 0-based read offset.
+///
+/// The read offset includes soft clipped bases. This returns `None` if the offset is in a soft
+/// clip or an insertion or is not in the read.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::record::Cigar;
+/// use noodles_util::alignment::cigar;
+///
+/// let alignment_start = Position::try_from(8)?;
+/// let raw_cigar: Cigar = "2S2M1I2D2M".parse()?;
+///
+/// assert!(cigar::read_to_reference(alignment_start, &raw_cigar, 1).is_none());
+/// assert_eq!(cigar::read_to_reference(alignment_start, &raw_cigar, 2), Position::new(8));
+/// assert!(cigar::read_to_reference(alignment_start, &raw_cigar, 4).is_none());
+/// assert_eq!(cigar::read_to_reference(alignment_start, &raw_cigar, 5), Position::new(12));
+/// assert!(cigar::read_to_reference(alignment_start, &raw_cigar, 7).is_none());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn read_to_reference(
+    alignment_start: Position,
+    cigar: &Cigar,
+    read_offset: usize,
+) -> Option<Position> {
+    let mut read_start = 0;
+    let mut reference_start = usize::from(alignment_start);
+
+    for op in cigar.iter() {
+        let kind = op.kind();
+        let len = op.len();
+
+        if kind.consumes_read() && read_offset < read_start + len {
+            return if kind.consumes_reference() {
+                Position::new(reference_start + (read_offset - read_start))
+            } else {
+                None
+            };
+        }
+
+        if kind.consumes_read() {
+            read_start += len;
+        }
+
+        if kind.consumes_reference() {
+            reference_start += len;
+        }
+    }
+
+    None
+}
+
+/// Returns the 0-based read offset aligned to a reference position.
+///
+/// The read offset includes soft clipped bases. This returns `None` if the position is in a
+/// deletion or skipped region or is not covered by the alignment.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::record::Cigar;
+/// use noodles_util::alignment::cigar;
+///
+/// let alignment_start = Position::try_from(8)?;
+/// let raw_cigar: Cigar = "2S2M1I2D2M".parse()?;
+///
+/// assert!(cigar::reference_to_read(alignment_start, &raw_cigar, Position::try_from(7)?).is_none());
+/// assert_eq!(cigar::reference_to_read(alignment_start, &raw_cigar, Position::try_from(8)?), Some(2));
+/// assert!(cigar::reference_to_read(alignment_start, &raw_cigar, Position::try_from(10)?).is_none());
+/// assert_eq!(cigar::reference_to_read(alignment_start, &raw_cigar, Position::try_from(12)?), Some(5));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn reference_to_read(
+    alignment_start: Position,
+    cigar: &Cigar,
+    reference_position: Position,
+) -> Option<usize> {
+    let reference_position = usize::from(reference_position);
+
+    let mut read_start = 0;
+    let mut reference_start = usize::from(alignment_start);
+
+    if reference_position < reference_start {
+        return None;
+    }
+
+    for op in cigar.iter() {
+        let kind = op.kind();
+        let len = op.len();
+
+        if kind.consumes_reference() && reference_position < reference_start + len {
+            return if kind.consumes_read() {
+                Some(read_start + (reference_position - reference_start))
+            } else {
+                None
+            };
+        }
+
+        if kind.consumes_read() {
+            read_start += len;
+        }
+
+        if kind.consumes_reference() {
+            reference_start += len;
+        }
+    }
+
+    None
+}
+
+pub(crate) fn is_clip(kind: Kind) -> bool {
+    matches!(kind, Kind::SoftClip | Kind::HardClip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() -> Result<(), noodles_sam::record::cigar::ParseError> {
+        let cigar: Cigar = "4M".parse()?;
+        assert_eq!(normalize(&cigar), cigar);
+
+        let cigar: Cigar = "1H0S2S1M1M1I1I0M1M".parse()?;
+        assert_eq!(normalize(&cigar).to_string(), "1H2S2M2I1M");
+
+        let cigar: Cigar = "0M".parse()?;
+        assert!(normalize(&cigar).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_to_reference() -> Result<(), Box<dyn std::error::Error>> {
+        let alignment_start = Position::try_from(8)?;
+        let cigar: Cigar = "1H2S2M1I2D1N2M1S".parse()?;
+
+        let actual: Vec<_> = (0..10)
+            .map(|i| read_to_reference(alignment_start, &cigar, i).map(usize::from))
+            .collect();
+
+        let expected = [
+            None,
+            None,
+            Some(8),
+            Some(9),
+            None,
+            Some(13),
+            Some(14),
+            None,
+            None,
+            None,
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reference_to_read() -> Result<(), Box<dyn std::error::Error>> {
+        let alignment_start = Position::try_from(8)?;
+        let cigar: Cigar = "1H2S2M1I2D1N2M1S".parse()?;
+
+        let actual: Vec<_> = (7..=15)
+            .map(|n| Position::try_from(n).map(|p| reference_to_read(alignment_start, &cigar, p)))
+            .collect::<Result<_, _>>()?;
+
+        let expected = [
+            None,
+            Some(2),
+            Some(3),
+            None,
+            None,
+            None,
+            Some(5),
+            Some(6),
+            None,
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+//! Alignment record clipping.
+//!
+//! This clips bases from either end of an alignment record, adjusting its CIGAR, alignment
+//! start, sequence, and quality scores.
+
+use std::io;
+
+use noodles_sam::{
+    alignment::Record,
+    record::{
+        cigar::{op::Kind, Op},
+        Cigar,
+    },
+};
+
+use super::cigar::{is_clip, normalize};
+
+/// A clip mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Clipped bases are kept in the sequence (`S`).
+    #[default]
+    Soft,
+    /// Clipped bases are removed from the sequence (`H`).
+    Hard,
+}
+
+/// Clips bases from the start of a record.
+///
+/// This clips at least the first `n` bases of the read, in reference orientation, including bases
+/// that are already soft clipped. Deletions, skipped regions, and insertions adjacent to the new
+/// clip are also removed. The alignment start is moved past the reference bases that are no
+/// longer aligned.
+///
+/// This returns an error if no aligned bases would remain.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::alignment::Record;
+/// use noodles_util::alignment::clip::{self, Mode};
+///
+/// let mut record = Record::builder()
+///     .set_alignment_start(Position::try_from(8)?)
+///     .set_cigar("1S4M1D2M".parse()?)
+///     .set_sequence("ACGTAGT".parse()?)
+///     .build();
+///
+/// clip::clip_start(&mut record, 5, Mode::Soft)?;
+///
+/// assert_eq!(record.alignment_start(), Position::new(13));
+/// assert_eq!(record.cigar().to_string(), "5S2M");
+/// assert_eq!(record.sequence().to_string(), "ACGTAGT");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn clip_start(record: &mut Record, n: usize, mode: Mode) -> io::Result<()> {
+    let (ops, read_len, reference_len) = clip_ops(record.cigar().iter().copied(), n, mode)?;
+
+    *record.cigar_mut() = build_cigar(ops);
+
+    if let Some(alignment_start) = record.alignment_start() {
+        *record.alignment_start_mut() = alignment_start.checked_add(reference_len);
+    }
+
+    if mode == Mode::Hard {
+        let bases: &mut Vec<_> = record.sequence_mut().as_mut();
+        bases.drain(..read_len.min(bases.len()));
+
+        let scores: &mut Vec<_> = record.quality_scores_mut().as_mut();
+        scores.drain(..read_len.min(scores.len()));
+    }
+
+    Ok(())
+}
+
+/// Clips bases from the end of a record.
+///
+/// This clips at least the last `n` bases of the read, in reference orientation, including bases
+/// that are already soft clipped. Deletions, skipped regions, and insertions adjacent to the new
+/// clip are also removed.
+///
+/// This returns an error if no aligned bases would remain.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::alignment::Record;
+/// use noodles_util::alignment::clip::{self, Mode};
+///
+/// let mut record = Record::builder()
+///     .set_alignment_start(Position::try_from(8)?)
+///     .set_cigar("4M1I2M".parse()?)
+///     .set_sequence("ACGTAGT".parse()?)
+///     .set_quality_scores("NDLS#NL".parse()?)
+///     .build();
+///
+/// clip::clip_end(&mut record, 2, Mode::Hard)?;
+///
+/// assert_eq!(record.alignment_start(), Position::new(8));
+/// assert_eq!(record.cigar().to_string(), "4M3H");
+/// assert_eq!(record.sequence().to_string(), "ACGT");
+/// assert_eq!(record.quality_scores().to_string(), "NDLS");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn clip_end(record: &mut Record, n: usize, mode: Mode) -> io::Result<()> {
+    let (ops, read_len, _) = clip_ops(record.cigar().iter().rev().copied(), n, mode)?;
+
+    *record.cigar_mut() = build_cigar(ops.into_iter().rev());
+
+    if mode == Mode::Hard {
+        let bases: &mut Vec<_> = record.sequence_mut().as_mut();
+        bases.truncate(bases.len().saturating_sub(read_len));
+
+        let scores: &mut Vec<_> = record.quality_scores_mut().as_mut();
+        scores.truncate(scores.len().saturating_sub(read_len));
+    }
+
+    Ok(())
+}
+
+/// Converts the soft clips of a record to hard clips.
+///
+/// The soft clipped bases are removed from the sequence and quality scores.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::alignment::Record;
+/// use noodles_util::alignment::clip;
+///
+/// let mut record = Record::builder()
+///     .set_cigar("1H2S3M1S".parse()?)
+///     .set_sequence("ACGTAG".parse()?)
+///     .build();
+///
+/// clip::soft_clip_to_hard_clip(&mut record);
+///
+/// assert_eq!(record.cigar().to_string(), "3H3M1H");
+/// assert_eq!(record.sequence().to_string(), "GTA");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn soft_clip_to_hard_clip(record: &mut Record) {
+    let soft_clip_len = |ops: &mut dyn Iterator<Item = &Op>| -> usize {
+        ops.take_while(|op| is_clip(op.kind()))
+            .filter(|op| op.kind() == Kind::SoftClip)
+            .map(|op| op.len())
+            .sum()
+    };
+
+    let leading_len = soft_clip_len(&mut record.cigar().iter());
+
+    // A CIGAR with only clips is counted once.
+    let trailing_len = if record.cigar().iter().all(|op| is_clip(op.kind())) {
+        0
+    } else {
+        soft_clip_len(&mut record.cigar().iter().rev())
+    };
+
+    let ops: Vec<_> = record
+        .cigar()
+        .iter()
+        .map(|op| {
+            if op.kind() == Kind::SoftClip {
+                Op::new(Kind::HardClip, op.len())
+            } else {
+                *op
+            }
+        })
+        .collect();
+
+    *record.cigar_mut() = build_cigar(ops);
+
+    remove_ends(record.sequence_mut().as_mut(), leading_len, trailing_len);
+    remove_ends(
+        record.quality_scores_mut().as_mut(),
+        leading_len,
+        trailing_len,
+    );
+}
+
+fn remove_ends<T>(values: &mut Vec<T>, leading_len: usize, trailing_len: usize) {
+    if values.len() < leading_len + trailing_len {
+        values.clear();
+        return;
+    }
+
+    values.truncate(values.len() - trailing_len);
+    values.drain(..leading_len);
+}
+
+// Clips `n` read bases from the start of a list of ops.
+//
+// This returns the new ops, the number of read bases clipped, and the number of reference bases
+// no longer aligned.
+fn clip_ops<I>(ops: I, n: usize, mode: Mode) -> io::Result<(Vec<Op>, usize, usize)>
+where
+    I: Iterator<Item = Op>,
+{
+    let mut ops = ops.peekable();
+
+    let mut hard_clip_len = 0;
+
+    while let Some(op) = ops.next_if(|op| op.kind() == Kind::HardClip) {
+        hard_clip_len += op.len();
+    }
+
+    let mut remaining = n;
+    let mut read_len = 0;
+    let mut reference_len = 0;
+    let mut rest = Vec::new();
+
+    while let Some(op) = ops.peek().copied() {
+        let kind = op.kind();
+        let len = op.len();
+
+        if remaining == 0 {
+            match kind {
+                Kind::Deletion | Kind::Skip => reference_len += len,
+                Kind::Insertion => read_len += len,
+                Kind::Pad => {}
+                _ => break,
+            }
+        } else if kind.consumes_read() {
+            let clipped_len = len.min(remaining);
+
+            remaining -= clipped_len;
+            read_len += clipped_len;
+
+            if kind.consumes_reference() {
+                reference_len += clipped_len;
+            }
+
+            if clipped_len < len {
+                ops.next();
+                rest.push(Op::new(kind, len - clipped_len));
+                break;
+            }
+        } else if kind.consumes_reference() {
+            reference_len += len;
+        }
+
+        ops.next();
+    }
+
+    rest.extend(ops);
+
+    let is_aligned = rest.iter().any(|op| {
+        matches!(
+            op.kind(),
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch
+        )
+    });
+
+    if !is_aligned {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "clip removes all aligned bases",
+        ));
+    }
+
+    let clip_ops = match mode {
+        Mode::Soft => [
+            Op::new(Kind::HardClip, hard_clip_len),
+            Op::new(Kind::SoftClip, read_len),
+        ],
+        Mode::Hard => [
+            Op::new(Kind::HardClip, hard_clip_len + read_len),
+            Op::new(Kind::SoftClip, 0),
+        ],
+    };
+
+    let mut new_ops = clip_ops.to_vec();
+    new_ops.extend(rest);
+
+    Ok((new_ops, read_len, reference_len))
+}
+
+fn build_cigar<I>(ops: I) -> Cigar
+where
+    I: IntoIterator<Item = Op>,
+{
+    let mut cigar = Cigar::default();
+    cigar.as_mut().extend(ops);
+    normalize(&cigar)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn test_clip_start() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("2S4M".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        clip_start(&mut record, 1, Mode::Soft)?;
+        assert_eq!(record.alignment_start(), Position::new(8));
+        assert_eq!(record.cigar().to_string(), "2S4M");
+
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("1H2S4M".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        clip_start(&mut record, 1, Mode::Hard)?;
+        assert_eq!(record.alignment_start(), Position::new(8));
+        assert_eq!(record.cigar().to_string(), "2H1S4M");
+        assert_eq!(record.sequence().to_string(), "CGTAG");
+        assert_eq!(record.quality_scores().to_string(), "DLS#N");
+
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("2M1I2N3M".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        clip_start(&mut record, 2, Mode::Hard)?;
+        assert_eq!(record.alignment_start(), Position::new(12));
+        assert_eq!(record.cigar().to_string(), "3H3M");
+        assert_eq!(record.sequence().to_string(), "TAG");
+        assert_eq!(record.quality_scores().to_string(), "S#N");
+
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("2S4M".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        assert!(matches!(
+            clip_start(&mut record, 6, Mode::Soft),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clip_end() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("4M1D2M".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        clip_end(&mut record, 3, Mode::Soft)?;
+        assert_eq!(record.alignment_start(), Position::new(8));
+        assert_eq!(record.cigar().to_string(), "3M3S");
+        assert_eq!(record.sequence().to_string(), "ACGTAG");
+
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("4M2S1H".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        clip_end(&mut record, 3, Mode::Hard)?;
+        assert_eq!(record.cigar().to_string(), "3M4H");
+        assert_eq!(record.sequence().to_string(), "ACG");
+        assert_eq!(record.quality_scores().to_string(), "NDL");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_soft_clip_to_hard_clip() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("2S2M2S".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        soft_clip_to_hard_clip(&mut record);
+        assert_eq!(record.alignment_start(), Position::new(8));
+        assert_eq!(record.cigar().to_string(), "2H2M2H");
+        assert_eq!(record.sequence().to_string(), "GT");
+        assert_eq!(record.quality_scores().to_string(), "LS");
+
+        let mut record = Record::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("6M".parse()?)
+            .set_sequence("ACGTAG".parse()?)
+            .set_quality_scores("NDLS#N".parse()?)
+            .build();
+
+        soft_clip_to_hard_clip(&mut record);
+        assert_eq!(record.cigar().to_string(), "6M");
+        assert_eq!(record.sequence().to_string(), "ACGTAG");
+
+        Ok(())
+    }
+}
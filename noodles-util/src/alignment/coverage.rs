@@ -0,0 +1,547 @@
+//! Alignment depth and coverage.
+//!
+//! [`Coverage`] holds the per-base depth of an interval on a reference sequence. It can be
+//! summarized as runs of equal depth, e.g., for bedGraph output, or as mean and median depths.
+//! Multiple coverages, e.g., of a list of targets, can be summarized together using
+//! [`summarize`].
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    io::{self, Read, Seek, Write},
+    iter,
+};
+
+use noodles_bam as bam;
+use noodles_bed as bed;
+use noodles_bgzf as bgzf;
+use noodles_core::{Position, Region};
+use noodles_sam as sam;
+
+/// The per-base depth of an interval.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Coverage {
+    start: Position,
+    depths: Vec<u32>,
+}
+
+impl Coverage {
+    /// Returns the start position.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end position.
+    ///
+    /// This is `None` if the coverage is empty.
+    pub fn end(&self) -> Option<Position> {
+        self.depths
+            .len()
+            .checked_sub(1)
+            .and_then(|n| self.start.checked_add(n))
+    }
+
+    /// Returns the depth at each position, starting at [`Self::start`].
+    pub fn depths(&self) -> &[u32] {
+        &self.depths
+    }
+
+    /// Returns the depth at the given position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_util::alignment::coverage;
+    ///
+    /// let coverage = coverage::Builder::default().build(
+    ///     Position::try_from(8)?,
+    ///     Position::try_from(13)?,
+    ///     [],
+    /// )?;
+    ///
+    /// assert_eq!(coverage.get(Position::try_from(8)?), Some(0));
+    /// assert!(coverage.get(Position::try_from(21)?).is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get(&self, position: Position) -> Option<u32> {
+        usize::from(position)
+            .checked_sub(usize::from(self.start))
+            .and_then(|i| self.depths.get(i))
+            .copied()
+    }
+
+    /// Returns an iterator over runs of positions with equal depth.
+    ///
+    /// Runs with a depth of 0 are included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::{self as sam, record::Flags};
+    /// use noodles_util::alignment::coverage;
+    ///
+    /// let record = sam::alignment::Record::builder()
+    ///     .set_flags(Flags::empty())
+    ///     .set_alignment_start(Position::try_from(2)?)
+    ///     .set_cigar("2M".parse()?)
+    ///     .build();
+    ///
+    /// let coverage = coverage::Builder::default().build(
+    ///     Position::try_from(1)?,
+    ///     Position::try_from(4)?,
+    ///     [Ok(record)],
+    /// )?;
+    ///
+    /// let intervals: Vec<_> = coverage
+    ///     .intervals()
+    ///     .map(|interval| (usize::from(interval.start()), usize::from(interval.end()), interval.depth()))
+    ///     .collect();
+    ///
+    /// assert_eq!(intervals, [(1, 1, 0), (2, 3, 1), (4, 4, 0)]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn intervals(&self) -> impl Iterator<Item = Interval> + '_ {
+        let mut start = self.start;
+        let mut depths = &self.depths[..];
+
+        iter::from_fn(move || {
+            let (&depth, rest) = depths.split_first()?;
+            let len = 1 + rest.iter().take_while(|&&d| d == depth).count();
+
+            let interval = Interval {
+                start,
+                end: start.checked_add(len - 1)?,
+                depth,
+            };
+
+            depths = &depths[len..];
+            start = start.checked_add(len)?;
+
+            Some(interval)
+        })
+    }
+
+    /// Returns the mean depth.
+    ///
+    /// This is `None` if the coverage is empty.
+    pub fn mean(&self) -> Option<f64> {
+        mean(&self.depths)
+    }
+
+    /// Returns the median depth.
+    ///
+    /// For an even number of positions, this is the mean of the two middle depths. This is `None`
+    /// if the coverage is empty.
+    pub fn median(&self) -> Option<f64> {
+        median(self.depths.clone())
+    }
+}
+
+/// Summary statistics over all positions of one or more coverages.
+///
+/// This is created by calling [`summarize`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    position_count: usize,
+    mean: f64,
+    median: f64,
+    breadth: f64,
+}
+
+impl Summary {
+    /// Returns the total number of positions.
+    pub fn position_count(&self) -> usize {
+        self.position_count
+    }
+
+    /// Returns the mean depth.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the median depth.
+    ///
+    /// For an even number of positions, this is the mean of the two middle depths.
+    pub fn median(&self) -> f64 {
+        self.median
+    }
+
+    /// Returns the fraction of positions with at least the minimum depth.
+    pub fn breadth(&self) -> f64 {
+        self.breadth
+    }
+}
+
+/// Summarizes the depths of all positions of the given coverages.
+///
+/// Each position is weighted equally, i.e., longer coverages contribute more to the summary than
+/// shorter ones. The breadth is the fraction of positions with a depth of at least `min_depth`.
+///
+/// This is `None` if the coverages have no positions.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::{self as sam, record::Flags};
+/// use noodles_util::alignment::coverage;
+///
+/// let record = sam::alignment::Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_alignment_start(Position::try_from(2)?)
+///     .set_cigar("2M".parse()?)
+///     .build();
+///
+/// let builder = coverage::Builder::default();
+///
+/// let coverages = [
+///     builder.build(Position::try_from(1)?, Position::try_from(2)?, [Ok(record.clone())])?,
+///     builder.build(Position::try_from(3)?, Position::try_from(8)?, [Ok(record)])?,
+/// ];
+///
+/// let summary = coverage::summarize(&coverages, 1).expect("missing summary");
+/// assert_eq!(summary.position_count(), 8);
+/// assert_eq!(summary.mean(), 0.25);
+/// assert_eq!(summary.median(), 0.0);
+/// assert_eq!(summary.breadth(), 0.25);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn summarize<'c, I>(coverages: I, min_depth: u32) -> Option<Summary>
+where
+    I: IntoIterator<Item = &'c Coverage>,
+{
+    let depths: Vec<_> = coverages
+        .into_iter()
+        .flat_map(|coverage| coverage.depths().iter().copied())
+        .collect();
+
+    let position_count = depths.len();
+    let mean = mean(&depths)?;
+    let covered_position_count = depths.iter().filter(|&&depth| depth >= min_depth).count();
+    let breadth = covered_position_count as f64 / position_count as f64;
+    let median = median(depths)?;
+
+    Some(Summary {
+        position_count,
+        mean,
+        median,
+        breadth,
+    })
+}
+
+fn mean(depths: &[u32]) -> Option<f64> {
+    if depths.is_empty() {
+        return None;
+    }
+
+    let sum: u64 = depths.iter().map(|&depth| u64::from(depth)).sum();
+    Some(sum as f64 / depths.len() as f64)
+}
+
+fn median(mut depths: Vec<u32>) -> Option<f64> {
+    if depths.is_empty() {
+        return None;
+    }
+
+    depths.sort_unstable();
+
+    let mid = depths.len() / 2;
+
+    if depths.len() % 2 == 0 {
+        Some((f64::from(depths[mid - 1]) + f64::from(depths[mid])) / 2.0)
+    } else {
+        Some(f64::from(depths[mid]))
+    }
+}
+
+/// A run of positions with equal depth.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Interval {
+    start: Position,
+    end: Position,
+    depth: u32,
+}
+
+impl Interval {
+    /// Returns the start position.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end position.
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the depth.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+}
+
+/// Writes the nonzero depth runs of a coverage as bedGraph records.
+///
+/// Each run is written as a BED3 record with the depth as a fourth column.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bed as bed;
+/// use noodles_core::Position;
+/// use noodles_sam::{self as sam, record::Flags};
+/// use noodles_util::alignment::coverage;
+///
+/// let record = sam::alignment::Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_alignment_start(Position::try_from(2)?)
+///     .set_cigar("2M".parse()?)
+///     .build();
+///
+/// let coverage = coverage::Builder::default().build(
+///     Position::try_from(1)?,
+///     Position::try_from(4)?,
+///     [Ok(record)],
+/// )?;
+///
+/// let mut writer = bed::Writer::new(Vec::new());
+/// coverage::write_bedgraph(&mut writer, "sq0", &coverage)?;
+///
+/// assert_eq!(writer.get_ref(), b"sq0\t1\t3\t1\n");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_bedgraph<W>(
+    writer: &mut bed::Writer<W>,
+    reference_sequence_name: &str,
+    coverage: &Coverage,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    for interval in coverage.intervals().filter(|interval| interval.depth() > 0) {
+        let record = bed::Record::<3>::builder()
+            .set_reference_sequence_name(reference_sequence_name)
+            .set_start_position(interval.start())
+            .set_end_position(interval.end())
+            .set_optional_fields(vec![interval.depth().to_string()].into())
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        writer.write_record(&record)?;
+    }
+
+    Ok(())
+}
+
+/// Computes the coverage of each target using an indexed BAM reader.
+///
+/// The returned coverages are in the same order as the targets. Use [`summarize`] to compute
+/// statistics over all target positions.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_bam as bam;
+/// use noodles_bed as bed;
+/// use noodles_core::Position;
+/// use noodles_util::alignment::{coverage, filter::Filter};
+///
+/// let mut reader = bam::indexed_reader::Builder::default().build_from_path("sample.bam")?;
+/// let header = reader.read_header()?;
+///
+/// let targets = [bed::Record::<3>::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_start_position(Position::try_from(8)?)
+///     .set_end_position(Position::try_from(13)?)
+///     .build()?];
+///
+/// let filter = Filter::builder().set_min_mapping_quality(20).build();
+/// let builder = coverage::Builder::default().set_filter(filter);
+///
+/// let coverages = coverage::target_coverage(&mut reader, &header, &builder, &targets)?;
+///
+/// for (target, coverage) in targets.iter().zip(&coverages) {
+///     println!("{}\t{:?}", target.reference_sequence_name(), coverage.mean());
+/// }
+///
+/// if let Some(summary) = coverage::summarize(&coverages, 1) {
+///     println!("all\t{}\t{}\t{}", summary.mean(), summary.median(), summary.breadth());
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn target_coverage<R>(
+    reader: &mut bam::IndexedReader<bgzf::Reader<R>>,
+    header: &sam::Header,
+    builder: &Builder,
+    targets: &[bed::Record<3>],
+) -> io::Result<Vec<Coverage>>
+where
+    R: Read + Seek,
+{
+    targets
+        .iter()
+        .map(|target| {
+            let (start, end) = (target.start_position(), target.end_position());
+            let region = Region::new(target.reference_sequence_name(), start..=end);
+            let query = reader.query(header, &region)?;
+            builder.build(start, end, query)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::{
+        alignment::Record,
+        record::{Flags, MappingQuality},
+    };
+
+    use super::*;
+    use crate::alignment::filter::Filter;
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("3M1D2M".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_alignment_start(Position::try_from(3)?)
+                .set_cigar("1M2N3M".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::DUPLICATE)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_cigar("4M".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_alignment_start(Position::try_from(5)?)
+                .set_cigar("2S6M".parse()?)
+                .build()),
+        ];
+
+        let coverage =
+            Builder::default().build(Position::try_from(2)?, Position::try_from(9)?, records)?;
+
+        assert_eq!(coverage.start(), Position::try_from(2)?);
+        assert_eq!(coverage.end(), Position::new(9));
+        assert_eq!(coverage.depths(), [1, 2, 0, 2, 3, 2, 2, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_mapping_quality_filter() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_alignment_start(Position::MIN)
+            .set_cigar("2M".parse()?)
+            .set_mapping_quality(MappingQuality::try_from(5)?)
+            .build();
+
+        let filter = Filter::builder().set_min_mapping_quality(10).build();
+
+        let coverage = Builder::default().set_filter(filter).build(
+            Position::try_from(1)?,
+            Position::try_from(2)?,
+            [Ok(record)],
+        )?;
+
+        assert_eq!(coverage.depths(), [0, 0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_base_quality_filter() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_alignment_start(Position::MIN)
+            .set_cigar("1S2M1I1M".parse()?)
+            .set_sequence("ACGTA".parse()?)
+            .set_quality_scores("NN!NN".parse()?)
+            .build();
+
+        let filter = Filter::builder().set_min_base_quality(13).build();
+
+        let coverage = Builder::default().set_filter(filter).build(
+            Position::MIN,
+            Position::try_from(3)?,
+            [Ok(record)],
+        )?;
+
+        assert_eq!(coverage.depths(), [1, 0, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary_statistics() {
+        let coverage = Coverage {
+            start: Position::MIN,
+            depths: vec![0, 4, 1, 1],
+        };
+
+        assert_eq!(coverage.mean(), Some(1.5));
+        assert_eq!(coverage.median(), Some(1.0));
+
+        let coverage = Coverage {
+            start: Position::MIN,
+            depths: vec![3, 0, 2],
+        };
+
+        assert_eq!(coverage.median(), Some(2.0));
+
+        let coverage = Coverage {
+            start: Position::MIN,
+            depths: Vec::new(),
+        };
+
+        assert!(coverage.mean().is_none());
+        assert!(coverage.median().is_none());
+        assert!(coverage.end().is_none());
+    }
+
+    #[test]
+    fn test_summarize() {
+        let coverages = [
+            Coverage {
+                start: Position::MIN,
+                depths: vec![2, 2],
+            },
+            Coverage {
+                start: Position::MIN,
+                depths: vec![0, 4, 4, 4, 6, 6],
+            },
+        ];
+
+        assert_eq!(
+            summarize(&coverages, 1),
+            Some(Summary {
+                position_count: 8,
+                mean: 3.5,
+                median: 4.0,
+                breadth: 0.875,
+            })
+        );
+
+        assert_eq!(
+            summarize(&coverages, 5).map(|summary| summary.breadth()),
+            Some(0.25)
+        );
+
+        let coverages = [Coverage {
+            start: Position::MIN,
+            depths: Vec::new(),
+        }];
+
+        assert!(summarize(&coverages, 1).is_none());
+    }
+}
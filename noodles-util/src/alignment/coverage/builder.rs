@@ -0,0 +1,124 @@
+use std::io;
+
+use noodles_core::Position;
+use noodles_sam::{alignment::Record, record::cigar::op::Kind};
+
+use super::Coverage;
+use crate::alignment::filter::Filter;
+
+/// A coverage builder.
+#[derive(Default)]
+pub struct Builder {
+    filter: Filter,
+}
+
+impl Builder {
+    /// Sets the record and base filter.
+    ///
+    /// Records and bases that are not included by the filter are not counted.
+    ///
+    /// By default, this is [`Filter::default`], which excludes records that are unmapped,
+    /// secondary, QC failed, or duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{coverage, filter::Filter};
+    /// let filter = Filter::builder().set_min_mapping_quality(20).build();
+    /// let builder = coverage::Builder::default().set_filter(filter);
+    /// ```
+    pub fn set_filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Computes the coverage of the interval [`start`, `end`] from the given records.
+    ///
+    /// The records are expected to be on the same reference sequence, e.g., the results of a
+    /// query. Only aligned bases (`M`, `=`, and `X`) are counted; deletions and skipped regions
+    /// are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::{self as sam, record::Flags};
+    /// use noodles_util::alignment::coverage;
+    ///
+    /// let record = sam::alignment::Record::builder()
+    ///     .set_flags(Flags::empty())
+    ///     .set_alignment_start(Position::try_from(2)?)
+    ///     .set_cigar("2M".parse()?)
+    ///     .build();
+    ///
+    /// let coverage = coverage::Builder::default().build(
+    ///     Position::try_from(1)?,
+    ///     Position::try_from(4)?,
+    ///     [Ok(record)],
+    /// )?;
+    ///
+    /// assert_eq!(coverage.depths(), [0, 1, 1, 0]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn build<I>(&self, start: Position, end: Position, records: I) -> io::Result<Coverage>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        let start_position = start;
+        let (start, end) = (usize::from(start), usize::from(end));
+
+        if end < start {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid interval: end < start",
+            ));
+        }
+
+        let len = end - start + 1;
+        let mut depths = vec![0; len];
+
+        for result in records {
+            let record = result?;
+
+            if !self.filter.is_included(&record) {
+                continue;
+            }
+
+            let mut reference_start = match record.alignment_start() {
+                Some(position) => usize::from(position),
+                None => continue,
+            };
+
+            let quality_scores = record.quality_scores().as_ref();
+            let mut read_position = 0;
+
+            for op in record.cigar().iter() {
+                match op.kind() {
+                    Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                        for i in 0..op.len() {
+                            let reference_position = reference_start + i;
+                            let quality_score = quality_scores.get(read_position + i).copied();
+
+                            if (start..=end).contains(&reference_position)
+                                && self.filter.is_base_included(quality_score)
+                            {
+                                depths[reference_position - start] += 1;
+                            }
+                        }
+
+                        reference_start += op.len();
+                        read_position += op.len();
+                    }
+                    Kind::Insertion | Kind::SoftClip => read_position += op.len(),
+                    Kind::Deletion | Kind::Skip => reference_start += op.len(),
+                    Kind::HardClip | Kind::Pad => {}
+                }
+            }
+        }
+
+        Ok(Coverage {
+            start: start_position,
+            depths,
+        })
+    }
+}
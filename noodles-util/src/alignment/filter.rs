@@ -0,0 +1,201 @@
+//! Alignment record filter.
+//!
+//! A [`Filter`] selects records by flags and mapping quality and bases by quality score. It is
+//! shared by record consumers, e.g., [`super::pileup`] and [`super::coverage`].
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use noodles_sam::{
+    alignment::Record,
+    record::{mapping_quality, quality_scores::Score, Flags},
+};
+
+/// An alignment record filter.
+///
+/// This is created by calling [`Builder::build`]. The default filter excludes records that are
+/// unmapped, secondary, QC failed, or duplicates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Filter {
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    required_flags: Flags,
+    excluded_flags: Flags,
+}
+
+impl Filter {
+    /// Creates an alignment record filter builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::filter::Filter;
+    /// let builder = Filter::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the minimum mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::filter::Filter;
+    /// let filter = Filter::builder().set_min_mapping_quality(20).build();
+    /// assert_eq!(filter.min_mapping_quality(), 20);
+    /// ```
+    pub fn min_mapping_quality(&self) -> u8 {
+        self.min_mapping_quality
+    }
+
+    /// Returns the minimum base quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::filter::Filter;
+    /// let filter = Filter::builder().set_min_base_quality(13).build();
+    /// assert_eq!(filter.min_base_quality(), 13);
+    /// ```
+    pub fn min_base_quality(&self) -> u8 {
+        self.min_base_quality
+    }
+
+    /// Returns the flags that must all be set for a record to be included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Flags;
+    /// use noodles_util::alignment::filter::Filter;
+    /// let filter = Filter::default();
+    /// assert!(filter.required_flags().is_empty());
+    /// ```
+    pub fn required_flags(&self) -> Flags {
+        self.required_flags
+    }
+
+    /// Returns the flags of which any being set excludes a record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Flags;
+    /// use noodles_util::alignment::filter::Filter;
+    ///
+    /// let filter = Filter::default();
+    ///
+    /// assert_eq!(
+    ///     filter.excluded_flags(),
+    ///     Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+    /// );
+    /// ```
+    pub fn excluded_flags(&self) -> Flags {
+        self.excluded_flags
+    }
+
+    /// Returns whether a record passes the flag and mapping quality filters.
+    ///
+    /// A missing mapping quality is treated as 255.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::Flags};
+    /// use noodles_util::alignment::filter::Filter;
+    ///
+    /// let filter = Filter::default();
+    ///
+    /// let record = sam::alignment::Record::builder().set_flags(Flags::empty()).build();
+    /// assert!(filter.is_included(&record));
+    ///
+    /// let record = sam::alignment::Record::builder().set_flags(Flags::DUPLICATE).build();
+    /// assert!(!filter.is_included(&record));
+    /// ```
+    pub fn is_included(&self, record: &Record) -> bool {
+        let flags = record.flags();
+
+        if !flags.contains(self.required_flags) || flags.intersects(self.excluded_flags) {
+            return false;
+        }
+
+        let mapping_quality = record
+            .mapping_quality()
+            .map(u8::from)
+            .unwrap_or(mapping_quality::MISSING);
+
+        mapping_quality >= self.min_mapping_quality
+    }
+
+    /// Returns whether a base passes the base quality filter.
+    ///
+    /// A base without a quality score is always included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::quality_scores::Score;
+    /// use noodles_util::alignment::filter::Filter;
+    ///
+    /// let filter = Filter::builder().set_min_base_quality(13).build();
+    ///
+    /// assert!(filter.is_base_included(Some(Score::try_from(21)?)));
+    /// assert!(!filter.is_base_included(Some(Score::try_from(8)?)));
+    /// assert!(filter.is_base_included(None));
+    /// # Ok::<_, noodles_sam::record::quality_scores::score::TryFromUByteError>(())
+    /// ```
+    pub fn is_base_included(&self, quality_score: Option<Score>) -> bool {
+        quality_score
+            .map(|score| u8::from(score) >= self.min_base_quality)
+            .unwrap_or(true)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Builder::default().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::MappingQuality;
+
+    use super::*;
+
+    #[test]
+    fn test_is_included() -> Result<(), Box<dyn std::error::Error>> {
+        let filter = Filter::builder()
+            .set_min_mapping_quality(10)
+            .set_required_flags(Flags::PROPERLY_ALIGNED)
+            .set_excluded_flags(Flags::DUPLICATE)
+            .build();
+
+        let record = Record::builder()
+            .set_flags(Flags::PROPERLY_ALIGNED)
+            .set_mapping_quality(MappingQuality::try_from(13)?)
+            .build();
+        assert!(filter.is_included(&record));
+
+        let record = Record::builder().set_flags(Flags::PROPERLY_ALIGNED).build();
+        assert!(filter.is_included(&record));
+
+        let record = Record::builder()
+            .set_flags(Flags::PROPERLY_ALIGNED)
+            .set_mapping_quality(MappingQuality::try_from(5)?)
+            .build();
+        assert!(!filter.is_included(&record));
+
+        let record = Record::builder().set_flags(Flags::empty()).build();
+        assert!(!filter.is_included(&record));
+
+        let record = Record::builder()
+            .set_flags(Flags::PROPERLY_ALIGNED | Flags::DUPLICATE)
+            .build();
+        assert!(!filter.is_included(&record));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use noodles_sam::record::Flags;
+
+use super::Filter;
+
+/// An alignment record filter builder.
+pub struct Builder {
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    required_flags: Flags,
+    excluded_flags: Flags,
+}
+
+impl Builder {
+    /// Sets the minimum mapping quality.
+    ///
+    /// Records with a lower mapping quality are excluded. A missing mapping quality is treated as
+    /// 255. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::filter;
+    /// let builder = filter::Builder::default().set_min_mapping_quality(20);
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.min_mapping_quality = min_mapping_quality;
+        self
+    }
+
+    /// Sets the minimum base quality.
+    ///
+    /// Bases with a lower quality score are excluded. Bases without quality scores are always
+    /// included. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::filter;
+    /// let builder = filter::Builder::default().set_min_base_quality(13);
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: u8) -> Self {
+        self.min_base_quality = min_base_quality;
+        self
+    }
+
+    /// Sets the flags that must all be set for a record to be included.
+    ///
+    /// By default, no flags are required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Flags;
+    /// use noodles_util::alignment::filter;
+    /// let builder = filter::Builder::default().set_required_flags(Flags::PROPERLY_ALIGNED);
+    /// ```
+    pub fn set_required_flags(mut self, required_flags: Flags) -> Self {
+        self.required_flags = required_flags;
+        self
+    }
+
+    /// Sets the flags of which any being set excludes a record.
+    ///
+    /// By default, records that are unmapped, secondary, QC failed, or duplicates are excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Flags;
+    /// use noodles_util::alignment::filter;
+    /// let builder = filter::Builder::default().set_excluded_flags(Flags::UNMAPPED);
+    /// ```
+    pub fn set_excluded_flags(mut self, excluded_flags: Flags) -> Self {
+        self.excluded_flags = excluded_flags;
+        self
+    }
+
+    /// Builds an alignment record filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::filter;
+    /// let filter = filter::Builder::default().build();
+    /// ```
+    pub fn build(self) -> Filter {
+        Filter {
+            min_mapping_quality: self.min_mapping_quality,
+            min_base_quality: self.min_base_quality,
+            required_flags: self.required_flags,
+            excluded_flags: self.excluded_flags,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            min_mapping_quality: 0,
+            min_base_quality: 0,
+            required_flags: Flags::empty(),
+            excluded_flags: Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+        }
+    }
+}
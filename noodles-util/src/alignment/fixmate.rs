@@ -0,0 +1,401 @@
+//! Alignment mate information fixing.
+//!
+//! This makes the mate fields of paired records consistent with each other, similar to
+//! `samtools fixmate`.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{cmp, collections::VecDeque, io};
+
+use noodles_sam::{
+    alignment::Record,
+    record::{
+        data::field::{Tag, Value},
+        mapping_quality, Flags,
+    },
+};
+
+/// An iterator over records with fixed mate information.
+///
+/// This is created by calling [`Builder::build`]. The input must be grouped by read name, e.g.,
+/// sorted by query name.
+///
+/// For each template with exactly two primary records that are paired in sequencing, each
+/// record's mate reference sequence ID, mate alignment start, mate flags (reverse complemented and
+/// unmapped), and template length are set from the other record. An unmapped record with a mapped
+/// mate is placed at its mate's position, and the properly aligned flag is cleared if either
+/// record is unmapped. Secondary and supplementary records are given the mate information of the
+/// primary record of the other segment.
+///
+/// Other templates are yielded unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_sam::{self as sam, record::{Flags, ReadName}};
+/// use noodles_util::alignment::fixmate;
+///
+/// let read_name: ReadName = "r0".parse()?;
+///
+/// let records = [
+///     sam::alignment::Record::builder()
+///         .set_read_name(read_name.clone())
+///         .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT)
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(8)?)
+///         .set_cigar("4M".parse()?)
+///         .build(),
+///     sam::alignment::Record::builder()
+///         .set_read_name(read_name)
+///         .set_flags(Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED)
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(13)?)
+///         .set_cigar("4M".parse()?)
+///         .build(),
+/// ];
+///
+/// let records = fixmate::Builder::default()
+///     .build(records.into_iter().map(Ok))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(records[0].mate_alignment_start(), Position::new(13));
+/// assert!(records[0].flags().is_mate_reverse_complemented());
+/// assert_eq!(records[0].template_length(), 9);
+/// assert_eq!(records[1].mate_alignment_start(), Position::new(8));
+/// assert_eq!(records[1].template_length(), -9);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct FixMate<I> {
+    records: I,
+    add_mate_tags: bool,
+    pending: Option<Record>,
+    buf: VecDeque<Record>,
+}
+
+impl<I> FixMate<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    fn read_template(&mut self) -> io::Result<()> {
+        let first = match self.pending.take() {
+            Some(record) => record,
+            None => match self.records.next() {
+                Some(result) => result?,
+                None => return Ok(()),
+            },
+        };
+
+        let mut template = vec![first];
+
+        if template[0].read_name().is_some() {
+            for result in &mut self.records {
+                let record = result?;
+
+                if record.read_name() == template[0].read_name() {
+                    template.push(record);
+                } else {
+                    self.pending = Some(record);
+                    break;
+                }
+            }
+        }
+
+        fix_template(&mut template, self.add_mate_tags);
+
+        self.buf.extend(template);
+
+        Ok(())
+    }
+}
+
+impl<I> Iterator for FixMate<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            if let Err(e) = self.read_template() {
+                return Some(Err(e));
+            }
+        }
+
+        self.buf.pop_front().map(Ok)
+    }
+}
+
+fn is_primary(flags: Flags) -> bool {
+    !flags.is_secondary() && !flags.is_supplementary()
+}
+
+fn fix_template(records: &mut [Record], add_mate_tags: bool) {
+    let primary_indices: Vec<_> = records
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| is_primary(record.flags()))
+        .map(|(i, _)| i)
+        .collect();
+
+    let (i, j) = match primary_indices[..] {
+        [i, j] => (i, j),
+        _ => return,
+    };
+
+    if !records[i].flags().is_segmented() || !records[j].flags().is_segmented() {
+        return;
+    }
+
+    place_unmapped(records, i, j);
+    place_unmapped(records, j, i);
+
+    let (a, b) = (records[i].clone(), records[j].clone());
+
+    for (k, record) in records.iter_mut().enumerate() {
+        let mate = if k == i {
+            &b
+        } else if k == j {
+            &a
+        } else {
+            let flags = record.flags();
+
+            if flags.is_first_segment() == a.flags().is_first_segment()
+                && flags.is_last_segment() == a.flags().is_last_segment()
+            {
+                &b
+            } else if flags.is_first_segment() == b.flags().is_first_segment()
+                && flags.is_last_segment() == b.flags().is_last_segment()
+            {
+                &a
+            } else {
+                continue;
+            }
+        };
+
+        set_mate(record, mate, add_mate_tags);
+    }
+}
+
+// Places an unmapped record at the position of its mapped mate.
+fn place_unmapped(records: &mut [Record], i: usize, j: usize) {
+    if records[i].flags().is_unmapped() && !records[j].flags().is_unmapped() {
+        let reference_sequence_id = records[j].reference_sequence_id();
+        let alignment_start = records[j].alignment_start();
+
+        let record = &mut records[i];
+        *record.reference_sequence_id_mut() = reference_sequence_id;
+        *record.alignment_start_mut() = alignment_start;
+    }
+}
+
+fn set_mate(record: &mut Record, mate: &Record, add_mate_tags: bool) {
+    let mate_flags = mate.flags();
+
+    let flags = record.flags_mut();
+    flags.set(
+        Flags::MATE_REVERSE_COMPLEMENTED,
+        mate_flags.is_reverse_complemented(),
+    );
+    flags.set(Flags::MATE_UNMAPPED, mate_flags.is_unmapped());
+
+    if flags.is_unmapped() || mate_flags.is_unmapped() {
+        flags.remove(Flags::PROPERLY_ALIGNED);
+    }
+
+    *record.mate_reference_sequence_id_mut() = mate.reference_sequence_id();
+    *record.mate_alignment_start_mut() = mate.alignment_start();
+    *record.template_length_mut() = calculate_template_length(record, mate);
+
+    if add_mate_tags {
+        let data = record.data_mut();
+
+        if mate_flags.is_unmapped() {
+            data.remove(Tag::MateCigar);
+            data.remove(Tag::MateMappingQuality);
+        } else {
+            data.insert(Tag::MateCigar, Value::String(mate.cigar().to_string()));
+
+            let mate_mapping_quality = mate
+                .mapping_quality()
+                .map(u8::from)
+                .unwrap_or(mapping_quality::MISSING);
+
+            data.insert(Tag::MateMappingQuality, Value::from(mate_mapping_quality));
+        }
+    }
+}
+
+// _Sequence Alignment/Map Format Specification_ (2021-06-03) § 1.4.9 "TLEN"
+fn calculate_template_length(record: &Record, mate: &Record) -> i32 {
+    if record.flags().is_unmapped()
+        || mate.flags().is_unmapped()
+        || record.reference_sequence_id() != mate.reference_sequence_id()
+    {
+        return 0;
+    }
+
+    let (start, mate_start) = match (record.alignment_start(), mate.alignment_start()) {
+        (Some(start), Some(mate_start)) => (start, mate_start),
+        _ => return 0,
+    };
+
+    let (end, mate_end) = match (record.alignment_end(), mate.alignment_end()) {
+        (Some(end), Some(mate_end)) => (end, mate_end),
+        _ => return 0,
+    };
+
+    let template_start = usize::from(cmp::min(start, mate_start));
+    let template_end = usize::from(cmp::max(end, mate_end));
+    let len = i32::try_from(template_end - template_start + 1).unwrap_or(i32::MAX);
+
+    // "The TLEN field is positive for the leftmost segment of the template, negative for the
+    // rightmost... If segments cover the same coordinates then the choice of which is leftmost
+    // and rightmost is arbitrary..."
+    let is_leftmost = match start.cmp(&mate_start) {
+        cmp::Ordering::Less => true,
+        cmp::Ordering::Greater => false,
+        cmp::Ordering::Equal => record.flags().is_first_segment(),
+    };
+
+    if is_leftmost {
+        len
+    } else {
+        -len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_sam::record::MappingQuality;
+
+    use super::*;
+
+    fn fix(records: Vec<Record>) -> io::Result<Vec<Record>> {
+        Builder::default()
+            .build(records.into_iter().map(Ok))
+            .collect()
+    }
+
+    #[test]
+    fn test_fix_mates() -> Result<(), Box<dyn std::error::Error>> {
+        let r1 = Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::PROPERLY_ALIGNED;
+        let r2 = Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::PROPERLY_ALIGNED;
+
+        let records = fix(vec![
+            Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(r1 | Flags::MATE_UNMAPPED)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(13)?)
+                .set_cigar("6M".parse()?)
+                .set_mapping_quality(MappingQuality::try_from(30)?)
+                .build(),
+            Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(r2 | Flags::REVERSE_COMPLEMENTED)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(8)?)
+                .set_cigar("4M".parse()?)
+                .set_mapping_quality(MappingQuality::try_from(30)?)
+                .build(),
+            Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(r2 | Flags::SUPPLEMENTARY)
+                .set_reference_sequence_id(1)
+                .set_alignment_start(Position::try_from(21)?)
+                .set_cigar("2M".parse()?)
+                .set_mapping_quality(MappingQuality::try_from(30)?)
+                .build(),
+            Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::MIN)
+                .set_cigar("1M".parse()?)
+                .set_mapping_quality(MappingQuality::try_from(30)?)
+                .build(),
+        ])?;
+
+        assert_eq!(records.len(), 4);
+
+        let record = &records[0];
+        assert!(record.flags().is_mate_reverse_complemented());
+        assert!(!record.flags().is_mate_unmapped());
+        assert!(record.flags().is_properly_aligned());
+        assert_eq!(record.mate_reference_sequence_id(), Some(0));
+        assert_eq!(record.mate_alignment_start(), Position::new(8));
+        assert_eq!(record.template_length(), -11);
+        assert_eq!(
+            record.data().get(Tag::MateCigar),
+            Some(&Value::String(String::from("4M")))
+        );
+        assert_eq!(
+            record.data().get(Tag::MateMappingQuality),
+            Some(&Value::from(30u8))
+        );
+
+        let record = &records[1];
+        assert!(!record.flags().is_mate_reverse_complemented());
+        assert_eq!(record.mate_alignment_start(), Position::new(13));
+        assert_eq!(record.template_length(), 11);
+
+        let record = &records[2];
+        assert_eq!(record.mate_reference_sequence_id(), Some(0));
+        assert_eq!(record.mate_alignment_start(), Position::new(13));
+        assert_eq!(record.template_length(), 0);
+        assert_eq!(
+            record.data().get(Tag::MateCigar),
+            Some(&Value::String(String::from("6M")))
+        );
+
+        let record = &records[3];
+        assert!(record.mate_alignment_start().is_none());
+        assert!(record.data().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mates_with_unmapped_mate() -> Result<(), Box<dyn std::error::Error>> {
+        let r1 = Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::PROPERLY_ALIGNED;
+        let r2 = Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::UNMAPPED;
+
+        let records = Builder::default().set_add_mate_tags(false).build(vec![
+            Ok(Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(r1)
+                .set_reference_sequence_id(2)
+                .set_alignment_start(Position::try_from(5)?)
+                .set_cigar("4M".parse()?)
+                .set_mapping_quality(MappingQuality::try_from(30)?)
+                .build()),
+            Ok(Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(r2)
+                .set_mapping_quality(MappingQuality::try_from(30)?)
+                .build()),
+        ]);
+        let records = records.collect::<io::Result<Vec<_>>>()?;
+
+        let record = &records[0];
+        assert!(record.flags().is_mate_unmapped());
+        assert!(!record.flags().is_properly_aligned());
+        assert_eq!(record.mate_reference_sequence_id(), Some(2));
+        assert_eq!(record.mate_alignment_start(), Position::new(5));
+        assert_eq!(record.template_length(), 0);
+        assert!(record.data().is_empty());
+
+        let record = &records[1];
+        assert_eq!(record.reference_sequence_id(), Some(2));
+        assert_eq!(record.alignment_start(), Position::new(5));
+        assert_eq!(record.mate_alignment_start(), Position::new(5));
+        assert!(!record.flags().is_mate_unmapped());
+
+        Ok(())
+    }
+}
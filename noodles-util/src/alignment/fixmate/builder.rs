@@ -0,0 +1,60 @@
+use std::io;
+
+use noodles_sam::alignment::Record;
+
+use super::FixMate;
+
+/// A mate information fixer builder.
+pub struct Builder {
+    add_mate_tags: bool,
+}
+
+impl Builder {
+    /// Sets whether to add mate CIGAR (`MC`) and mate mapping quality (`MQ`) data fields.
+    ///
+    /// The fields are only added when the mate is mapped. By default, this is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::fixmate;
+    /// let builder = fixmate::Builder::default().set_add_mate_tags(false);
+    /// ```
+    pub fn set_add_mate_tags(mut self, add_mate_tags: bool) -> Self {
+        self.add_mate_tags = add_mate_tags;
+        self
+    }
+
+    /// Builds a mate information fixer from an iterator of name-grouped records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_sam::alignment::Record;
+    /// use noodles_util::alignment::fixmate;
+    ///
+    /// let records = Vec::<io::Result<Record>>::new();
+    /// let mut records = fixmate::Builder::default().build(records);
+    /// assert!(records.next().is_none());
+    /// ```
+    pub fn build<I>(self, records: I) -> FixMate<I::IntoIter>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        FixMate {
+            records: records.into_iter(),
+            add_mate_tags: self.add_mate_tags,
+            pending: None,
+            buf: Default::default(),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            add_mate_tags: true,
+        }
+    }
+}
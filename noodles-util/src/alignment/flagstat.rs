@@ -0,0 +1,596 @@
+//! Alignment flag statistics.
+//!
+//! This computes the same counts as `samtools flagstat`.
+
+use std::{fmt, io};
+
+use noodles_bam as bam;
+use noodles_sam::{
+    self as sam,
+    record::{mapping_quality, Flags, MappingQuality},
+};
+
+const HIGH_MAPPING_QUALITY: u8 = 5;
+
+/// Flag counts of a set of alignment records.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Counts {
+    read: u64,
+    primary: u64,
+    secondary: u64,
+    supplementary: u64,
+    duplicate: u64,
+    primary_duplicate: u64,
+    mapped: u64,
+    primary_mapped: u64,
+    paired: u64,
+    read_1: u64,
+    read_2: u64,
+    proper_pair: u64,
+    mate_mapped: u64,
+    singleton: u64,
+    mate_reference_sequence_id_mismatch: u64,
+    mate_reference_sequence_id_mismatch_hq: u64,
+}
+
+impl Counts {
+    /// Returns the number of records.
+    pub fn read(&self) -> u64 {
+        self.read
+    }
+
+    /// Returns the number of primary records.
+    pub fn primary(&self) -> u64 {
+        self.primary
+    }
+
+    /// Returns the number of secondary records.
+    pub fn secondary(&self) -> u64 {
+        self.secondary
+    }
+
+    /// Returns the number of supplementary records.
+    pub fn supplementary(&self) -> u64 {
+        self.supplementary
+    }
+
+    /// Returns the number of records marked as duplicates.
+    pub fn duplicate(&self) -> u64 {
+        self.duplicate
+    }
+
+    /// Returns the number of primary records marked as duplicates.
+    pub fn primary_duplicate(&self) -> u64 {
+        self.primary_duplicate
+    }
+
+    /// Returns the number of mapped records.
+    pub fn mapped(&self) -> u64 {
+        self.mapped
+    }
+
+    /// Returns the number of mapped primary records.
+    pub fn primary_mapped(&self) -> u64 {
+        self.primary_mapped
+    }
+
+    /// Returns the number of primary records that are paired in sequencing.
+    pub fn paired(&self) -> u64 {
+        self.paired
+    }
+
+    /// Returns the number of paired primary records that are the first segment.
+    pub fn read_1(&self) -> u64 {
+        self.read_1
+    }
+
+    /// Returns the number of paired primary records that are the last segment.
+    pub fn read_2(&self) -> u64 {
+        self.read_2
+    }
+
+    /// Returns the number of mapped paired primary records that are properly aligned.
+    pub fn proper_pair(&self) -> u64 {
+        self.proper_pair
+    }
+
+    /// Returns the number of mapped paired primary records with a mapped mate.
+    pub fn mate_mapped(&self) -> u64 {
+        self.mate_mapped
+    }
+
+    /// Returns the number of mapped paired primary records with an unmapped mate.
+    pub fn singleton(&self) -> u64 {
+        self.singleton
+    }
+
+    /// Returns the number of mapped paired primary records with a mate mapped to a different
+    /// reference sequence.
+    pub fn mate_reference_sequence_id_mismatch(&self) -> u64 {
+        self.mate_reference_sequence_id_mismatch
+    }
+
+    /// Returns the number of mapped paired primary records with a mate mapped to a different
+    /// reference sequence and a mapping quality of at least 5.
+    pub fn mate_reference_sequence_id_mismatch_hq(&self) -> u64 {
+        self.mate_reference_sequence_id_mismatch_hq
+    }
+
+    fn add(
+        &mut self,
+        flags: Flags,
+        reference_sequence_id: Option<usize>,
+        mate_reference_sequence_id: Option<usize>,
+        mapping_quality: Option<MappingQuality>,
+    ) {
+        self.read += 1;
+
+        if !flags.is_unmapped() {
+            self.mapped += 1;
+        }
+
+        if flags.is_duplicate() {
+            self.duplicate += 1;
+        }
+
+        if flags.is_secondary() {
+            self.secondary += 1;
+        } else if flags.is_supplementary() {
+            self.supplementary += 1;
+        } else {
+            self.primary += 1;
+
+            if !flags.is_unmapped() {
+                self.primary_mapped += 1;
+            }
+
+            if flags.is_duplicate() {
+                self.primary_duplicate += 1;
+            }
+
+            if flags.is_segmented() {
+                self.paired += 1;
+
+                if flags.is_first_segment() {
+                    self.read_1 += 1;
+                }
+
+                if flags.is_last_segment() {
+                    self.read_2 += 1;
+                }
+
+                if !flags.is_unmapped() {
+                    if flags.is_properly_aligned() {
+                        self.proper_pair += 1;
+                    }
+
+                    if flags.is_mate_unmapped() {
+                        self.singleton += 1;
+                    } else {
+                        self.mate_mapped += 1;
+
+                        if mate_reference_sequence_id != reference_sequence_id {
+                            self.mate_reference_sequence_id_mismatch += 1;
+
+                            let mapq = mapping_quality
+                                .map(u8::from)
+                                .unwrap_or(mapping_quality::MISSING);
+
+                            if mapq >= HIGH_MAPPING_QUALITY {
+                                self.mate_reference_sequence_id_mismatch_hq += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.read += other.read;
+        self.primary += other.primary;
+        self.secondary += other.secondary;
+        self.supplementary += other.supplementary;
+        self.duplicate += other.duplicate;
+        self.primary_duplicate += other.primary_duplicate;
+        self.mapped += other.mapped;
+        self.primary_mapped += other.primary_mapped;
+        self.paired += other.paired;
+        self.read_1 += other.read_1;
+        self.read_2 += other.read_2;
+        self.proper_pair += other.proper_pair;
+        self.mate_mapped += other.mate_mapped;
+        self.singleton += other.singleton;
+        self.mate_reference_sequence_id_mismatch += other.mate_reference_sequence_id_mismatch;
+        self.mate_reference_sequence_id_mismatch_hq += other.mate_reference_sequence_id_mismatch_hq;
+    }
+}
+
+/// Alignment flag statistics.
+///
+/// Records are counted separately by whether they passed quality control (QC). The statistics of
+/// multiple inputs, e.g., of records counted in parallel, can be combined using [`Self::merge`].
+///
+/// The [`fmt::Display`] implementation matches the output of `samtools flagstat`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FlagStatistics {
+    qc_pass: Counts,
+    qc_fail: Counts,
+}
+
+impl FlagStatistics {
+    /// Returns the counts of records that passed QC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::flagstat::FlagStatistics;
+    /// let stats = FlagStatistics::default();
+    /// assert_eq!(stats.qc_pass().read(), 0);
+    /// ```
+    pub fn qc_pass(&self) -> &Counts {
+        &self.qc_pass
+    }
+
+    /// Returns the counts of records that failed QC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::flagstat::FlagStatistics;
+    /// let stats = FlagStatistics::default();
+    /// assert_eq!(stats.qc_fail().read(), 0);
+    /// ```
+    pub fn qc_fail(&self) -> &Counts {
+        &self.qc_fail
+    }
+
+    /// Adds an alignment record to the statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::Flags};
+    /// use noodles_util::alignment::flagstat::FlagStatistics;
+    ///
+    /// let mut stats = FlagStatistics::default();
+    ///
+    /// let record = sam::alignment::Record::default();
+    /// stats.add_record(&record);
+    ///
+    /// let record = sam::alignment::Record::builder()
+    ///     .set_flags(Flags::UNMAPPED | Flags::QC_FAIL)
+    ///     .build();
+    /// stats.add_record(&record);
+    ///
+    /// assert_eq!(stats.qc_pass().read(), 1);
+    /// assert_eq!(stats.qc_fail().read(), 1);
+    /// ```
+    pub fn add_record(&mut self, record: &sam::alignment::Record) {
+        self.add(
+            record.flags(),
+            record.reference_sequence_id(),
+            record.mate_reference_sequence_id(),
+            record.mapping_quality(),
+        );
+    }
+
+    /// Adds a lazy BAM record to the statistics.
+    ///
+    /// Only the fields needed to compute the statistics are decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_util::alignment::flagstat::FlagStatistics;
+    ///
+    /// let mut stats = FlagStatistics::default();
+    ///
+    /// let record = bam::lazy::Record::default();
+    /// stats.add_lazy_record(&record)?;
+    ///
+    /// assert_eq!(stats.qc_pass().read(), 1);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn add_lazy_record(&mut self, record: &bam::lazy::Record) -> io::Result<()> {
+        self.add(
+            record.flags()?,
+            record.reference_sequence_id()?,
+            record.mate_reference_sequence_id()?,
+            record.mapping_quality()?,
+        );
+
+        Ok(())
+    }
+
+    /// Merges other statistics into these statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::flagstat::FlagStatistics;
+    ///
+    /// let record = sam::alignment::Record::default();
+    ///
+    /// let mut a = FlagStatistics::default();
+    /// a.add_record(&record);
+    ///
+    /// let mut b = FlagStatistics::default();
+    /// b.add_record(&record);
+    ///
+    /// a.merge(&b);
+    /// assert_eq!(a.qc_pass().read(), 2);
+    /// ```
+    pub fn merge(&mut self, other: &Self) {
+        self.qc_pass.merge(&other.qc_pass);
+        self.qc_fail.merge(&other.qc_fail);
+    }
+
+    fn add(
+        &mut self,
+        flags: Flags,
+        reference_sequence_id: Option<usize>,
+        mate_reference_sequence_id: Option<usize>,
+        mapping_quality: Option<MappingQuality>,
+    ) {
+        let counts = if flags.is_qc_fail() {
+            &mut self.qc_fail
+        } else {
+            &mut self.qc_pass
+        };
+
+        counts.add(
+            flags,
+            reference_sequence_id,
+            mate_reference_sequence_id,
+            mapping_quality,
+        );
+    }
+}
+
+impl<'a> Extend<&'a sam::alignment::Record> for FlagStatistics {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = &'a sam::alignment::Record>,
+    {
+        for record in iter {
+            self.add_record(record);
+        }
+    }
+}
+
+struct PercentageFormat(u64, u64);
+
+impl fmt::Display for PercentageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.1 == 0 {
+            f.write_str("N/A")
+        } else {
+            let (a, b) = (self.0 as f64, self.1 as f64);
+            write!(f, "{:.2}%", a / b * 100.0)
+        }
+    }
+}
+
+impl fmt::Display for FlagStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (pass, fail) = (&self.qc_pass, &self.qc_fail);
+
+        writeln!(
+            f,
+            "{} + {} in total (QC-passed reads + QC-failed reads)",
+            pass.read, fail.read
+        )?;
+        writeln!(f, "{} + {} primary", pass.primary, fail.primary)?;
+        writeln!(f, "{} + {} secondary", pass.secondary, fail.secondary)?;
+        writeln!(
+            f,
+            "{} + {} supplementary",
+            pass.supplementary, fail.supplementary
+        )?;
+        writeln!(f, "{} + {} duplicates", pass.duplicate, fail.duplicate)?;
+        writeln!(
+            f,
+            "{} + {} primary duplicates",
+            pass.primary_duplicate, fail.primary_duplicate
+        )?;
+        writeln!(
+            f,
+            "{} + {} mapped ({} : {})",
+            pass.mapped,
+            fail.mapped,
+            PercentageFormat(pass.mapped, pass.read),
+            PercentageFormat(fail.mapped, fail.read)
+        )?;
+        writeln!(
+            f,
+            "{} + {} primary mapped ({} : {})",
+            pass.primary_mapped,
+            fail.primary_mapped,
+            PercentageFormat(pass.primary_mapped, pass.primary),
+            PercentageFormat(fail.primary_mapped, fail.primary)
+        )?;
+        writeln!(f, "{} + {} paired in sequencing", pass.paired, fail.paired)?;
+        writeln!(f, "{} + {} read1", pass.read_1, fail.read_1)?;
+        writeln!(f, "{} + {} read2", pass.read_2, fail.read_2)?;
+        writeln!(
+            f,
+            "{} + {} properly paired ({} : {})",
+            pass.proper_pair,
+            fail.proper_pair,
+            PercentageFormat(pass.proper_pair, pass.paired),
+            PercentageFormat(fail.proper_pair, fail.paired)
+        )?;
+        writeln!(
+            f,
+            "{} + {} with itself and mate mapped",
+            pass.mate_mapped, fail.mate_mapped
+        )?;
+        writeln!(
+            f,
+            "{} + {} singletons ({} : {})",
+            pass.singleton,
+            fail.singleton,
+            PercentageFormat(pass.singleton, pass.paired),
+            PercentageFormat(fail.singleton, fail.paired)
+        )?;
+        writeln!(
+            f,
+            "{} + {} with mate mapped to a different chr",
+            pass.mate_reference_sequence_id_mismatch, fail.mate_reference_sequence_id_mismatch
+        )?;
+        writeln!(
+            f,
+            "{} + {} with mate mapped to a different chr (mapQ>=5)",
+            pass.mate_reference_sequence_id_mismatch_hq,
+            fail.mate_reference_sequence_id_mismatch_hq
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sam::{alignment::Record, record::Flags};
+
+    use super::*;
+
+    #[test]
+    fn test_add_record() -> Result<(), Box<dyn std::error::Error>> {
+        let paired = Flags::SEGMENTED | Flags::PROPERLY_ALIGNED;
+
+        let records = [
+            Record::builder()
+                .set_flags(paired | Flags::FIRST_SEGMENT)
+                .set_reference_sequence_id(0)
+                .set_mate_reference_sequence_id(0)
+                .set_mapping_quality(MappingQuality::try_from(60)?)
+                .build(),
+            Record::builder()
+                .set_flags(paired | Flags::LAST_SEGMENT)
+                .set_reference_sequence_id(0)
+                .set_mate_reference_sequence_id(1)
+                .set_mapping_quality(MappingQuality::try_from(60)?)
+                .build(),
+            Record::builder()
+                .set_flags(paired | Flags::FIRST_SEGMENT)
+                .set_reference_sequence_id(1)
+                .set_mate_reference_sequence_id(0)
+                .set_mapping_quality(MappingQuality::try_from(3)?)
+                .build(),
+            Record::builder()
+                .set_flags(Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::MATE_UNMAPPED)
+                .set_reference_sequence_id(1)
+                .set_mapping_quality(MappingQuality::try_from(60)?)
+                .build(),
+            Record::builder()
+                .set_flags(Flags::SECONDARY)
+                .set_reference_sequence_id(0)
+                .set_mapping_quality(MappingQuality::MIN)
+                .build(),
+            Record::builder()
+                .set_flags(Flags::SUPPLEMENTARY | Flags::DUPLICATE)
+                .set_reference_sequence_id(0)
+                .set_mapping_quality(MappingQuality::MIN)
+                .build(),
+            Record::builder()
+                .set_flags(Flags::UNMAPPED | Flags::DUPLICATE)
+                .build(),
+            Record::builder()
+                .set_flags(Flags::UNMAPPED | Flags::QC_FAIL)
+                .build(),
+        ];
+
+        let mut stats = FlagStatistics::default();
+        stats.extend(&records);
+
+        let expected = Counts {
+            read: 7,
+            primary: 5,
+            secondary: 1,
+            supplementary: 1,
+            duplicate: 2,
+            primary_duplicate: 1,
+            mapped: 6,
+            primary_mapped: 4,
+            paired: 4,
+            read_1: 2,
+            read_2: 2,
+            proper_pair: 3,
+            mate_mapped: 3,
+            singleton: 1,
+            mate_reference_sequence_id_mismatch: 2,
+            mate_reference_sequence_id_mismatch_hq: 1,
+        };
+
+        assert_eq!(stats.qc_pass(), &expected);
+        assert_eq!(stats.qc_fail().read(), 1);
+        assert_eq!(stats.qc_fail().mapped(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let records = [
+            Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_mapping_quality(MappingQuality::try_from(60)?)
+                .build(),
+            Record::builder()
+                .set_flags(Flags::UNMAPPED | Flags::QC_FAIL)
+                .build(),
+        ];
+
+        let mut expected = FlagStatistics::default();
+        expected.extend(&records);
+
+        let mut actual = FlagStatistics::default();
+        actual.extend(&records[..1]);
+
+        let mut other = FlagStatistics::default();
+        other.extend(&records[1..]);
+
+        actual.merge(&other);
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_mapping_quality(MappingQuality::try_from(60)?)
+            .build();
+
+        let mut stats = FlagStatistics::default();
+        stats.add_record(&record);
+        let expected = "\
+1 + 0 in total (QC-passed reads + QC-failed reads)
+1 + 0 primary
+0 + 0 secondary
+0 + 0 supplementary
+0 + 0 duplicates
+0 + 0 primary duplicates
+1 + 0 mapped (100.00% : N/A)
+1 + 0 primary mapped (100.00% : N/A)
+0 + 0 paired in sequencing
+0 + 0 read1
+0 + 0 read2
+0 + 0 properly paired (N/A : N/A)
+0 + 0 with itself and mate mapped
+0 + 0 singletons (N/A : N/A)
+0 + 0 with mate mapped to a different chr
+0 + 0 with mate mapped to a different chr (mapQ>=5)
+";
+
+        assert_eq!(stats.to_string(), expected);
+
+        Ok(())
+    }
+}
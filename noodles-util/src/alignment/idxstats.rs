@@ -0,0 +1,197 @@
+//! Alignment index statistics.
+//!
+//! This computes the same statistics as `samtools idxstats`.
+
+use std::{fmt, io};
+
+use noodles_csi as csi;
+use noodles_sam as sam;
+
+const UNPLACED_NAME: &str = "*";
+
+/// Index statistics of a reference sequence.
+///
+/// The [`fmt::Display`] implementation writes a tab-delimited line with the following columns:
+/// reference sequence name, reference sequence length, number of mapped records, and number of
+/// unmapped records.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    name: String,
+    length: usize,
+    mapped_record_count: u64,
+    unmapped_record_count: u64,
+}
+
+impl Record {
+    /// Returns the reference sequence name.
+    ///
+    /// This is `*` for unplaced, unmapped records.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the reference sequence length.
+    ///
+    /// This is 0 for unplaced, unmapped records.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the number of mapped records.
+    pub fn mapped_record_count(&self) -> u64 {
+        self.mapped_record_count
+    }
+
+    /// Returns the number of unmapped records.
+    pub fn unmapped_record_count(&self) -> u64 {
+        self.unmapped_record_count
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.name, self.length, self.mapped_record_count, self.unmapped_record_count
+        )
+    }
+}
+
+/// Reads index statistics from an alignment header and its associated index.
+///
+/// A record is returned for each reference sequence, followed by a record named `*` with the
+/// number of unplaced, unmapped records. Reference sequences without index metadata have zero
+/// counts.
+///
+/// # Errors
+///
+/// This returns an error if the number of reference sequences in the header and index differ.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use noodles_csi as csi;
+/// use noodles_sam::{self as sam, header::record::value::{map::ReferenceSequence, Map}};
+/// use noodles_util::alignment::idxstats;
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(
+///         "sq0".parse()?,
+///         Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+///     )
+///     .build();
+///
+/// let index = csi::Index::builder()
+///     .set_reference_sequences(vec![csi::index::ReferenceSequence::new(
+///         Default::default(),
+///         Vec::new(),
+///         None,
+///     )])
+///     .build();
+///
+/// let records = idxstats::read(&header, &index)?;
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[0].to_string(), "sq0\t8\t0\t0");
+/// assert_eq!(records[1].to_string(), "*\t0\t0\t0");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn read(header: &sam::Header, index: &csi::Index) -> io::Result<Vec<Record>> {
+    let reference_sequences = header.reference_sequences();
+    let index_reference_sequences = index.reference_sequences();
+
+    if reference_sequences.len() != index_reference_sequences.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "reference sequence count mismatch: expected {}, got {}",
+                reference_sequences.len(),
+                index_reference_sequences.len()
+            ),
+        ));
+    }
+
+    let mut records: Vec<_> = reference_sequences
+        .iter()
+        .zip(index_reference_sequences)
+        .map(|((name, reference_sequence), index_reference_sequence)| {
+            let (mapped_record_count, unmapped_record_count) = index_reference_sequence
+                .metadata()
+                .map(|m| (m.mapped_record_count(), m.unmapped_record_count()))
+                .unwrap_or_default();
+
+            Record {
+                name: name.to_string(),
+                length: usize::from(reference_sequence.length()),
+                mapped_record_count,
+                unmapped_record_count,
+            }
+        })
+        .collect();
+
+    records.push(Record {
+        name: UNPLACED_NAME.into(),
+        length: 0,
+        mapped_record_count: 0,
+        unmapped_record_count: index.unplaced_unmapped_record_count().unwrap_or_default(),
+    });
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_bgzf as bgzf;
+    use noodles_csi::index::reference_sequence::Metadata;
+    use sam::header::record::value::{map::ReferenceSequence, Map};
+
+    use super::*;
+
+    #[test]
+    fn test_read() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .add_reference_sequence(
+                "sq1".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+            )
+            .build();
+
+        let metadata = Metadata::new(
+            bgzf::VirtualPosition::default(),
+            bgzf::VirtualPosition::default(),
+            5,
+            2,
+        );
+
+        let index = csi::Index::builder()
+            .set_reference_sequences(vec![
+                csi::index::ReferenceSequence::new(Default::default(), Vec::new(), Some(metadata)),
+                csi::index::ReferenceSequence::new(Default::default(), Vec::new(), None),
+            ])
+            .set_unplaced_unmapped_record_count(3)
+            .build();
+
+        let actual: Vec<_> = read(&header, &index)?
+            .iter()
+            .map(|record| record.to_string())
+            .collect();
+
+        assert_eq!(actual, ["sq0\t8\t5\t2", "sq1\t13\t0\t0", "*\t0\t0\t3"]);
+
+        let index = csi::Index::default();
+        assert!(matches!(
+            read(&header, &index),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,842 @@
+//! Alignment duplicate marking.
+//!
+//! This finds records that are likely PCR or optical duplicates in coordinate-sorted input,
+//! similar to `samtools markdup`.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    io,
+};
+
+use noodles_sam::{
+    alignment::Record,
+    record::{cigar::op::Kind, data::field::Tag, Cigar, Flags},
+};
+
+const MIN_SCORED_BASE_QUALITY: u8 = 15;
+const MATE_SCORE: [u8; 2] = *b"ms";
+
+/// An iterator over records with duplicates marked.
+///
+/// This is created by calling [`Builder::build`]. The input must be sorted by coordinate.
+///
+/// Primary, mapped records that pass QC are grouped by library (the `LB` of the record's read
+/// group), unclipped 5' position, and strand. Paired records whose mate is mapped are grouped by
+/// both ends, using the mate CIGAR (`MC`) data field to find the unclipped 5' position of the
+/// mate. Within a group, the record or pair with the highest score, i.e., the sum of base quality
+/// scores of at least 15, is kept, and the others are duplicates. The score of a pair includes the
+/// mate score (`ms`) data field, if present. Single records at the same position as a pair end
+/// are always duplicates.
+///
+/// Pairs are decided by the read that appears first, and its mate is given the same decision. If
+/// the read that appears first is not grouped, e.g., because it failed QC, its mate is yielded
+/// unchanged. Other records, e.g., secondary, supplementary, and unmapped records, are yielded unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_sam::{self as sam, record::Flags};
+/// use noodles_util::alignment::markdup;
+///
+/// let build_record = |quality_scores: &str| -> Result<_, Box<dyn std::error::Error>> {
+///     Ok(sam::alignment::Record::builder()
+///         .set_flags(Flags::empty())
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(8)?)
+///         .set_cigar("2M".parse()?)
+///         .set_sequence("AC".parse()?)
+///         .set_quality_scores(quality_scores.parse()?)
+///         .build())
+/// };
+///
+/// let header = sam::Header::default();
+/// let records = [build_record("##")?, build_record("NN")?];
+///
+/// let records = markdup::Builder::default()
+///     .build(&header, records.into_iter().map(Ok))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert!(records[0].flags().is_duplicate());
+/// assert!(!records[1].flags().is_duplicate());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct MarkDuplicates<I> {
+    records: I,
+    remove_duplicates: bool,
+    read_group_library_ids: HashMap<String, usize>,
+    buf: VecDeque<Slot>,
+    offset: usize,
+    groups: HashMap<Key, Group>,
+    pair_ends: BTreeSet<(usize, usize, usize, bool)>,
+    // The names of leading records that are in a group.
+    leading_names: HashSet<Vec<u8>>,
+    pair_decisions: HashMap<Vec<u8>, bool>,
+    pending_mates: HashMap<Vec<u8>, PendingMates>,
+    window: usize,
+    is_eof: bool,
+}
+
+struct Slot {
+    record: Record,
+    status: Status,
+}
+
+// Mates waiting on the decision of their leading record.
+struct PendingMates {
+    leading_position: (usize, usize),
+    indices: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Status {
+    Pending,
+    Unchanged,
+    Decided(bool),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct End {
+    reference_sequence_id: usize,
+    position: usize,
+    is_reverse_complemented: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Key {
+    Single {
+        library_id: usize,
+        end: End,
+    },
+    Pair {
+        library_id: usize,
+        end: End,
+        mate_end: End,
+    },
+}
+
+#[derive(Default)]
+struct Group {
+    // The position after which no more records can join the group.
+    close_position: (usize, usize),
+    members: Vec<(usize, u64)>,
+}
+
+impl<I> MarkDuplicates<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    fn read_record(&mut self) -> io::Result<()> {
+        let record = match self.records.next() {
+            Some(result) => result?,
+            None => {
+                self.is_eof = true;
+                self.close_groups(None);
+                self.finish_pending();
+                return Ok(());
+            }
+        };
+
+        let i = self.offset + self.buf.len();
+        let status = self.add_record(i, &record)?;
+        self.buf.push_back(Slot { record, status });
+
+        if let Some(current_position) = self.buf.back().and_then(|slot| position(&slot.record)) {
+            self.close_groups(Some(current_position));
+        }
+
+        Ok(())
+    }
+
+    fn add_record(&mut self, i: usize, record: &Record) -> io::Result<Status> {
+        let flags = record.flags();
+
+        if flags.is_unmapped()
+            || flags.is_secondary()
+            || flags.is_supplementary()
+            || flags.is_qc_fail()
+        {
+            return Ok(Status::Unchanged);
+        }
+
+        let end = match unclipped_end(record) {
+            Some(end) => end,
+            None => return Ok(Status::Unchanged),
+        };
+
+        self.window = self.window.max(record.cigar().read_length());
+
+        let library_id = self.library_id(record);
+        let score = score(record);
+
+        let is_paired = flags.is_segmented() && !flags.is_mate_unmapped();
+
+        if !is_paired {
+            let key = Key::Single { library_id, end };
+            self.add_to_group(key, end, i, score);
+            return Ok(Status::Pending);
+        }
+
+        self.pair_ends.insert((
+            end.reference_sequence_id,
+            end.position,
+            library_id,
+            end.is_reverse_complemented,
+        ));
+
+        let name = read_name(record);
+
+        if !is_leading(record) {
+            if let Some(is_duplicate) = self.pair_decisions.remove(&name) {
+                return Ok(Status::Decided(is_duplicate));
+            }
+
+            // A record that is not leading always has a mate position.
+            let leading_position = mate_position(record).unwrap_or_default();
+
+            self.pending_mates
+                .entry(name)
+                .or_insert_with(|| PendingMates {
+                    leading_position,
+                    indices: Vec::new(),
+                })
+                .indices
+                .push(i);
+
+            return Ok(Status::Pending);
+        }
+
+        let mate_end = match mate_unclipped_end(record)? {
+            Some(mate_end) => mate_end,
+            None => return Ok(Status::Unchanged),
+        };
+
+        let key = Key::Pair {
+            library_id,
+            end,
+            mate_end,
+        };
+
+        let score = score + mate_score(record);
+        self.add_to_group(key, end, i, score);
+        self.leading_names.insert(name);
+
+        Ok(Status::Pending)
+    }
+
+    fn library_id(&self, record: &Record) -> usize {
+        record
+            .data()
+            .get(Tag::ReadGroup)
+            .and_then(|value| value.as_str())
+            .and_then(|id| self.read_group_library_ids.get(id))
+            .copied()
+            .unwrap_or(usize::MAX)
+    }
+
+    fn add_to_group(&mut self, key: Key, end: End, i: usize, score: u64) {
+        // A forward record with a leading clip starts after its unclipped 5' position. The
+        // longest read seen so far bounds the clip length.
+        let close_position = if end.is_reverse_complemented {
+            end.position
+        } else {
+            end.position + self.window
+        };
+
+        let group = self.groups.entry(key).or_default();
+        group.close_position = (end.reference_sequence_id, close_position);
+        group.members.push((i, score));
+    }
+
+    fn close_groups(&mut self, current_position: Option<(usize, usize)>) {
+        let mut keys: Vec<_> = self
+            .groups
+            .iter()
+            .filter(|(_, group)| match current_position {
+                Some(position) => group.close_position < position,
+                None => true,
+            })
+            .map(|(key, _)| *key)
+            .collect();
+
+        // Pairs are closed first so that their ends are known to single groups.
+        keys.sort_by_key(|key| matches!(key, Key::Single { .. }));
+
+        for key in keys {
+            if let Some(group) = self.groups.remove(&key) {
+                self.close_group(key, group);
+            }
+        }
+
+        if let Some(position) = current_position {
+            self.resolve_orphaned_mates(position);
+        }
+
+        if let Some((reference_sequence_id, position)) = current_position {
+            let min_position = position.saturating_sub(2 * self.window);
+            let retained =
+                self.pair_ends
+                    .split_off(&(reference_sequence_id, min_position, 0, false));
+            self.pair_ends = retained;
+        }
+    }
+
+    fn close_group(&mut self, key: Key, group: Group) {
+        let has_pair_end = match key {
+            Key::Single { library_id, end } => self.pair_ends.contains(&(
+                end.reference_sequence_id,
+                end.position,
+                library_id,
+                end.is_reverse_complemented,
+            )),
+            Key::Pair { .. } => false,
+        };
+
+        // Ties are resolved by the earliest record.
+        let best = if has_pair_end {
+            None
+        } else {
+            group
+                .members
+                .iter()
+                .fold(None, |best: Option<(usize, u64)>, &(i, score)| match best {
+                    Some((_, best_score)) if best_score >= score => best,
+                    _ => Some((i, score)),
+                })
+                .map(|(i, _)| i)
+        };
+
+        for (i, _) in group.members {
+            let is_duplicate = Some(i) != best;
+            self.decide(i, is_duplicate, matches!(key, Key::Pair { .. }));
+        }
+    }
+
+    fn decide(&mut self, i: usize, is_duplicate: bool, is_paired: bool) {
+        let slot = match self.buf.get_mut(i - self.offset) {
+            Some(slot) => slot,
+            None => return,
+        };
+
+        slot.status = Status::Decided(is_duplicate);
+
+        if !is_paired {
+            return;
+        }
+
+        let name = read_name(&slot.record);
+        self.leading_names.remove(&name);
+
+        match self.pending_mates.remove(&name) {
+            Some(mates) => {
+                for j in mates.indices {
+                    if let Some(mate_slot) = self.buf.get_mut(j - self.offset) {
+                        mate_slot.status = Status::Decided(is_duplicate);
+                    }
+                }
+            }
+            None => {
+                self.pair_decisions.insert(name, is_duplicate);
+            }
+        }
+    }
+
+    // Once the input is past the position of a leading record that is not in a group, the
+    // leading record was excluded (e.g., QC failed) or is missing. Its mates are never decided
+    // and are left unchanged.
+    fn resolve_orphaned_mates(&mut self, current_position: (usize, usize)) {
+        let leading_names = &self.leading_names;
+        let buf = &mut self.buf;
+        let offset = self.offset;
+
+        self.pending_mates.retain(|name, mates| {
+            if mates.leading_position >= current_position || leading_names.contains(name) {
+                return true;
+            }
+
+            for &j in &mates.indices {
+                if let Some(slot) = buf.get_mut(j - offset) {
+                    slot.status = Status::Unchanged;
+                }
+            }
+
+            false
+        });
+    }
+
+    // At EOF, mates without a decided leading record are not duplicates.
+    fn finish_pending(&mut self) {
+        for slot in &mut self.buf {
+            if slot.status == Status::Pending {
+                slot.status = Status::Decided(false);
+            }
+        }
+
+        self.leading_names.clear();
+        self.pending_mates.clear();
+        self.pair_decisions.clear();
+    }
+}
+
+impl<I> Iterator for MarkDuplicates<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(slot) = self.buf.front() {
+                if slot.status != Status::Pending {
+                    let Slot { mut record, status } = self.buf.pop_front()?;
+                    self.offset += 1;
+
+                    match status {
+                        Status::Decided(true) if self.remove_duplicates => continue,
+                        Status::Decided(is_duplicate) => {
+                            record.flags_mut().set(Flags::DUPLICATE, is_duplicate);
+                        }
+                        _ => {}
+                    }
+
+                    return Some(Ok(record));
+                }
+            } else if self.is_eof {
+                return None;
+            }
+
+            if let Err(e) = self.read_record() {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+fn position(record: &Record) -> Option<(usize, usize)> {
+    record
+        .reference_sequence_id()
+        .zip(record.alignment_start().map(usize::from))
+}
+
+fn read_name(record: &Record) -> Vec<u8> {
+    record
+        .read_name()
+        .map(|name| AsRef::<[u8]>::as_ref(name).to_vec())
+        .unwrap_or_default()
+}
+
+fn mate_position(record: &Record) -> Option<(usize, usize)> {
+    record
+        .mate_reference_sequence_id()
+        .zip(record.mate_alignment_start().map(usize::from))
+}
+
+// A paired record is leading if it appears before its mate in coordinate order.
+fn is_leading(record: &Record) -> bool {
+    match (position(record), mate_position(record)) {
+        (Some(a), Some(b)) if a == b => record.flags().is_first_segment(),
+        (Some(a), Some(b)) => a < b,
+        _ => true,
+    }
+}
+
+fn unclipped_end(record: &Record) -> Option<End> {
+    end(
+        record.reference_sequence_id()?,
+        usize::from(record.alignment_start()?),
+        record.cigar(),
+        record.flags().is_reverse_complemented(),
+    )
+}
+
+fn mate_unclipped_end(record: &Record) -> io::Result<Option<End>> {
+    let (reference_sequence_id, start) = match (
+        record.mate_reference_sequence_id(),
+        record.mate_alignment_start(),
+    ) {
+        (Some(id), Some(start)) => (id, usize::from(start)),
+        _ => return Ok(None),
+    };
+
+    let is_reverse_complemented = record.flags().is_mate_reverse_complemented();
+
+    let cigar: Cigar = match record
+        .data()
+        .get(Tag::MateCigar)
+        .and_then(|value| value.as_str())
+    {
+        Some(s) => s
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        // Without the mate CIGAR, the mate start is used as its 5' position.
+        None => {
+            return Ok(Some(End {
+                reference_sequence_id,
+                position: start,
+                is_reverse_complemented,
+            }))
+        }
+    };
+
+    Ok(end(
+        reference_sequence_id,
+        start,
+        &cigar,
+        is_reverse_complemented,
+    ))
+}
+
+fn end(
+    reference_sequence_id: usize,
+    start: usize,
+    cigar: &Cigar,
+    is_reverse_complemented: bool,
+) -> Option<End> {
+    let is_clip = |kind| matches!(kind, Kind::SoftClip | Kind::HardClip);
+
+    let position = if is_reverse_complemented {
+        let trailing_clip_len: usize = cigar
+            .iter()
+            .rev()
+            .take_while(|op| is_clip(op.kind()))
+            .map(|op| op.len())
+            .sum();
+
+        let end = start + cigar.alignment_span().max(1) - 1;
+        end + trailing_clip_len
+    } else {
+        let leading_clip_len: usize = cigar
+            .iter()
+            .take_while(|op| is_clip(op.kind()))
+            .map(|op| op.len())
+            .sum();
+
+        start.checked_sub(leading_clip_len)?
+    };
+
+    Some(End {
+        reference_sequence_id,
+        position,
+        is_reverse_complemented,
+    })
+}
+
+fn score(record: &Record) -> u64 {
+    record
+        .quality_scores()
+        .as_ref()
+        .iter()
+        .map(|score| u8::from(*score))
+        .filter(|&n| n >= MIN_SCORED_BASE_QUALITY)
+        .map(u64::from)
+        .sum()
+}
+
+fn mate_score(record: &Record) -> u64 {
+    Tag::try_from(MATE_SCORE)
+        .ok()
+        .and_then(|tag| record.data().get(tag))
+        .and_then(|value| value.as_int())
+        .and_then(|n| u64::try_from(n).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use noodles_core::Position;
+    use noodles_sam::{
+        self as sam,
+        header::record::value::{map::ReadGroup, Map},
+    };
+
+    use super::*;
+
+    fn duplicates(header: &sam::Header, records: Vec<Record>) -> io::Result<Vec<bool>> {
+        Builder::default()
+            .build(header, records.into_iter().map(Ok))
+            .map(|result| result.map(|record| record.flags().is_duplicate()))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::default();
+
+        let records = vec![
+            Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("####".parse()?)
+                .build(),
+            // Same unclipped 5' position as r0.
+            Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(3)?)
+                .set_cigar("2S2M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .build(),
+            // Different strand.
+            Record::builder()
+                .set_read_name("r2".parse()?)
+                .set_flags(Flags::REVERSE_COMPLEMENTED)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .build(),
+            // Same unclipped 5' position as r2.
+            Record::builder()
+                .set_read_name("r3".parse()?)
+                .set_flags(Flags::REVERSE_COMPLEMENTED | Flags::DUPLICATE)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_cigar("1S3M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("r4".parse()?)
+                .set_flags(Flags::SECONDARY)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("####".parse()?)
+                .build(),
+        ];
+
+        assert_eq!(
+            duplicates(&header, records)?,
+            [true, false, false, true, false]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_records_with_libraries() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header = sam::Header::default();
+
+        for (id, library) in [("rg0", "lb0"), ("rg1", "lb1")] {
+            let read_group = Map::<ReadGroup>::builder().set_library(library).build()?;
+            header.read_groups_mut().insert(id.into(), read_group);
+        }
+
+        let records = vec![
+            Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .set_data("RG:Z:rg0".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .set_data("RG:Z:rg1".parse()?)
+                .build(),
+        ];
+
+        assert_eq!(duplicates(&header, records)?, [false, false]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pairs() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::default();
+
+        let r1 = Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::MATE_REVERSE_COMPLEMENTED;
+        let r2 = Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED;
+
+        let records = vec![
+            Record::builder()
+                .set_read_name("p0".parse()?)
+                .set_flags(r1)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("####".parse()?)
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(21)?)
+                .set_data("MC:Z:4M".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("p1".parse()?)
+                .set_flags(r1)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(21)?)
+                .set_data("MC:Z:4M".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("s0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("p0".parse()?)
+                .set_flags(r2)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(21)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(1)?)
+                .set_data("MC:Z:4M".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("p1".parse()?)
+                .set_flags(r2)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(21)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(1)?)
+                .set_data("MC:Z:4M".parse()?)
+                .build(),
+        ];
+
+        assert_eq!(
+            duplicates(&header, records)?,
+            [true, false, true, true, false]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pairs_with_qc_fail_leading_mate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::default();
+
+        let r1 = Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::MATE_REVERSE_COMPLEMENTED;
+        let r2 = Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED;
+
+        let records = vec![
+            Record::builder()
+                .set_read_name("p0".parse()?)
+                .set_flags(r1 | Flags::QC_FAIL)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(21)?)
+                .set_data("MC:Z:4M".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("p0".parse()?)
+                .set_flags(r2)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(21)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(1)?)
+                .set_data("MC:Z:4M".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("s0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(34)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .build(),
+        ];
+
+        // The input fails after the last record. The mate of the QC failed record must be yielded
+        // without waiting for the end of the input.
+        let mut iter = Builder::default().build(
+            &header,
+            records
+                .into_iter()
+                .map(Ok)
+                .chain(iter::once(Err(io::Error::from(io::ErrorKind::InvalidData)))),
+        );
+
+        let record = iter.next().transpose()?.ok_or("missing record")?;
+        assert!(record.flags().is_qc_fail());
+        assert!(!record.flags().is_duplicate());
+
+        let record = iter.next().transpose()?.ok_or("missing record")?;
+        assert_eq!(record.alignment_start(), Some(Position::try_from(21)?));
+        assert!(!record.flags().is_duplicate());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::default();
+
+        let records = vec![
+            Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("####".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("AAAA".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .build(),
+        ];
+
+        let names: Vec<_> = Builder::default()
+            .set_remove_duplicates(true)
+            .build(&header, records.into_iter().map(Ok))
+            .map(|result| result.map(|record| read_name(&record)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [b"r1".to_vec()]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
+
+use noodles_sam::{self as sam, alignment::Record};
+
+use super::MarkDuplicates;
+
+/// A duplicate marker builder.
+#[derive(Default)]
+pub struct Builder {
+    remove_duplicates: bool,
+}
+
+impl Builder {
+    /// Sets whether to remove duplicates instead of marking them.
+    ///
+    /// By default, duplicates are marked with the duplicate flag and kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::markdup;
+    /// let builder = markdup::Builder::default().set_remove_duplicates(true);
+    /// ```
+    pub fn set_remove_duplicates(mut self, remove_duplicates: bool) -> Self {
+        self.remove_duplicates = remove_duplicates;
+        self
+    }
+
+    /// Builds a duplicate marker from an iterator of coordinate-sorted records.
+    ///
+    /// The header is used to group records by library.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_sam::{self as sam, alignment::Record};
+    /// use noodles_util::alignment::markdup;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = Vec::<io::Result<Record>>::new();
+    ///
+    /// let mut records = markdup::Builder::default().build(&header, records);
+    /// assert!(records.next().is_none());
+    /// ```
+    pub fn build<I>(self, header: &sam::Header, records: I) -> MarkDuplicates<I::IntoIter>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        let mut library_ids = HashMap::new();
+        let mut read_group_library_ids = HashMap::new();
+
+        for (id, read_group) in header.read_groups() {
+            let library = read_group.library().unwrap_or_default().to_string();
+            let next_id = library_ids.len();
+            let library_id = *library_ids.entry(library).or_insert(next_id);
+            read_group_library_ids.insert(id.clone(), library_id);
+        }
+
+        MarkDuplicates {
+            records: records.into_iter(),
+            remove_duplicates: self.remove_duplicates,
+            read_group_library_ids,
+            buf: Default::default(),
+            offset: 0,
+            groups: HashMap::new(),
+            pair_ends: Default::default(),
+            leading_names: HashSet::new(),
+            pair_decisions: HashMap::new(),
+            pending_mates: HashMap::new(),
+            window: 0,
+            is_eof: false,
+        }
+    }
+}
@@ -0,0 +1,662 @@
+//! Alignment merging.
+//!
+//! [`Merger`] merges multiple sorted alignment inputs into a single sorted output. The input
+//! headers are reconciled into one header, and records are rewritten to match it.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{collections::HashMap, io, mem};
+
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    record::data::field::{Tag, Value},
+};
+
+use super::sort::Order;
+
+/// An alignment merger.
+///
+/// This is built using a [`Builder`].
+pub struct Merger {
+    order: Order,
+    header: sam::Header,
+    mappings: Vec<Mapping>,
+}
+
+impl Merger {
+    /// Returns the merged header.
+    ///
+    /// Reference sequences are the union of those of the inputs. Read groups and programs with
+    /// the same ID but different values are renamed by appending a numeric suffix, e.g.,
+    /// `rg0-1`. Identical read groups and programs are deduplicated. Programs are compared after
+    /// their previous program IDs (`PP`) are renamed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::record::value::{map::ReadGroup, Map}};
+    /// use noodles_util::alignment::merge;
+    ///
+    /// let mut header = sam::Header::default();
+    /// header
+    ///     .read_groups_mut()
+    ///     .insert(String::from("rg0"), Map::<ReadGroup>::default());
+    ///
+    /// let merger = merge::Builder::default().build(&[header.clone(), header])?;
+    /// assert_eq!(merger.header().read_groups().len(), 1);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn header(&self) -> &sam::Header {
+        &self.header
+    }
+
+    /// Merges sorted inputs.
+    ///
+    /// The inputs must be in the same order as the headers given to [`Builder::build`]. Records
+    /// are rewritten to use the reference sequence IDs, read group IDs (`RG`), and program IDs
+    /// (`PG`) of the merged header. Records that compare equal are yielded in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::merge;
+    ///
+    /// let build_record = |start| {
+    ///     Position::try_from(start)
+    ///         .map(|position| {
+    ///             sam::alignment::Record::builder()
+    ///                 .set_alignment_start(position)
+    ///                 .build()
+    ///         })
+    ///         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    /// };
+    ///
+    /// let headers = [sam::Header::default(), sam::Header::default()];
+    /// let merger = merge::Builder::default().build(&headers)?;
+    ///
+    /// let inputs = vec![
+    ///     vec![build_record(1), build_record(8)].into_iter(),
+    ///     vec![build_record(5)].into_iter(),
+    /// ];
+    ///
+    /// let starts = merger
+    ///     .merge(inputs)?
+    ///     .map(|result| result.map(|record| record.alignment_start().map(usize::from)))
+    ///     .collect::<io::Result<Vec<_>>>()?;
+    ///
+    /// assert_eq!(starts, [Some(1), Some(5), Some(8)]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn merge<I>(&self, inputs: Vec<I>) -> io::Result<Merge<'_, I>>
+    where
+        I: Iterator<Item = io::Result<Record>>,
+    {
+        if inputs.len() != self.mappings.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "input count mismatch: expected {}, got {}",
+                    self.mappings.len(),
+                    inputs.len()
+                ),
+            ));
+        }
+
+        let mut merge = Merge {
+            merger: self,
+            inputs,
+            heads: Vec::new(),
+        };
+
+        for i in 0..merge.inputs.len() {
+            let head = merge.read_input(i)?;
+            merge.heads.push(head);
+        }
+
+        Ok(merge)
+    }
+}
+
+/// An iterator over merged alignment records.
+///
+/// This is created by calling [`Merger::merge`].
+pub struct Merge<'a, I> {
+    merger: &'a Merger,
+    inputs: Vec<I>,
+    heads: Vec<Option<Record>>,
+}
+
+impl<'a, I> Merge<'a, I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    fn read_input(&mut self, i: usize) -> io::Result<Option<Record>> {
+        match self.inputs[i].next() {
+            Some(result) => {
+                let mut record = result?;
+                self.merger.mappings[i].apply(&mut record)?;
+                Ok(Some(record))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<'a, I> Iterator for Merge<'a, I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let order = self.merger.order;
+        let mut min: Option<(usize, &Record)> = None;
+
+        for (i, head) in self.heads.iter().enumerate() {
+            if let Some(record) = head {
+                match min {
+                    Some((_, min_record))
+                        if order.cmp(record, min_record) != std::cmp::Ordering::Less => {}
+                    _ => min = Some((i, record)),
+                }
+            }
+        }
+
+        let i = min?.0;
+
+        let next_head = match self.read_input(i) {
+            Ok(head) => head,
+            Err(e) => return Some(Err(e)),
+        };
+
+        mem::replace(&mut self.heads[i], next_head).map(Ok)
+    }
+}
+
+// The mapping of an input's header IDs to the merged header's IDs.
+#[derive(Debug, Default)]
+struct Mapping {
+    reference_sequence_ids: Vec<usize>,
+    read_group_ids: HashMap<String, String>,
+    program_ids: HashMap<String, String>,
+}
+
+impl Mapping {
+    fn apply(&self, record: &mut Record) -> io::Result<()> {
+        if let Some(id) = record.reference_sequence_id() {
+            *record.reference_sequence_id_mut() = Some(self.map_reference_sequence_id(id)?);
+        }
+
+        if let Some(id) = record.mate_reference_sequence_id() {
+            *record.mate_reference_sequence_id_mut() = Some(self.map_reference_sequence_id(id)?);
+        }
+
+        rename_data_field(record, Tag::ReadGroup, &self.read_group_ids);
+        rename_data_field(record, Tag::Program, &self.program_ids);
+
+        Ok(())
+    }
+
+    // Returns whether the input's reference sequences are in the same relative order in the
+    // merged header.
+    fn is_reference_sequence_order_preserved(&self) -> bool {
+        self.reference_sequence_ids
+            .windows(2)
+            .all(|ids| ids[0] < ids[1])
+    }
+
+    fn map_reference_sequence_id(&self, id: usize) -> io::Result<usize> {
+        self.reference_sequence_ids.get(id).copied().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid reference sequence ID: {id}"),
+            )
+        })
+    }
+}
+
+fn rename_data_field(record: &mut Record, tag: Tag, ids: &HashMap<String, String>) {
+    let new_id = record
+        .data()
+        .get(tag)
+        .and_then(|value| value.as_str())
+        .and_then(|id| ids.get(id))
+        .cloned();
+
+    if let Some(id) = new_id {
+        record.data_mut().insert(tag, Value::String(id));
+    }
+}
+
+fn merge_headers(headers: &[sam::Header]) -> io::Result<(sam::Header, Vec<Mapping>)> {
+    let mut header = sam::Header::default();
+    *header.header_mut() = headers.first().and_then(|h| h.header()).cloned();
+
+    let mut mappings = Vec::with_capacity(headers.len());
+
+    for input_header in headers {
+        let mut mapping = Mapping::default();
+
+        for (name, reference_sequence) in input_header.reference_sequences() {
+            let reference_sequences = header.reference_sequences_mut();
+
+            let id = match reference_sequences.get_full(name) {
+                Some((id, _, existing)) => {
+                    if existing.length() != reference_sequence.length() {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "reference sequence length conflict: {name}: expected {}, got {}",
+                                existing.length(),
+                                reference_sequence.length()
+                            ),
+                        ));
+                    }
+
+                    id
+                }
+                None => {
+                    reference_sequences.insert(name.clone(), reference_sequence.clone());
+                    reference_sequences.len() - 1
+                }
+            };
+
+            mapping.reference_sequence_ids.push(id);
+        }
+
+        for (id, read_group) in input_header.read_groups() {
+            let read_groups = header.read_groups_mut();
+
+            match read_groups.get(id) {
+                Some(existing) if existing == read_group => {}
+                Some(_) => {
+                    let new_id = unique_id(id, |s| read_groups.contains_key(s));
+                    read_groups.insert(new_id.clone(), read_group.clone());
+                    mapping.read_group_ids.insert(id.clone(), new_id);
+                }
+                None => {
+                    read_groups.insert(id.clone(), read_group.clone());
+                }
+            }
+        }
+
+        let programs = header.programs_mut();
+        let mut pending = Vec::new();
+
+        // Programs are compared after their previous program IDs (`PP`) are rewritten, so parents
+        // are visited before their children.
+        for i in chain_order(input_header.programs()) {
+            let (id, program) = match input_header.programs().get_index(i) {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            let mut program = program.clone();
+
+            if let Some(previous_id) = program.previous_id_mut() {
+                if let Some(new_previous_id) = mapping.program_ids.get(previous_id) {
+                    *previous_id = new_previous_id.clone();
+                }
+            }
+
+            match programs.get(id) {
+                Some(existing) if *existing == program => {}
+                Some(_) => {
+                    let new_id = unique_id(id, |s| {
+                        programs.contains_key(s)
+                            || input_header.programs().contains_key(s)
+                            || pending.iter().any(|(_, t, _)| t == s)
+                    });
+
+                    mapping.program_ids.insert(id.clone(), new_id.clone());
+                    pending.push((i, new_id, program));
+                }
+                None => pending.push((i, id.clone(), program)),
+            }
+        }
+
+        pending.sort_by_key(|(i, _, _)| *i);
+
+        for (_, id, program) in pending {
+            programs.insert(id, program);
+        }
+
+        for comment in input_header.comments() {
+            if !header.comments().contains(comment) {
+                header.add_comment(comment.clone());
+            }
+        }
+
+        mappings.push(mapping);
+    }
+
+    Ok((header, mappings))
+}
+
+// Returns the indices of programs ordered such that a program comes after its previous program
+// (`PP`).
+fn chain_order(programs: &sam::header::Programs) -> Vec<usize> {
+    let mut order = Vec::with_capacity(programs.len());
+    let mut is_visited = vec![false; programs.len()];
+
+    for i in 0..programs.len() {
+        let mut chain = Vec::new();
+        let mut next = Some(i);
+
+        while let Some(j) = next {
+            if is_visited[j] {
+                break;
+            }
+
+            is_visited[j] = true;
+            chain.push(j);
+
+            next = programs
+                .get_index(j)
+                .and_then(|(_, program)| program.previous_id())
+                .and_then(|id| programs.get_index_of(id));
+        }
+
+        order.extend(chain.into_iter().rev());
+    }
+
+    order
+}
+
+fn unique_id<F>(id: &str, is_taken: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    (1..)
+        .map(|i| format!("{id}-{i}"))
+        .find(|candidate| !is_taken(candidate))
+        .unwrap_or_else(|| id.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_core::Position;
+    use noodles_sam::header::record::value::{
+        map::{Program, ReadGroup, ReferenceSequence},
+        Map,
+    };
+
+    use super::*;
+
+    fn add_reference_sequence(
+        header: &mut sam::Header,
+        name: &str,
+        length: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        header.reference_sequences_mut().insert(
+            name.parse()?,
+            Map::<ReferenceSequence>::new(NonZeroUsize::try_from(length)?),
+        );
+
+        Ok(())
+    }
+
+    fn build_read_group(platform: &str) -> Result<Map<ReadGroup>, Box<dyn std::error::Error>> {
+        Ok(Map::<ReadGroup>::builder()
+            .set_platform(platform.parse()?)
+            .build()?)
+    }
+
+    fn build_program(
+        name: &str,
+        previous_id: Option<&str>,
+    ) -> Result<Map<Program>, Box<dyn std::error::Error>> {
+        let mut builder = Map::<Program>::builder().set_name(name);
+
+        if let Some(id) = previous_id {
+            builder = builder.set_previous_id(id);
+        }
+
+        Ok(builder.build()?)
+    }
+
+    #[test]
+    fn test_merge_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header0 = sam::Header::default();
+        add_reference_sequence(&mut header0, "sq0", 8)?;
+        add_reference_sequence(&mut header0, "sq1", 13)?;
+        header0
+            .read_groups_mut()
+            .insert(String::from("rg0"), build_read_group("ILLUMINA")?);
+        header0
+            .programs_mut()
+            .insert(String::from("pg0"), build_program("noodles", None)?);
+
+        let mut header1 = sam::Header::default();
+        add_reference_sequence(&mut header1, "sq2", 21)?;
+        add_reference_sequence(&mut header1, "sq0", 8)?;
+        header1
+            .read_groups_mut()
+            .insert(String::from("rg0"), build_read_group("PACBIO")?);
+        header1
+            .programs_mut()
+            .insert(String::from("pg0"), build_program("noodles", Some("pg1"))?);
+        header1
+            .programs_mut()
+            .insert(String::from("pg1"), build_program("noodles", None)?);
+
+        let (header, mappings) = merge_headers(&[header0, header1])?;
+
+        let names: Vec<_> = header
+            .reference_sequences()
+            .keys()
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(names, ["sq0", "sq1", "sq2"]);
+
+        let read_group_ids: Vec<_> = header.read_groups().keys().cloned().collect();
+        assert_eq!(read_group_ids, ["rg0", "rg0-1"]);
+
+        let program_ids: Vec<_> = header.programs().keys().cloned().collect();
+        assert_eq!(program_ids, ["pg0", "pg0-1", "pg1"]);
+        assert_eq!(
+            header
+                .programs()
+                .get("pg0-1")
+                .and_then(|pg| pg.previous_id()),
+            Some("pg1")
+        );
+
+        assert_eq!(mappings[0].reference_sequence_ids, [0, 1]);
+        assert!(mappings[0].read_group_ids.is_empty());
+        assert_eq!(mappings[1].reference_sequence_ids, [2, 0]);
+        assert_eq!(
+            mappings[1].read_group_ids.get("rg0").map(|s| s.as_str()),
+            Some("rg0-1")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_headers_with_program_chain() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header0 = sam::Header::default();
+        header0
+            .programs_mut()
+            .insert(String::from("pg0"), build_program("a", None)?);
+        header0
+            .programs_mut()
+            .insert(String::from("pg1"), build_program("c", Some("pg0"))?);
+
+        // pg1 is listed before its previous program.
+        let mut header1 = sam::Header::default();
+        header1
+            .programs_mut()
+            .insert(String::from("pg1"), build_program("c", Some("pg0"))?);
+        header1
+            .programs_mut()
+            .insert(String::from("pg0"), build_program("b", None)?);
+
+        let (header, mappings) = merge_headers(&[header0, header1])?;
+
+        let program_ids: Vec<_> = header.programs().keys().cloned().collect();
+        assert_eq!(program_ids, ["pg0", "pg1", "pg1-1", "pg0-1"]);
+
+        assert_eq!(
+            header
+                .programs()
+                .get("pg1-1")
+                .and_then(|pg| pg.previous_id()),
+            Some("pg0-1")
+        );
+
+        assert!(mappings[0].program_ids.is_empty());
+        assert_eq!(
+            mappings[1].program_ids.get("pg0").map(|s| s.as_str()),
+            Some("pg0-1")
+        );
+        assert_eq!(
+            mappings[1].program_ids.get("pg1").map(|s| s.as_str()),
+            Some("pg1-1")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_headers_with_reference_sequence_conflict(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut header0 = sam::Header::default();
+        add_reference_sequence(&mut header0, "sq0", 8)?;
+
+        let mut header1 = sam::Header::default();
+        add_reference_sequence(&mut header1, "sq0", 13)?;
+
+        assert!(matches!(
+            merge_headers(&[header0, header1]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header0 = sam::Header::default();
+        add_reference_sequence(&mut header0, "sq0", 100)?;
+        add_reference_sequence(&mut header0, "sq2", 100)?;
+        header0
+            .read_groups_mut()
+            .insert(String::from("rg0"), build_read_group("ILLUMINA")?);
+
+        let mut header1 = sam::Header::default();
+        add_reference_sequence(&mut header1, "sq0", 100)?;
+        add_reference_sequence(&mut header1, "sq1", 100)?;
+        header1
+            .read_groups_mut()
+            .insert(String::from("rg0"), build_read_group("PACBIO")?);
+
+        let inputs = vec![
+            vec![
+                Ok(Record::builder()
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(5)?)
+                    .set_data("RG:Z:rg0".parse()?)
+                    .build()),
+                Ok(Record::builder()
+                    .set_reference_sequence_id(1)
+                    .set_alignment_start(Position::try_from(21)?)
+                    .set_data("RG:Z:rg0".parse()?)
+                    .build()),
+            ]
+            .into_iter(),
+            vec![
+                Ok(Record::builder()
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::MIN)
+                    .set_data("RG:Z:rg0".parse()?)
+                    .build()),
+                Ok(Record::builder()
+                    .set_reference_sequence_id(1)
+                    .set_alignment_start(Position::try_from(8)?)
+                    .set_data("RG:Z:rg0".parse()?)
+                    .build()),
+            ]
+            .into_iter(),
+        ];
+
+        let merger = Builder::default().build(&[header0, header1])?;
+
+        let names: Vec<_> = merger
+            .header()
+            .reference_sequences()
+            .keys()
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(names, ["sq0", "sq2", "sq1"]);
+
+        let records = merger.merge(inputs)?.collect::<io::Result<Vec<_>>>()?;
+
+        let actual: Vec<_> = records
+            .iter()
+            .map(|record| {
+                (
+                    record.reference_sequence_id(),
+                    record.alignment_start().map(usize::from),
+                    record
+                        .data()
+                        .get(Tag::ReadGroup)
+                        .and_then(|value| value.as_str())
+                        .map(String::from),
+                )
+            })
+            .collect();
+
+        let expected = [
+            (Some(0), Some(1), Some(String::from("rg0-1"))),
+            (Some(0), Some(5), Some(String::from("rg0"))),
+            (Some(1), Some(21), Some(String::from("rg0"))),
+            (Some(2), Some(8), Some(String::from("rg0-1"))),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_reference_sequence_order_conflict() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut header0 = sam::Header::default();
+        add_reference_sequence(&mut header0, "sq0", 8)?;
+        add_reference_sequence(&mut header0, "sq1", 13)?;
+
+        let mut header1 = sam::Header::default();
+        add_reference_sequence(&mut header1, "sq1", 13)?;
+        add_reference_sequence(&mut header1, "sq0", 8)?;
+
+        let headers = [header0, header1];
+
+        assert!(matches!(
+            Builder::default().build(&headers),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            Builder::default()
+                .set_order(Order::Tag(Tag::ReadGroup))
+                .build(&headers),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(Builder::default()
+            .set_order(Order::QueryName)
+            .build(&headers)
+            .is_ok());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use std::io;
+
+use noodles_sam as sam;
+
+use super::{merge_headers, Merger};
+use crate::alignment::sort::Order;
+
+/// An alignment merger builder.
+#[derive(Default)]
+pub struct Builder {
+    order: Order,
+}
+
+impl Builder {
+    /// Sets the order of the inputs.
+    ///
+    /// All inputs must already be sorted in this order. By default, this is by coordinate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{merge, sort::Order};
+    /// let builder = merge::Builder::default().set_order(Order::QueryName);
+    /// ```
+    pub fn set_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Builds an alignment merger from the headers of the inputs.
+    ///
+    /// This reconciles the headers into a single output header.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if two inputs have a reference sequence with the same name but a
+    /// different length.
+    ///
+    /// When the order compares coordinates (i.e., [`Order::Coordinate`] and [`Order::Tag`]), this
+    /// also returns an error if the inputs list shared reference sequences in different orders.
+    /// Records of such an input would no longer be sorted after their reference sequence IDs are
+    /// rewritten to those of the merged header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::merge;
+    ///
+    /// let headers = [sam::Header::default(), sam::Header::default()];
+    /// let merger = merge::Builder::default().build(&headers)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build(self, headers: &[sam::Header]) -> io::Result<Merger> {
+        let (mut header, mappings) = merge_headers(headers)?;
+
+        if matches!(self.order, Order::Coordinate | Order::Tag(_))
+            && !mappings
+                .iter()
+                .all(|mapping| mapping.is_reference_sequence_order_preserved())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "reference sequence order conflict",
+            ));
+        }
+
+        self.order.update_header(&mut header);
+
+        Ok(Merger {
+            order: self.order,
+            header,
+            mappings,
+        })
+    }
+}
@@ -0,0 +1,466 @@
+//! Alignment pileup.
+//!
+//! A pileup iterates the reference positions covered by a set of coordinate-sorted alignment
+//! records. Each position is yielded as a [`Column`] of [`Entry`]s, one per read aligned to it.
+
+mod builder;
+mod column;
+mod entry;
+
+pub use self::{
+    builder::Builder,
+    column::Column,
+    entry::{Entry, Indel},
+};
+
+use std::{collections::VecDeque, io};
+
+use noodles_core::Position;
+use noodles_sam::{alignment::Record, record::cigar::op::Kind};
+
+use super::filter::Filter;
+
+/// An iterator over pileup columns.
+///
+/// This is created by calling [`Builder::build`]. Records must be coordinate-sorted. Only reads
+/// that overlap the current position are kept in memory; reads are evicted once all their
+/// positions are yielded.
+///
+/// Skipped regions (`N`) do not contribute entries, and columns with no remaining entries after
+/// filtering are not yielded.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_sam::{self as sam, record::{sequence::Base, Flags}};
+/// use noodles_util::alignment::pileup;
+///
+/// let record = sam::alignment::Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::try_from(8)?)
+///     .set_cigar("2M".parse()?)
+///     .set_sequence("AC".parse()?)
+///     .build();
+///
+/// let mut pileup = pileup::Builder::default().build([Ok(record)]);
+///
+/// let column = pileup.next().transpose()?.expect("missing column");
+/// assert_eq!(column.position(), Position::try_from(8)?);
+/// assert_eq!(column.entries()[0].base(), Some(Base::A));
+///
+/// let column = pileup.next().transpose()?.expect("missing column");
+/// assert_eq!(column.position(), Position::try_from(9)?);
+/// assert_eq!(column.entries()[0].base(), Some(Base::C));
+///
+/// assert!(pileup.next().is_none());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Pileup<I> {
+    records: I,
+    filter: Filter,
+    pending: Option<(usize, Position, Record)>,
+    last_start: Option<(usize, Position)>,
+    position: Option<(usize, Position)>,
+    reads: Vec<VecDeque<(Position, Entry)>>,
+}
+
+impl<I> Pileup<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    // Reads the next included record and its start, checking that the input is sorted.
+    fn peek(&mut self) -> io::Result<Option<(usize, Position)>> {
+        while self.pending.is_none() {
+            let record = match self.records.next() {
+                Some(result) => result?,
+                None => return Ok(None),
+            };
+
+            let (reference_sequence_id, alignment_start) =
+                match (record.reference_sequence_id(), record.alignment_start()) {
+                    (Some(id), Some(start)) => (id, start),
+                    _ => continue,
+                };
+
+            let start = (reference_sequence_id, alignment_start);
+
+            if let Some(last_start) = self.last_start {
+                if start < last_start {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "records are not coordinate-sorted",
+                    ));
+                }
+            }
+
+            self.last_start = Some(start);
+
+            if self.filter.is_included(&record) {
+                self.pending = Some((reference_sequence_id, alignment_start, record));
+            }
+        }
+
+        Ok(self.pending.as_ref().map(|(id, start, _)| (*id, *start)))
+    }
+
+    fn advance(&mut self) -> io::Result<Option<Column>> {
+        if self.position.is_none() {
+            self.position = self.peek()?;
+        }
+
+        let (reference_sequence_id, position) = match self.position {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+
+        while self.peek()? == Some((reference_sequence_id, position)) {
+            if let Some((_, start, record)) = self.pending.take() {
+                let entries = expand(start, &record)?;
+
+                if !entries.is_empty() {
+                    self.reads.push(entries);
+                }
+            }
+        }
+
+        let mut entries = Vec::new();
+
+        for read in &mut self.reads {
+            if let Some((_, entry)) = read.front().filter(|(p, _)| *p == position) {
+                if self.filter.is_base_included(entry.quality_score) {
+                    entries.push(entry.clone());
+                }
+
+                read.pop_front();
+            }
+        }
+
+        self.reads.retain(|read| !read.is_empty());
+
+        let next_read_position = self
+            .reads
+            .iter()
+            .filter_map(|read| read.front())
+            .map(|(p, _)| *p)
+            .min();
+
+        let next_pending_position = self
+            .peek()?
+            .filter(|(id, _)| *id == reference_sequence_id)
+            .map(|(_, start)| start);
+
+        self.position = match (next_read_position, next_pending_position) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (Some(p), None) | (None, Some(p)) => Some(p),
+            (None, None) => None,
+        }
+        .map(|p| (reference_sequence_id, p));
+
+        Ok(Some(Column {
+            reference_sequence_id,
+            position,
+            entries,
+        }))
+    }
+}
+
+impl<I> Iterator for Pileup<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Column>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.advance() {
+                Ok(Some(column)) if column.entries.is_empty() => {}
+                Ok(Some(column)) => return Some(Ok(column)),
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+// Expands a record into an entry for each reference position it covers.
+fn expand(start: Position, record: &Record) -> io::Result<VecDeque<(Position, Entry)>> {
+    let bases = record.sequence().as_ref();
+    let quality_scores = record.quality_scores().as_ref();
+    let is_reverse_complemented = record.flags().is_reverse_complemented();
+
+    let mut entries: VecDeque<(Position, Entry)> = VecDeque::new();
+    let mut reference_offset = 0;
+    let mut read_position = 0;
+
+    let position = |offset: usize| {
+        start
+            .checked_add(offset)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid alignment position"))
+    };
+
+    for op in record.cigar().iter() {
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                for _ in 0..op.len() {
+                    let entry = Entry {
+                        base: Some(bases.get(read_position).copied().ok_or_else(|| {
+                            io::Error::new(io::ErrorKind::InvalidData, "missing read base")
+                        })?),
+                        quality_score: quality_scores.get(read_position).copied(),
+                        read_position,
+                        is_reverse_complemented,
+                        indel: None,
+                        is_read_start: false,
+                        is_read_end: false,
+                    };
+
+                    entries.push_back((position(reference_offset)?, entry));
+
+                    reference_offset += 1;
+                    read_position += 1;
+                }
+            }
+            Kind::Insertion => {
+                let end = read_position + op.len();
+
+                if let (Some((_, entry)), Some(inserted_bases)) =
+                    (entries.back_mut(), bases.get(read_position..end))
+                {
+                    entry.indel = Some(Indel::Insertion(inserted_bases.to_vec()));
+                }
+
+                read_position = end;
+            }
+            Kind::Deletion => {
+                if let Some((_, entry)) = entries.back_mut() {
+                    entry.indel = Some(Indel::Deletion(op.len()));
+                }
+
+                for _ in 0..op.len() {
+                    let entry = Entry {
+                        base: None,
+                        quality_score: None,
+                        read_position,
+                        is_reverse_complemented,
+                        indel: None,
+                        is_read_start: false,
+                        is_read_end: false,
+                    };
+
+                    entries.push_back((position(reference_offset)?, entry));
+
+                    reference_offset += 1;
+                }
+            }
+            Kind::Skip => reference_offset += op.len(),
+            Kind::SoftClip => read_position += op.len(),
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    if let Some((_, entry)) = entries.front_mut() {
+        entry.is_read_start = true;
+    }
+
+    if let Some((_, entry)) = entries.back_mut() {
+        entry.is_read_end = true;
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::{sequence::Base, Flags, MappingQuality};
+
+    use super::*;
+
+    fn summarize(column: &Column) -> (usize, usize, String) {
+        let bases = column
+            .entries()
+            .iter()
+            .map(|entry| entry.base().map(char::from).unwrap_or('*'))
+            .collect();
+
+        (
+            column.reference_sequence_id(),
+            usize::from(column.position()),
+            bases,
+        )
+    }
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("2M1I1M1D1M".parse()?)
+                .set_sequence("ACGTA".parse()?)
+                .set_quality_scores("NNNNN".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_cigar("1S3M".parse()?)
+                .set_sequence("TCGT".parse()?)
+                .set_quality_scores("NNNN".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(5)?)
+                .set_cigar("1M2N1M".parse()?)
+                .set_sequence("GG".parse()?)
+                .set_quality_scores("NN".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(1)
+                .set_alignment_start(Position::try_from(3)?)
+                .set_cigar("1M".parse()?)
+                .set_sequence("T".parse()?)
+                .set_quality_scores("N".parse()?)
+                .build()),
+        ];
+
+        let actual: Vec<_> = Builder::default()
+            .build(records)
+            .map(|result| result.map(|column| summarize(&column)))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (0, 1, String::from("A")),
+            (0, 2, String::from("CC")),
+            (0, 3, String::from("TG")),
+            (0, 4, String::from("*T")),
+            (0, 5, String::from("AG")),
+            (0, 8, String::from("G")),
+            (1, 3, String::from("T")),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_markers_and_indels() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![Ok(Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(1)?)
+            .set_cigar("2M1I1M1D1M".parse()?)
+            .set_sequence("ACGTA".parse()?)
+            .set_quality_scores("NNNNN".parse()?)
+            .build())];
+        let columns: Vec<_> = Builder::default()
+            .build(records)
+            .collect::<io::Result<_>>()?;
+
+        let entries: Vec<_> = columns
+            .iter()
+            .map(|column| column.entries()[0].clone())
+            .collect();
+
+        assert!(entries[0].is_read_start());
+        assert!(!entries[0].is_read_end());
+        assert_eq!(entries[1].indel(), Some(&Indel::Insertion(vec![Base::G])));
+        assert_eq!(entries[2].indel(), Some(&Indel::Deletion(1)));
+        assert!(entries[3].is_deletion());
+        assert_eq!(entries[3].read_position(), 4);
+        assert!(entries[4].is_read_end());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_filters() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            Ok(Record::builder()
+                .set_flags(Flags::DUPLICATE)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("1M".parse()?)
+                .set_sequence("A".parse()?)
+                .set_quality_scores("N".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("1M".parse()?)
+                .set_sequence("C".parse()?)
+                .set_quality_scores("N".parse()?)
+                .set_mapping_quality(MappingQuality::try_from(5)?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("1M".parse()?)
+                .set_sequence("G".parse()?)
+                .set_quality_scores("!".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("1M".parse()?)
+                .set_sequence("T".parse()?)
+                .set_quality_scores("N".parse()?)
+                .build()),
+        ];
+
+        let filter = Filter::builder()
+            .set_min_mapping_quality(10)
+            .set_min_base_quality(13)
+            .build();
+
+        let actual: Vec<_> = Builder::default()
+            .set_filter(filter)
+            .build(records)
+            .map(|result| result.map(|column| summarize(&column)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(actual, [(0, 1, String::from("T"))]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(5)?)
+                .set_cigar("1M".parse()?)
+                .set_sequence("A".parse()?)
+                .set_quality_scores("N".parse()?)
+                .build()),
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("1M".parse()?)
+                .set_sequence("A".parse()?)
+                .set_quality_scores("N".parse()?)
+                .build()),
+        ];
+
+        let mut pileup = Builder::default().build(records);
+
+        assert!(matches!(
+            pileup.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use std::io;
+
+use noodles_sam::alignment::Record;
+
+use super::Pileup;
+use crate::alignment::filter::Filter;
+
+/// A pileup builder.
+#[derive(Default)]
+pub struct Builder {
+    filter: Filter,
+}
+
+impl Builder {
+    /// Sets the record and base filter.
+    ///
+    /// Records that are not included by the filter are skipped, and bases that are not included
+    /// are omitted from columns. Deletions are always kept. Unplaced records are always skipped.
+    ///
+    /// By default, this is [`Filter::default`], which excludes records that are unmapped,
+    /// secondary, QC failed, or duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{filter::Filter, pileup};
+    ///
+    /// let filter = Filter::builder()
+    ///     .set_min_mapping_quality(20)
+    ///     .set_min_base_quality(13)
+    ///     .build();
+    ///
+    /// let builder = pileup::Builder::default().set_filter(filter);
+    /// ```
+    pub fn set_filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Builds a pileup iterator from an iterator of coordinate-sorted records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_sam::alignment::Record;
+    /// use noodles_util::alignment::pileup;
+    ///
+    /// let records = Vec::<io::Result<Record>>::new();
+    /// let mut pileup = pileup::Builder::default().build(records);
+    /// assert!(pileup.next().is_none());
+    /// ```
+    pub fn build<I>(self, records: I) -> Pileup<I::IntoIter>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        Pileup {
+            records: records.into_iter(),
+            filter: self.filter,
+            pending: None,
+            last_start: None,
+            position: None,
+            reads: Vec::new(),
+        }
+    }
+}
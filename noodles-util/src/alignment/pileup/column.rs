@@ -0,0 +1,35 @@
+use noodles_core::Position;
+
+use super::Entry;
+
+/// A pileup column.
+///
+/// A column is the set of read entries aligned to a single reference position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Column {
+    pub(super) reference_sequence_id: usize,
+    pub(super) position: Position,
+    pub(super) entries: Vec<Entry>,
+}
+
+impl Column {
+    /// Returns the reference sequence ID.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the reference position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the read entries.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns the number of read entries, i.e., the depth.
+    pub fn depth(&self) -> usize {
+        self.entries.len()
+    }
+}
@@ -0,0 +1,70 @@
+use noodles_sam::record::{quality_scores::Score, sequence::Base};
+
+/// An indel that follows a pileup entry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Indel {
+    /// Bases inserted after the entry's reference position.
+    Insertion(Vec<Base>),
+    /// The number of reference bases deleted after the entry's reference position.
+    Deletion(usize),
+}
+
+/// A read's contribution to a pileup column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub(super) base: Option<Base>,
+    pub(super) quality_score: Option<Score>,
+    pub(super) read_position: usize,
+    pub(super) is_reverse_complemented: bool,
+    pub(super) indel: Option<Indel>,
+    pub(super) is_read_start: bool,
+    pub(super) is_read_end: bool,
+}
+
+impl Entry {
+    /// Returns the read base.
+    ///
+    /// This is `None` if the position is deleted in the read.
+    pub fn base(&self) -> Option<Base> {
+        self.base
+    }
+
+    /// Returns the base quality score.
+    ///
+    /// This is `None` if the position is deleted in the read or the read has no quality scores.
+    pub fn quality_score(&self) -> Option<Score> {
+        self.quality_score
+    }
+
+    /// Returns the 0-based position in the read sequence.
+    ///
+    /// For a deletion, this is the position of the next aligned base.
+    pub fn read_position(&self) -> usize {
+        self.read_position
+    }
+
+    /// Returns whether the position is deleted in the read.
+    pub fn is_deletion(&self) -> bool {
+        self.base.is_none()
+    }
+
+    /// Returns whether the read is mapped to the reverse strand.
+    pub fn is_reverse_complemented(&self) -> bool {
+        self.is_reverse_complemented
+    }
+
+    /// Returns the indel that immediately follows this position, if any.
+    pub fn indel(&self) -> Option<&Indel> {
+        self.indel.as_ref()
+    }
+
+    /// Returns whether this is the first reference position covered by the read.
+    pub fn is_read_start(&self) -> bool {
+        self.is_read_start
+    }
+
+    /// Returns whether this is the last reference position covered by the read.
+    pub fn is_read_end(&self) -> bool {
+        self.is_read_end
+    }
+}
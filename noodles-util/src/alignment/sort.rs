@@ -0,0 +1,579 @@
+//! Alignment record sorting.
+//!
+//! [`Sorter`] sorts a stream of alignment records using an external merge sort. Records are
+//! buffered in memory up to a limit, and each full buffer is sorted and spilled to a temporary
+//! BAM file. The sorted runs are then merged. When there are more runs than can be open at once,
+//! they are first merged in rounds into fewer, larger runs.
+
+mod builder;
+mod order;
+
+pub use self::{builder::Builder, order::Order};
+
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    fs::{self, File},
+    io, mem,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{self, AtomicUsize},
+    vec,
+};
+
+use noodles_bam as bam;
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    record::{cigar::Op, data::field},
+};
+
+static SORT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// An alignment record sorter.
+///
+/// This is built using a [`Builder`].
+pub struct Sorter {
+    order: Order,
+    memory_limit: usize,
+    max_open_files: usize,
+    temp_dir: PathBuf,
+}
+
+impl Sorter {
+    /// Sets the sort order (`SO`) and subsort order (`SS`) of a header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::record::value::map::header::SortOrder};
+    /// use noodles_util::alignment::sort::{self, Order};
+    ///
+    /// let sorter = sort::Builder::default().set_order(Order::QueryName).build();
+    ///
+    /// let mut header = sam::Header::default();
+    /// sorter.update_header(&mut header);
+    ///
+    /// let hd = header.header().expect("missing header");
+    /// assert_eq!(hd.sort_order(), Some(SortOrder::QueryName));
+    /// assert_eq!(
+    ///     hd.subsort_order().map(|order| order.to_string()),
+    ///     Some(String::from("queryname:natural"))
+    /// );
+    /// ```
+    pub fn update_header(&self, header: &mut sam::Header) {
+        self.order.update_header(header);
+    }
+
+    /// Sorts alignment records.
+    ///
+    /// The header is used to encode records spilled to temporary files. Temporary files are
+    /// removed when the returned iterator is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::sort;
+    ///
+    /// let header = sam::Header::default();
+    ///
+    /// let records = [8, 1, 5].into_iter().map(|start| {
+    ///     Position::try_from(start)
+    ///         .map(|position| {
+    ///             sam::alignment::Record::builder()
+    ///                 .set_alignment_start(position)
+    ///                 .build()
+    ///         })
+    ///         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    /// });
+    ///
+    /// let sorter = sort::Builder::default().build();
+    ///
+    /// let starts = sorter
+    ///     .sort(&header, records)?
+    ///     .map(|result| result.map(|record| record.alignment_start().map(usize::from)))
+    ///     .collect::<io::Result<Vec<_>>>()?;
+    ///
+    /// assert_eq!(starts, [Some(1), Some(5), Some(8)]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn sort<I>(&self, header: &sam::Header, records: I) -> io::Result<Sorted>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        let id = SORT_ID.fetch_add(1, atomic::Ordering::Relaxed);
+        let mut temp_files = TempFiles::default();
+        let mut file_count = 0;
+
+        let mut next_path = || {
+            let path = self.temp_dir.join(format!(
+                "noodles-sort-{}-{}-{}.bam",
+                process::id(),
+                id,
+                file_count
+            ));
+
+            file_count += 1;
+
+            path
+        };
+
+        let mut buf = Vec::new();
+        let mut buf_size = 0;
+
+        for result in records {
+            let record = result?;
+
+            buf_size += estimate_size(&record);
+            buf.push(record);
+
+            if buf_size >= self.memory_limit {
+                let path = next_path();
+                temp_files.paths.push(path.clone());
+
+                self.sort_records(&mut buf);
+                spill(&path, header, buf.drain(..).map(Ok))?;
+
+                buf_size = 0;
+            }
+        }
+
+        // Runs are merged in order so that ties remain resolved by the earliest run.
+        while temp_files.paths.len() > self.max_open_files {
+            let src_files = TempFiles {
+                paths: mem::take(&mut temp_files.paths),
+            };
+
+            for paths in src_files.paths.chunks(self.max_open_files) {
+                let path = next_path();
+                temp_files.paths.push(path.clone());
+
+                let merger = Merger::new(self.order, header, open_runs(paths)?)?;
+                spill(&path, header, merger)?;
+            }
+        }
+
+        self.sort_records(&mut buf);
+
+        let mut runs = open_runs(&temp_files.paths)?;
+        runs.push(Run::Memory(buf.into_iter()));
+
+        Ok(Sorted {
+            merger: Merger::new(self.order, header, runs)?,
+            _temp_files: temp_files,
+        })
+    }
+
+    fn sort_records(&self, records: &mut [Record]) {
+        records.sort_by(|a, b| self.order.cmp(a, b));
+    }
+}
+
+/// An iterator over sorted alignment records.
+///
+/// This is created by calling [`Sorter::sort`].
+pub struct Sorted {
+    merger: Merger,
+    _temp_files: TempFiles,
+}
+
+impl Iterator for Sorted {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.merger.next()
+    }
+}
+
+// A k-way merge of sorted runs.
+struct Merger {
+    order: Order,
+    header: sam::Header,
+    runs: Vec<Run>,
+    heads: BinaryHeap<Head>,
+}
+
+impl Merger {
+    fn new(order: Order, header: &sam::Header, runs: Vec<Run>) -> io::Result<Self> {
+        let mut merger = Self {
+            order,
+            header: header.clone(),
+            heads: BinaryHeap::with_capacity(runs.len()),
+            runs,
+        };
+
+        for i in 0..merger.runs.len() {
+            merger.push_head(i)?;
+        }
+
+        Ok(merger)
+    }
+
+    fn push_head(&mut self, i: usize) -> io::Result<()> {
+        if let Some(record) = self.runs[i].read_record(&self.header)? {
+            self.heads.push(Head {
+                order: self.order,
+                run: i,
+                record,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Iterator for Merger {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let head = self.heads.pop()?;
+
+        match self.push_head(head.run) {
+            Ok(()) => Some(Ok(head.record)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+// The next record of a run.
+struct Head {
+    order: Order,
+    run: usize,
+    record: Record,
+}
+
+impl Ord for Head {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `BinaryHeap` is a max-heap, so the ordering is reversed to pop the least record first.
+        // Ties are resolved by the earliest run, which keeps the sort stable.
+        self.order
+            .cmp(&self.record, &other.record)
+            .then_with(|| self.run.cmp(&other.run))
+            .reverse()
+    }
+}
+
+impl PartialOrd for Head {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Head {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Head {}
+
+enum Run {
+    File(bam::Reader<bgzf::Reader<File>>),
+    Memory(vec::IntoIter<Record>),
+}
+
+impl Run {
+    fn read_record(&mut self, header: &sam::Header) -> io::Result<Option<Record>> {
+        match self {
+            Self::File(reader) => {
+                let mut record = Record::default();
+
+                match reader.read_record(header, &mut record)? {
+                    0 => Ok(None),
+                    _ => Ok(Some(record)),
+                }
+            }
+            Self::Memory(records) => Ok(records.next()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TempFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl Drop for TempFiles {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn open_runs(paths: &[PathBuf]) -> io::Result<Vec<Run>> {
+    paths
+        .iter()
+        .map(|path| {
+            let mut reader = File::open(path).map(bam::Reader::new)?;
+            reader.read_header()?;
+            Ok(Run::File(reader))
+        })
+        .collect()
+}
+
+fn spill<I>(dst: &Path, header: &sam::Header, records: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+{
+    let mut writer = bam::writer::Builder::default()
+        .set_compression_level(CompressionLevel::fast())
+        .build_from_path(dst)?;
+
+    writer.write_header(header)?;
+
+    for result in records {
+        let record = result?;
+        writer.write_record(header, &record)?;
+    }
+
+    writer.try_finish()
+}
+
+// Approximates the number of bytes a record uses in memory.
+fn estimate_size(record: &Record) -> usize {
+    mem::size_of::<Record>()
+        + record
+            .read_name()
+            .map(|name| AsRef::<[u8]>::as_ref(name).len())
+            .unwrap_or_default()
+        + record.cigar().len() * mem::size_of::<Op>()
+        + record.sequence().len()
+        + record.quality_scores().len()
+        + record
+            .data()
+            .values()
+            .map(|value| mem::size_of::<(field::Tag, field::Value)>() + value_size(value))
+            .sum::<usize>()
+}
+
+fn value_size(value: &field::Value) -> usize {
+    use field::value::Array;
+
+    match value {
+        field::Value::String(s) => s.len(),
+        field::Value::Hex(s) => s.as_ref().len(),
+        field::Value::Array(Array::Int8(values)) => values.len(),
+        field::Value::Array(Array::UInt8(values)) => values.len(),
+        field::Value::Array(Array::Int16(values)) => values.len() * 2,
+        field::Value::Array(Array::UInt16(values)) => values.len() * 2,
+        field::Value::Array(Array::Int32(values)) => values.len() * 4,
+        field::Value::Array(Array::UInt32(values)) => values.len() * 4,
+        field::Value::Array(Array::Float(values)) => values.len() * 4,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_sam::{
+        header::record::value::{map, Map},
+        record::{data::field::Tag, Flags},
+    };
+
+    use super::*;
+
+    fn read_names(records: &[Record]) -> Vec<String> {
+        records
+            .iter()
+            .map(|record| {
+                record
+                    .read_name()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use sam::header::record::value::map::ReferenceSequence;
+
+        Ok(sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .add_reference_sequence(
+                "sq1".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build())
+    }
+
+    fn build_records() -> Result<Vec<Record>, Box<dyn std::error::Error>> {
+        Ok(vec![
+            Record::builder()
+                .set_read_name("r10".parse()?)
+                .set_flags(Flags::UNMAPPED)
+                .build(),
+            Record::builder()
+                .set_read_name("r2".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(1)
+                .set_alignment_start(Position::try_from(5)?)
+                .build(),
+            Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(8)?)
+                .build(),
+            Record::builder()
+                .set_read_name("r2".parse()?)
+                .set_flags(Flags::REVERSE_COMPLEMENTED)
+                .set_reference_sequence_id(1)
+                .set_alignment_start(Position::try_from(5)?)
+                .build(),
+            Record::builder()
+                .set_read_name("r3".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::MIN)
+                .build(),
+        ])
+    }
+
+    fn sort(
+        builder: Builder,
+        records: Vec<Record>,
+    ) -> Result<Vec<Record>, Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let sorter = builder.build();
+        let sorted = sorter.sort(&header, records.into_iter().map(Ok))?;
+        Ok(sorted.collect::<io::Result<_>>()?)
+    }
+
+    #[test]
+    fn test_sort_by_coordinate() -> Result<(), Box<dyn std::error::Error>> {
+        let records = build_records()?;
+
+        let expected = ["r3", "r1", "r2", "r2", "r10"];
+
+        let actual = sort(Builder::default(), records.clone())?;
+        assert_eq!(read_names(&actual), expected);
+        assert!(actual[3].flags().is_reverse_complemented());
+
+        // Spills every record to a temporary file.
+        let temp_dir = std::env::temp_dir().join(format!("noodles-util-sort-{}", process::id()));
+        fs::create_dir_all(&temp_dir)?;
+
+        let actual = sort(
+            Builder::default()
+                .set_memory_limit(1)
+                .set_temp_dir(&temp_dir),
+            records,
+        )?;
+
+        assert_eq!(read_names(&actual), expected);
+        assert!(actual[3].flags().is_reverse_complemented());
+        assert!(fs::read_dir(&temp_dir)?.next().is_none());
+
+        fs::remove_dir(&temp_dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_with_max_open_files() -> Result<(), Box<dyn std::error::Error>> {
+        let records: Vec<_> = [(1, 5), (0, 8), (1, 5), (0, 1), (1, 5), (0, 8), (1, 2)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (reference_sequence_id, start))| {
+                Ok(Record::builder()
+                    .set_read_name(format!("r{i}").parse()?)
+                    .set_flags(Flags::empty())
+                    .set_reference_sequence_id(reference_sequence_id)
+                    .set_alignment_start(Position::try_from(start)?)
+                    .build())
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        let temp_dir = std::env::temp_dir().join(format!(
+            "noodles-util-sort-max-open-files-{}",
+            process::id()
+        ));
+        fs::create_dir_all(&temp_dir)?;
+
+        // Spills every record to a temporary file and merges them in rounds of 2.
+        let actual = sort(
+            Builder::default()
+                .set_memory_limit(1)
+                .set_max_open_files(2)
+                .set_temp_dir(&temp_dir),
+            records,
+        )?;
+
+        assert_eq!(
+            read_names(&actual),
+            ["r3", "r1", "r5", "r6", "r0", "r2", "r4"]
+        );
+        assert!(fs::read_dir(&temp_dir)?.next().is_none());
+
+        fs::remove_dir(&temp_dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_by_query_name() -> Result<(), Box<dyn std::error::Error>> {
+        let records = build_records()?;
+
+        let builder = Builder::default().set_order(Order::QueryName);
+        let actual = sort(builder.set_memory_limit(1024), records.clone())?;
+        assert_eq!(read_names(&actual), ["r1", "r2", "r2", "r3", "r10"]);
+
+        let builder = Builder::default().set_order(Order::QueryNameLexicographical);
+        let actual = sort(builder, records)?;
+        assert_eq!(read_names(&actual), ["r1", "r10", "r2", "r2", "r3"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_by_tag() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = build_records()?;
+        *records[2].data_mut() = "RG:Z:rg1".parse()?;
+        *records[4].data_mut() = "RG:Z:rg0".parse()?;
+
+        let builder = Builder::default()
+            .set_order(Order::Tag(Tag::ReadGroup))
+            .set_memory_limit(1);
+        let records = sort(builder, records)?;
+        assert_eq!(read_names(&records), ["r2", "r2", "r10", "r3", "r1"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_header() {
+        let mut header = sam::Header::default();
+        Builder::default().build().update_header(&mut header);
+
+        let hd = header.header();
+        assert_eq!(
+            hd.and_then(|hd| hd.sort_order()),
+            Some(map::header::SortOrder::Coordinate)
+        );
+        assert!(hd.and_then(|hd| hd.subsort_order()).is_none());
+
+        Builder::default()
+            .set_order(Order::Tag(Tag::ReadGroup))
+            .build()
+            .update_header(&mut header);
+
+        assert_eq!(
+            header
+                .header()
+                .and_then(|hd| hd.subsort_order())
+                .map(|order| order.to_string()),
+            Some(String::from("unsorted:tag:RG"))
+        );
+    }
+}
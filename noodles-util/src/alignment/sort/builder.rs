@@ -0,0 +1,112 @@
+use std::{env, path::PathBuf};
+
+use super::{Order, Sorter};
+
+// 768 MiB, the default of `samtools sort`.
+const DEFAULT_MEMORY_LIMIT: usize = 768 * 1024 * 1024;
+
+const DEFAULT_MAX_OPEN_FILES: usize = 64;
+
+/// An alignment record sorter builder.
+pub struct Builder {
+    order: Order,
+    memory_limit: usize,
+    max_open_files: usize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Sets the sort order.
+    ///
+    /// By default, records are sorted by coordinate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort::{self, Order};
+    /// let builder = sort::Builder::default().set_order(Order::QueryName);
+    /// ```
+    pub fn set_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets the approximate number of bytes of records to hold in memory.
+    ///
+    /// When the limit is reached, the records in memory are sorted and spilled to a temporary
+    /// file. By default, this is 768 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort;
+    /// let builder = sort::Builder::default().set_memory_limit(1 << 30);
+    /// ```
+    pub fn set_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Sets the maximum number of temporary files to read at once.
+    ///
+    /// When more records are spilled than this, temporary files are merged in rounds of up to
+    /// this many files before the final merge. This is clamped to at least 2. By default, this is
+    /// 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort;
+    /// let builder = sort::Builder::default().set_max_open_files(16);
+    /// ```
+    pub fn set_max_open_files(mut self, max_open_files: usize) -> Self {
+        self.max_open_files = max_open_files;
+        self
+    }
+
+    /// Sets the directory to write temporary files to.
+    ///
+    /// By default, this is [`std::env::temp_dir`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort;
+    /// let builder = sort::Builder::default().set_temp_dir("/tmp");
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.temp_dir = Some(temp_dir.into());
+        self
+    }
+
+    /// Builds an alignment record sorter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort;
+    /// let sorter = sort::Builder::default().build();
+    /// ```
+    pub fn build(self) -> Sorter {
+        Sorter {
+            order: self.order,
+            memory_limit: self.memory_limit,
+            max_open_files: self.max_open_files.max(2),
+            temp_dir: self.temp_dir.unwrap_or_else(env::temp_dir),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            order: Order::default(),
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            max_open_files: DEFAULT_MAX_OPEN_FILES,
+            temp_dir: None,
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    header::record::value::{
+        map::{
+            self,
+            header::{SortOrder, SubsortOrder},
+        },
+        Map,
+    },
+    record::data::field::{Tag, Value},
+};
+
+/// An alignment record sort order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Order {
+    /// Sorted by reference sequence, alignment start, and strand.
+    ///
+    /// Unplaced records are last.
+    #[default]
+    Coordinate,
+    /// Sorted by read name, comparing runs of digits numerically.
+    ///
+    /// This is the order used by `samtools sort -n`.
+    QueryName,
+    /// Sorted by read name, comparing bytes.
+    QueryNameLexicographical,
+    /// Sorted by the value of a data field and then by coordinate.
+    ///
+    /// Records without the field are first.
+    Tag(Tag),
+}
+
+impl Order {
+    // Sets the sort order (`SO`) and subsort order (`SS`) of a header.
+    pub(crate) fn update_header(&self, header: &mut sam::Header) {
+        let hd = header
+            .header_mut()
+            .get_or_insert_with(Map::<map::Header>::default);
+
+        *hd.sort_order_mut() = Some(self.sort_order());
+        *hd.subsort_order_mut() = self.subsort_order();
+    }
+
+    fn sort_order(&self) -> SortOrder {
+        match self {
+            Self::Coordinate => SortOrder::Coordinate,
+            Self::QueryName | Self::QueryNameLexicographical => SortOrder::QueryName,
+            Self::Tag(_) => SortOrder::Unsorted,
+        }
+    }
+
+    fn subsort_order(&self) -> Option<SubsortOrder> {
+        match self {
+            Self::Coordinate => None,
+            Self::QueryName => Some(SubsortOrder::QueryName(vec![String::from("natural")])),
+            Self::QueryNameLexicographical => Some(SubsortOrder::QueryName(vec![String::from(
+                "lexicographical",
+            )])),
+            Self::Tag(tag) => Some(SubsortOrder::Unsorted(vec![
+                String::from("tag"),
+                tag.to_string(),
+            ])),
+        }
+    }
+
+    pub(crate) fn cmp(&self, a: &Record, b: &Record) -> Ordering {
+        match self {
+            Self::Coordinate => cmp_coordinates(a, b),
+            Self::QueryName => cmp_read_names(a, b, natural_cmp),
+            Self::QueryNameLexicographical => cmp_read_names(a, b, |x, y| x.cmp(y)),
+            Self::Tag(tag) => cmp_values(a.data().get(*tag), b.data().get(*tag))
+                .then_with(|| cmp_coordinates(a, b)),
+        }
+    }
+}
+
+fn cmp_coordinates(a: &Record, b: &Record) -> Ordering {
+    // `None` is mapped to `usize::MAX` to place unplaced records last.
+    let key = |record: &Record| {
+        (
+            record.reference_sequence_id().unwrap_or(usize::MAX),
+            record
+                .alignment_start()
+                .map(usize::from)
+                .unwrap_or(usize::MAX),
+            record.flags().is_reverse_complemented(),
+        )
+    };
+
+    key(a).cmp(&key(b))
+}
+
+fn cmp_read_names<F>(a: &Record, b: &Record, cmp_names: F) -> Ordering
+where
+    F: Fn(&[u8], &[u8]) -> Ordering,
+{
+    // Within a name, segments are ordered first then last, and primary records come before
+    // secondary and supplementary records.
+    let flags_key = |record: &Record| {
+        let flags = record.flags();
+
+        (
+            !flags.is_first_segment(),
+            !flags.is_last_segment(),
+            flags.is_secondary(),
+            flags.is_supplementary(),
+        )
+    };
+
+    cmp_names(read_name(a), read_name(b)).then_with(|| flags_key(a).cmp(&flags_key(b)))
+}
+
+fn read_name(record: &Record) -> &[u8] {
+    record
+        .read_name()
+        .map(AsRef::<[u8]>::as_ref)
+        .unwrap_or_default()
+}
+
+// Compares byte strings, treating runs of ASCII digits as numbers.
+fn natural_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let a_end = i + a[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let b_end = j + b[j..].iter().take_while(|c| c.is_ascii_digit()).count();
+
+            let a_digits = trim_leading_zeros(&a[i..a_end]);
+            let b_digits = trim_leading_zeros(&b[j..b_end]);
+
+            let ordering = a_digits
+                .len()
+                .cmp(&b_digits.len())
+                .then_with(|| a_digits.cmp(b_digits))
+                .then_with(|| (a_end - i).cmp(&(b_end - j)));
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+
+            i = a_end;
+            j = b_end;
+        } else {
+            match a[i].cmp(&b[j]) {
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+                ordering => return ordering,
+            }
+        }
+    }
+
+    (a.len() - i).cmp(&(b.len() - j))
+}
+
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let n = digits.iter().take_while(|&&c| c == b'0').count();
+    &digits[n..]
+}
+
+// Missing values are first, followed by numbers and then strings.
+fn cmp_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    enum Key<'a> {
+        Missing,
+        Int(i64),
+        Float(f32),
+        String(&'a str),
+        Other(String),
+    }
+
+    fn key(value: Option<&Value>) -> Key<'_> {
+        match value {
+            None => Key::Missing,
+            Some(value) => {
+                if let Some(n) = value.as_int() {
+                    Key::Int(n)
+                } else if let Some(n) = value.as_float() {
+                    Key::Float(n)
+                } else if let Some(s) = value.as_str() {
+                    Key::String(s)
+                } else {
+                    Key::Other(value.to_string())
+                }
+            }
+        }
+    }
+
+    fn rank(key: &Key<'_>) -> u8 {
+        match key {
+            Key::Missing => 0,
+            Key::Int(_) | Key::Float(_) => 1,
+            Key::String(_) | Key::Other(_) => 2,
+        }
+    }
+
+    let (a, b) = (key(a), key(b));
+
+    match (&a, &b) {
+        (Key::Int(m), Key::Int(n)) => m.cmp(n),
+        (Key::Int(m), Key::Float(n)) => (*m as f64).total_cmp(&f64::from(*n)),
+        (Key::Float(m), Key::Int(n)) => f64::from(*m).total_cmp(&(*n as f64)),
+        (Key::Float(m), Key::Float(n)) => m.total_cmp(n),
+        (Key::String(s), Key::String(t)) => s.cmp(t),
+        (Key::String(s), Key::Other(t)) => (*s).cmp(t.as_str()),
+        (Key::Other(s), Key::String(t)) => s.as_str().cmp(t),
+        (Key::Other(s), Key::Other(t)) => s.cmp(t),
+        _ => rank(&a).cmp(&rank(&b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp(b"r2", b"r10"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r10", b"r2"), Ordering::Greater);
+        assert_eq!(natural_cmp(b"r10", b"r10"), Ordering::Equal);
+        assert_eq!(natural_cmp(b"r01", b"r1"), Ordering::Greater);
+        assert_eq!(natural_cmp(b"a1b2", b"a1b10"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r1", b"r1a"), Ordering::Less);
+        assert_eq!(natural_cmp(b"ra", b"r1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_cmp_values() {
+        let s = Value::String(String::from("ndls"));
+
+        assert_eq!(cmp_values(None, Some(&Value::from(1))), Ordering::Less);
+        assert_eq!(
+            cmp_values(Some(&Value::from(2)), Some(&Value::from(10))),
+            Ordering::Less
+        );
+        assert_eq!(
+            cmp_values(Some(&Value::from(2.5)), Some(&Value::from(2))),
+            Ordering::Greater
+        );
+        assert_eq!(cmp_values(Some(&Value::from(1)), Some(&s)), Ordering::Less);
+        assert_eq!(cmp_values(Some(&s), Some(&s)), Ordering::Equal);
+    }
+}
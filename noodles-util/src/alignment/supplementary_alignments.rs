@@ -0,0 +1,272 @@
+//! Alignment record supplementary alignments.
+//!
+//! Chimeric alignments list their other canonical alignments in the `SA` data field, e.g.,
+//! `sq0,8,+,2S4M,13,0;sq1,21,-,4M2S,34,1;`. [`SupplementaryAlignments`] is a typed view of this
+//! value. The BWA-specific alternative hits (`XA`) data field can be read using
+//! [`SupplementaryAlignments::from_alternative_hits`].
+
+pub mod supplementary_alignment;
+
+pub use self::supplementary_alignment::SupplementaryAlignment;
+
+use std::{error, fmt, ops::Deref, str::FromStr};
+
+use noodles_sam::record::data::field::Value;
+
+const DELIMITER: char = ';';
+
+/// A list of supplementary alignments.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SupplementaryAlignments(Vec<SupplementaryAlignment>);
+
+impl SupplementaryAlignments {
+    /// Parses a list of alternative hits.
+    ///
+    /// This is the value of the BWA-specific `XA` data field, i.e., `(chr,[+-]pos,CIGAR,NM;)+`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignments;
+    ///
+    /// let supplementary_alignments =
+    ///     SupplementaryAlignments::from_alternative_hits("sq0,+8,4M,0;sq1,-13,4M,1;")?;
+    ///
+    /// assert_eq!(supplementary_alignments.len(), 2);
+    /// # Ok::<_, noodles_util::alignment::supplementary_alignments::ParseError>(())
+    /// ```
+    pub fn from_alternative_hits(s: &str) -> Result<Self, ParseError> {
+        parse(s, SupplementaryAlignment::from_alternative_hit)
+    }
+}
+
+impl Deref for SupplementaryAlignments {
+    type Target = [SupplementaryAlignment];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsMut<Vec<SupplementaryAlignment>> for SupplementaryAlignments {
+    fn as_mut(&mut self) -> &mut Vec<SupplementaryAlignment> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<SupplementaryAlignment>> for SupplementaryAlignments {
+    fn from(supplementary_alignments: Vec<SupplementaryAlignment>) -> Self {
+        Self(supplementary_alignments)
+    }
+}
+
+impl From<SupplementaryAlignments> for Vec<SupplementaryAlignment> {
+    fn from(supplementary_alignments: SupplementaryAlignments) -> Self {
+        supplementary_alignments.0
+    }
+}
+
+impl fmt::Display for SupplementaryAlignments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for supplementary_alignment in self.iter() {
+            write!(f, "{supplementary_alignment}{DELIMITER}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw list of supplementary alignments fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The data field value is not a string.
+    InvalidType,
+    /// A supplementary alignment is invalid.
+    InvalidSupplementaryAlignment(supplementary_alignment::ParseError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidSupplementaryAlignment(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::InvalidType => f.write_str("invalid type"),
+            Self::InvalidSupplementaryAlignment(_) => {
+                f.write_str("invalid supplementary alignment")
+            }
+        }
+    }
+}
+
+impl FromStr for SupplementaryAlignments {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, str::parse)
+    }
+}
+
+impl TryFrom<&Value> for SupplementaryAlignments {
+    type Error = ParseError;
+
+    /// Converts an other canonical alignments (`SA`) data field value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{alignment::Record, record::data::field::{Tag, Value}};
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignments;
+    ///
+    /// let mut record = Record::default();
+    /// record.data_mut().insert(
+    ///     Tag::OtherAlignments,
+    ///     Value::String(String::from("sq0,8,+,4M,13,0;")),
+    /// );
+    ///
+    /// let supplementary_alignments = record
+    ///     .data()
+    ///     .get(Tag::OtherAlignments)
+    ///     .map(SupplementaryAlignments::try_from)
+    ///     .transpose()?;
+    ///
+    /// assert_eq!(supplementary_alignments.map(|sa| sa.len()), Some(1));
+    /// # Ok::<_, noodles_util::alignment::supplementary_alignments::ParseError>(())
+    /// ```
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .as_str()
+            .ok_or(ParseError::InvalidType)
+            .and_then(|s| s.parse())
+    }
+}
+
+impl From<SupplementaryAlignments> for Value {
+    fn from(supplementary_alignments: SupplementaryAlignments) -> Self {
+        Self::String(supplementary_alignments.to_string())
+    }
+}
+
+fn parse<F>(s: &str, f: F) -> Result<SupplementaryAlignments, ParseError>
+where
+    F: Fn(&str) -> Result<SupplementaryAlignment, supplementary_alignment::ParseError>,
+{
+    // The list is terminated by a delimiter, but a missing final delimiter is accepted.
+    let s = s.strip_suffix(DELIMITER).unwrap_or(s);
+
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    s.split(DELIMITER)
+        .map(|t| f(t).map_err(ParseError::InvalidSupplementaryAlignment))
+        .collect::<Result<_, _>>()
+        .map(SupplementaryAlignments)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_sam::record::MappingQuality;
+
+    use super::{supplementary_alignment::Strand, *};
+
+    fn build_supplementary_alignments(
+    ) -> Result<SupplementaryAlignments, Box<dyn std::error::Error>> {
+        Ok(SupplementaryAlignments::from(vec![
+            SupplementaryAlignment::new(
+                String::from("sq0"),
+                Position::try_from(8)?,
+                Strand::Forward,
+                "2S4M".parse()?,
+                MappingQuality::new(13),
+                0,
+            ),
+            SupplementaryAlignment::new(
+                String::from("sq1"),
+                Position::try_from(21)?,
+                Strand::Reverse,
+                "4M2S".parse()?,
+                MappingQuality::new(34),
+                1,
+            ),
+        ]))
+    }
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let supplementary_alignments = build_supplementary_alignments()?;
+
+        assert_eq!(
+            supplementary_alignments.to_string(),
+            "sq0,8,+,2S4M,13,0;sq1,21,-,4M2S,34,1;"
+        );
+
+        assert!(SupplementaryAlignments::default().to_string().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = build_supplementary_alignments()?;
+
+        assert_eq!(
+            "sq0,8,+,2S4M,13,0;sq1,21,-,4M2S,34,1;".parse(),
+            Ok(expected.clone())
+        );
+        assert_eq!("sq0,8,+,2S4M,13,0;sq1,21,-,4M2S,34,1".parse(), Ok(expected));
+
+        assert_eq!(
+            "".parse::<SupplementaryAlignments>(),
+            Err(ParseError::Empty)
+        );
+        assert_eq!(
+            ";".parse::<SupplementaryAlignments>(),
+            Err(ParseError::Empty)
+        );
+        assert!(matches!(
+            "sq0,8,+,2S4M,13,0;;".parse::<SupplementaryAlignments>(),
+            Err(ParseError::InvalidSupplementaryAlignment(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_alternative_hits() -> Result<(), Box<dyn std::error::Error>> {
+        let supplementary_alignments =
+            SupplementaryAlignments::from_alternative_hits("sq0,+8,2S4M,0;sq1,-21,4M2S,1;")?;
+
+        assert_eq!(supplementary_alignments.len(), 2);
+        assert_eq!(supplementary_alignments[1].strand(), Strand::Reverse);
+        assert!(supplementary_alignments[1].mapping_quality().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_value_for_supplementary_alignments() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let expected = build_supplementary_alignments()?;
+
+        let value = Value::from(expected.clone());
+        assert_eq!(SupplementaryAlignments::try_from(&value), Ok(expected));
+
+        assert_eq!(
+            SupplementaryAlignments::try_from(&Value::from(0)),
+            Err(ParseError::InvalidType)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,532 @@
+//! Supplementary alignment.
+
+pub mod strand;
+
+pub use self::strand::Strand;
+
+use std::{error, fmt, num, str::FromStr};
+
+use noodles_core::{position, Position};
+use noodles_sam::{
+    self as sam,
+    record::{cigar, mapping_quality, Cigar, MappingQuality},
+};
+
+const DELIMITER: char = ',';
+
+/// A supplementary alignment.
+///
+/// This is a single entry in the other canonical alignments (`SA`) data field, i.e.,
+/// `rname,pos,strand,CIGAR,mapQ,NM`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupplementaryAlignment {
+    reference_sequence_name: String,
+    position: Position,
+    strand: Strand,
+    cigar: Cigar,
+    mapping_quality: Option<MappingQuality>,
+    edit_distance: u32,
+}
+
+impl SupplementaryAlignment {
+    /// Creates a supplementary alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::record::MappingQuality;
+    /// use noodles_util::alignment::supplementary_alignments::{
+    ///     supplementary_alignment::Strand, SupplementaryAlignment,
+    /// };
+    ///
+    /// let supplementary_alignment = SupplementaryAlignment::new(
+    ///     String::from("sq0"),
+    ///     Position::try_from(8)?,
+    ///     Strand::Forward,
+    ///     "4M".parse()?,
+    ///     MappingQuality::new(13),
+    ///     0,
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(
+        reference_sequence_name: String,
+        position: Position,
+        strand: Strand,
+        cigar: Cigar,
+        mapping_quality: Option<MappingQuality>,
+        edit_distance: u32,
+    ) -> Self {
+        Self {
+            reference_sequence_name,
+            position,
+            strand,
+            cigar,
+            mapping_quality,
+            edit_distance,
+        }
+    }
+
+    /// Parses an alternative hit.
+    ///
+    /// Alternative hits are entries in the BWA-specific `XA` data field, i.e.,
+    /// `chr,[+-]pos,CIGAR,NM`. The strand is the sign of the position, and they do not have a
+    /// mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_util::alignment::supplementary_alignments::{
+    ///     supplementary_alignment::Strand, SupplementaryAlignment,
+    /// };
+    ///
+    /// let supplementary_alignment = SupplementaryAlignment::from_alternative_hit("sq0,-8,4M,1")?;
+    /// assert_eq!(supplementary_alignment.position(), Position::try_from(8)?);
+    /// assert_eq!(supplementary_alignment.strand(), Strand::Reverse);
+    /// assert!(supplementary_alignment.mapping_quality().is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_alternative_hit(s: &str) -> Result<Self, ParseError> {
+        let mut fields = s.split(DELIMITER);
+
+        let reference_sequence_name = parse_reference_sequence_name(&mut fields)?;
+
+        let raw_position = fields.next().ok_or(ParseError::MissingPosition)?;
+
+        let (strand, raw_position) = if let Some(t) = raw_position.strip_prefix('-') {
+            (Strand::Reverse, t)
+        } else if let Some(t) = raw_position.strip_prefix('+') {
+            (Strand::Forward, t)
+        } else {
+            return Err(ParseError::InvalidStrand(strand::ParseError::Invalid));
+        };
+
+        let position = parse_position(raw_position)?;
+        let cigar = parse_cigar(&mut fields)?;
+        let edit_distance = parse_edit_distance(&mut fields)?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::ExpectedEof);
+        }
+
+        Ok(Self::new(
+            reference_sequence_name,
+            position,
+            strand,
+            cigar,
+            None,
+            edit_distance,
+        ))
+    }
+
+    /// Returns the reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignment;
+    /// let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,4M,13,0".parse()?;
+    /// assert_eq!(supplementary_alignment.reference_sequence_name(), "sq0");
+    /// # Ok::<_, noodles_util::alignment::supplementary_alignments::supplementary_alignment::ParseError>(())
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        &self.reference_sequence_name
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignment;
+    /// let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,4M,13,0".parse()?;
+    /// assert_eq!(supplementary_alignment.position(), Position::try_from(8)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the strand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::supplementary_alignments::{
+    ///     supplementary_alignment::Strand, SupplementaryAlignment,
+    /// };
+    ///
+    /// let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,4M,13,0".parse()?;
+    /// assert_eq!(supplementary_alignment.strand(), Strand::Forward);
+    /// # Ok::<_, noodles_util::alignment::supplementary_alignments::supplementary_alignment::ParseError>(())
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignment;
+    /// let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,4M,13,0".parse()?;
+    /// assert_eq!(supplementary_alignment.cigar().to_string(), "4M");
+    /// # Ok::<_, noodles_util::alignment::supplementary_alignments::supplementary_alignment::ParseError>(())
+    /// ```
+    pub fn cigar(&self) -> &Cigar {
+        &self.cigar
+    }
+
+    /// Returns the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::MappingQuality;
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignment;
+    /// let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,4M,13,0".parse()?;
+    /// assert_eq!(supplementary_alignment.mapping_quality(), MappingQuality::new(13));
+    /// # Ok::<_, noodles_util::alignment::supplementary_alignments::supplementary_alignment::ParseError>(())
+    /// ```
+    pub fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality
+    }
+
+    /// Returns the edit distance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignment;
+    /// let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,4M,13,0".parse()?;
+    /// assert_eq!(supplementary_alignment.edit_distance(), 0);
+    /// # Ok::<_, noodles_util::alignment::supplementary_alignments::supplementary_alignment::ParseError>(())
+    /// ```
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+
+    /// Calculates the end position.
+    ///
+    /// This uses the alignment span of the CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignment;
+    /// let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,2S4M1D,13,1".parse()?;
+    /// assert_eq!(supplementary_alignment.alignment_end(), Position::new(12));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn alignment_end(&self) -> Option<Position> {
+        let span = self.cigar.alignment_span().checked_sub(1)?;
+        self.position.checked_add(span)
+    }
+
+    /// Returns the reference sequence ID of the reference sequence name in the header.
+    ///
+    /// This returns `None` if the header does not have a reference sequence with the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_sam::{self as sam, header::record::value::{map::ReferenceSequence, Map}};
+    /// use noodles_util::alignment::supplementary_alignments::SupplementaryAlignment;
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(
+    ///         "sq0".parse()?,
+    ///         Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+    ///     )
+    ///     .add_reference_sequence(
+    ///         "sq1".parse()?,
+    ///         Map::<ReferenceSequence>::new(NonZeroUsize::try_from(21)?),
+    ///     )
+    ///     .build();
+    ///
+    /// let supplementary_alignment: SupplementaryAlignment = "sq1,8,+,4M,13,0".parse()?;
+    /// assert_eq!(supplementary_alignment.reference_sequence_id(&header), Some(1));
+    ///
+    /// let supplementary_alignment: SupplementaryAlignment = "sq2,8,+,4M,13,0".parse()?;
+    /// assert!(supplementary_alignment.reference_sequence_id(&header).is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_sequence_id(&self, header: &sam::Header) -> Option<usize> {
+        header
+            .reference_sequences()
+            .get_index_of(self.reference_sequence_name.as_str())
+    }
+}
+
+impl fmt::Display for SupplementaryAlignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mapping_quality = self
+            .mapping_quality
+            .map(u8::from)
+            .unwrap_or(mapping_quality::MISSING);
+
+        write!(
+            f,
+            "{},{},{},{},{},{}",
+            self.reference_sequence_name,
+            self.position,
+            self.strand,
+            self.cigar,
+            mapping_quality,
+            self.edit_distance
+        )
+    }
+}
+
+/// An error returned when a raw supplementary alignment fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The position is missing.
+    MissingPosition,
+    /// The position is invalid.
+    InvalidPosition(Option<position::ParseError>),
+    /// The strand is missing.
+    MissingStrand,
+    /// The strand is invalid.
+    InvalidStrand(strand::ParseError),
+    /// The CIGAR is missing.
+    MissingCigar,
+    /// The CIGAR is invalid.
+    InvalidCigar(cigar::ParseError),
+    /// The mapping quality is missing.
+    MissingMappingQuality,
+    /// The mapping quality is invalid.
+    InvalidMappingQuality(num::ParseIntError),
+    /// The edit distance is missing.
+    MissingEditDistance,
+    /// The edit distance is invalid.
+    InvalidEditDistance(num::ParseIntError),
+    /// The input has unexpected trailing fields.
+    ExpectedEof,
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidPosition(Some(e)) => Some(e),
+            Self::InvalidStrand(e) => Some(e),
+            Self::InvalidCigar(e) => Some(e),
+            Self::InvalidMappingQuality(e) => Some(e),
+            Self::InvalidEditDistance(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::MissingPosition => f.write_str("missing position"),
+            Self::InvalidPosition(_) => f.write_str("invalid position"),
+            Self::MissingStrand => f.write_str("missing strand"),
+            Self::InvalidStrand(_) => f.write_str("invalid strand"),
+            Self::MissingCigar => f.write_str("missing CIGAR"),
+            Self::InvalidCigar(_) => f.write_str("invalid CIGAR"),
+            Self::MissingMappingQuality => f.write_str("missing mapping quality"),
+            Self::InvalidMappingQuality(_) => f.write_str("invalid mapping quality"),
+            Self::MissingEditDistance => f.write_str("missing edit distance"),
+            Self::InvalidEditDistance(_) => f.write_str("invalid edit distance"),
+            Self::ExpectedEof => f.write_str("expected EOF"),
+        }
+    }
+}
+
+impl FromStr for SupplementaryAlignment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(DELIMITER);
+
+        let reference_sequence_name = parse_reference_sequence_name(&mut fields)?;
+
+        let position = fields
+            .next()
+            .ok_or(ParseError::MissingPosition)
+            .and_then(parse_position)?;
+
+        let strand = fields
+            .next()
+            .ok_or(ParseError::MissingStrand)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidStrand))?;
+
+        let cigar = parse_cigar(&mut fields)?;
+
+        // A missing mapping quality is represented by 255.
+        let mapping_quality = fields
+            .next()
+            .ok_or(ParseError::MissingMappingQuality)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidMappingQuality))
+            .map(MappingQuality::new)?;
+
+        let edit_distance = parse_edit_distance(&mut fields)?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::ExpectedEof);
+        }
+
+        Ok(Self::new(
+            reference_sequence_name,
+            position,
+            strand,
+            cigar,
+            mapping_quality,
+            edit_distance,
+        ))
+    }
+}
+
+fn parse_reference_sequence_name<'a, I>(fields: &mut I) -> Result<String, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    fields
+        .next()
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .ok_or(ParseError::Empty)
+}
+
+fn parse_position(s: &str) -> Result<Position, ParseError> {
+    s.parse::<usize>()
+        .map_err(|e| ParseError::InvalidPosition(Some(e)))
+        .and_then(|n| Position::new(n).ok_or(ParseError::InvalidPosition(None)))
+}
+
+fn parse_cigar<'a, I>(fields: &mut I) -> Result<Cigar, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    fields
+        .next()
+        .ok_or(ParseError::MissingCigar)
+        .and_then(|t| t.parse().map_err(ParseError::InvalidCigar))
+}
+
+fn parse_edit_distance<'a, I>(fields: &mut I) -> Result<u32, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    fields
+        .next()
+        .ok_or(ParseError::MissingEditDistance)
+        .and_then(|t| t.parse().map_err(ParseError::InvalidEditDistance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let supplementary_alignment = SupplementaryAlignment::new(
+            String::from("sq0"),
+            Position::try_from(8)?,
+            Strand::Reverse,
+            "2S4M".parse()?,
+            MappingQuality::new(13),
+            1,
+        );
+
+        assert_eq!(supplementary_alignment.to_string(), "sq0,8,-,2S4M,13,1");
+
+        let supplementary_alignment = SupplementaryAlignment::new(
+            String::from("sq0"),
+            Position::try_from(8)?,
+            Strand::Forward,
+            "4M".parse()?,
+            None,
+            0,
+        );
+
+        assert_eq!(supplementary_alignment.to_string(), "sq0,8,+,4M,255,0");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = SupplementaryAlignment::new(
+            String::from("sq0"),
+            Position::try_from(8)?,
+            Strand::Reverse,
+            "2S4M".parse()?,
+            MappingQuality::new(13),
+            1,
+        );
+
+        assert_eq!("sq0,8,-,2S4M,13,1".parse(), Ok(expected));
+
+        let supplementary_alignment: SupplementaryAlignment = "sq0,8,+,4M,255,0".parse()?;
+        assert!(supplementary_alignment.mapping_quality().is_none());
+
+        assert_eq!("".parse::<SupplementaryAlignment>(), Err(ParseError::Empty));
+        assert_eq!(
+            "sq0".parse::<SupplementaryAlignment>(),
+            Err(ParseError::MissingPosition)
+        );
+        assert_eq!(
+            "sq0,0,+,4M,13,0".parse::<SupplementaryAlignment>(),
+            Err(ParseError::InvalidPosition(None))
+        );
+        assert!(matches!(
+            "sq0,8,.,4M,13,0".parse::<SupplementaryAlignment>(),
+            Err(ParseError::InvalidStrand(_))
+        ));
+        assert!(matches!(
+            "sq0,8,+,4Z,13,0".parse::<SupplementaryAlignment>(),
+            Err(ParseError::InvalidCigar(_))
+        ));
+        assert!(matches!(
+            "sq0,8,+,4M,256,0".parse::<SupplementaryAlignment>(),
+            Err(ParseError::InvalidMappingQuality(_))
+        ));
+        assert_eq!(
+            "sq0,8,+,4M,13".parse::<SupplementaryAlignment>(),
+            Err(ParseError::MissingEditDistance)
+        );
+        assert_eq!(
+            "sq0,8,+,4M,13,0,ndls".parse::<SupplementaryAlignment>(),
+            Err(ParseError::ExpectedEof)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_alternative_hit() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = SupplementaryAlignment::new(
+            String::from("sq0"),
+            Position::try_from(8)?,
+            Strand::Forward,
+            "4M".parse()?,
+            None,
+            2,
+        );
+
+        assert_eq!(
+            SupplementaryAlignment::from_alternative_hit("sq0,+8,4M,2"),
+            Ok(expected)
+        );
+
+        assert_eq!(
+            SupplementaryAlignment::from_alternative_hit("sq0,8,4M,2"),
+            Err(ParseError::InvalidStrand(strand::ParseError::Invalid))
+        );
+
+        Ok(())
+    }
+}
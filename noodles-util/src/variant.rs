@@ -1,7 +1,9 @@
 //! I/O for variant formats.
 
 mod format;
+pub mod norm;
 pub mod reader;
+pub mod samples;
 pub mod writer;
 
 pub use self::{
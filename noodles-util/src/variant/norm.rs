@@ -0,0 +1,937 @@
+//! Variant normalization.
+//!
+//! This left-aligns and trims alleles and splits and joins multiallelic records, similar to
+//! `bcftools norm`.
+
+use std::{fmt, io};
+
+use noodles_core::Position;
+use noodles_fasta as fasta;
+use noodles_vcf::{
+    self as vcf,
+    header::Number,
+    record::{
+        alternate_bases::Allele,
+        genotypes::{keys::key, sample::Value as SampleValue, Keys},
+        info::field::Value as InfoValue,
+        reference_bases::Base,
+        AlternateBases, Chromosome, Genotypes, ReferenceBases,
+    },
+    Record,
+};
+
+const DELIMITER: char = ',';
+const MISSING: &str = ".";
+
+/// Left-aligns and trims the alleles of a record.
+///
+/// Bases shared by the ends of all alleles are trimmed, and indels are shifted left as far as the
+/// reference sequence allows. A single base of padding is kept before indels, as required by VCF.
+/// The reference bases are checked against the reference sequence.
+///
+/// Records with symbolic, breakend, or overlapping deletion (`*`) alleles or with alternate alleles
+/// that are all the same as the reference allele are not changed.
+///
+/// This returns whether the record was changed.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta as fasta;
+/// use noodles_vcf::{self as vcf, record::Position};
+/// use noodles_util::variant::norm;
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     fasta::record::Definition::new("sq0", None),
+///     fasta::record::Sequence::from(b"GCACACAT".to_vec()),
+/// )]);
+///
+/// let mut record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(Position::from(5))
+///     .set_reference_bases("ACA".parse()?)
+///     .set_alternate_bases("A".parse()?)
+///     .build()?;
+///
+/// assert!(norm::left_align(&repository, &mut record)?);
+/// assert_eq!(usize::from(record.position()), 1);
+/// assert_eq!(record.reference_bases().to_string(), "GCA");
+/// assert_eq!(record.alternate_bases().to_string(), "G");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn left_align(repository: &fasta::Repository, record: &mut Record) -> io::Result<bool> {
+    if record.alternate_bases().is_empty() {
+        return Ok(false);
+    }
+
+    let mut alleles = vec![record.reference_bases().to_vec()];
+
+    for allele in record.alternate_bases().iter() {
+        match allele {
+            Allele::Bases(bases) => alleles.push(bases.clone()),
+            _ => return Ok(false),
+        }
+    }
+
+    let name = match record.chromosome() {
+        Chromosome::Name(name) => name,
+        Chromosome::Symbol(_) => return Ok(false),
+    };
+
+    let reference_sequence = repository.get(name).transpose()?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("missing reference sequence: {name}"),
+        )
+    })?;
+
+    let start = usize::from(record.position());
+
+    for (i, &base) in alleles[0].iter().enumerate() {
+        let reference_base = get_base(&reference_sequence, start + i)?;
+
+        if base != Base::N && reference_base != Base::N && base != reference_base {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "reference bases do not match the reference sequence",
+            ));
+        }
+    }
+
+    let position = normalize_alleles(&reference_sequence, start, &mut alleles)?;
+
+    if position == start && alleles[0] == record.reference_bases().to_vec() {
+        return Ok(false);
+    }
+
+    let alternate_alleles = alleles.split_off(1);
+
+    let reference_bases = alleles
+        .pop()
+        .map(ReferenceBases::try_from)
+        .transpose()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing reference bases"))?;
+
+    *record.position_mut() = vcf::record::Position::from(position);
+    *record.reference_bases_mut() = reference_bases;
+    *record.alternate_bases_mut() = AlternateBases::from(
+        alternate_alleles
+            .into_iter()
+            .map(Allele::Bases)
+            .collect::<Vec<_>>(),
+    );
+
+    Ok(true)
+}
+
+/// Splits a multiallelic record into biallelic records.
+///
+/// Info and genotype fields with a number of alternate alleles (`A`), reference and alternate
+/// alleles (`R`), or genotypes (`G`), as defined in the header, are subset for each alternate
+/// allele. Genotype (`GT`) alleles that are not the alternate allele of the split record are set
+/// to the reference allele. Other fields are copied.
+///
+/// Records with at most one alternate allele are returned unchanged. The split records are not
+/// left-aligned (see [`left_align`]).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, record::Position};
+/// use noodles_util::variant::norm;
+///
+/// let header: vcf::Header = "##fileformat=VCFv4.3
+/// ###INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// "
+/// .parse()?;
+///
+/// let record = vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC,G\t.\t.\tAC=1,2"))?;
+/// let records = norm::split(&header, &record)?;
+///
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[0].to_string(), "sq0\t1\t.\tA\tC\t.\t.\tAC=1");
+/// assert_eq!(records[1].to_string(), "sq0\t1\t.\tA\tG\t.\t.\tAC=2");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn split(header: &vcf::Header, record: &Record) -> io::Result<Vec<Record>> {
+    let alternate_allele_count = record.alternate_bases().len();
+
+    if alternate_allele_count <= 1 {
+        return Ok(vec![record.clone()]);
+    }
+
+    let allele_count = alternate_allele_count + 1;
+    let mut records = Vec::with_capacity(alternate_allele_count);
+
+    for (i, allele) in record.alternate_bases().iter().enumerate() {
+        let k = i + 1;
+        let mut split_record = record.clone();
+
+        *split_record.alternate_bases_mut() = AlternateBases::from(vec![allele.clone()]);
+
+        for (key, value) in split_record.info_mut().as_mut().iter_mut() {
+            let info = match header.infos().get(key) {
+                Some(info) => info,
+                None => continue,
+            };
+
+            if let Some(v) = value {
+                let elements = split_elements(v);
+
+                if let Some(indices) =
+                    subset_indices(info.number(), allele_count, elements.len(), k)
+                {
+                    let s = select(&elements, &indices);
+                    *v = InfoValue::from_str_info(&s, info)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                }
+            }
+        }
+
+        let genotypes = record.genotypes();
+        let keys = genotypes.keys();
+
+        let values = genotypes
+            .values()
+            .map(|sample| {
+                keys.iter()
+                    .zip(sample.values())
+                    .map(|(key, value)| {
+                        let value = match value {
+                            Some(value) => value,
+                            None => return Ok(None),
+                        };
+
+                        if key == &key::GENOTYPE {
+                            return Ok(Some(SampleValue::String(split_genotype(
+                                &value.to_string(),
+                                k,
+                            ))));
+                        }
+
+                        let format = match header.formats().get(key) {
+                            Some(format) => format,
+                            None => return Ok(Some(value.clone())),
+                        };
+
+                        let elements = split_elements(value);
+
+                        match subset_indices(format.number(), allele_count, elements.len(), k) {
+                            Some(indices) => {
+                                let s = select(&elements, &indices);
+                                parse_sample_value(&s, format)
+                            }
+                            None => Ok(Some(value.clone())),
+                        }
+                    })
+                    .collect::<io::Result<Vec<_>>>()
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        *split_record.genotypes_mut() = Genotypes::new(keys.clone(), values);
+
+        records.push(split_record);
+    }
+
+    Ok(records)
+}
+
+/// Joins records at the same site into a multiallelic record.
+///
+/// The records must have the same chromosome, position, reference bases, and number of samples.
+/// The alternate alleles are merged, and info and genotype fields with a number of alternate
+/// alleles (`A`), reference and alternate alleles (`R`), or genotypes (`G`), as defined in the
+/// header, are combined. Genotype (`GT`) alleles are remapped to the merged alternate alleles.
+/// Other fields are taken from the first record that has them.
+///
+/// This is the inverse of [`split`].
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, record::Position};
+/// use noodles_util::variant::norm;
+///
+/// let header: vcf::Header = "##fileformat=VCFv4.3
+/// ###INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// "
+/// .parse()?;
+///
+/// let records = [
+///     vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\t.\tAC=1"))?,
+///     vcf::Record::try_from((&header, "sq0\t1\t.\tA\tG\t.\t.\tAC=2"))?,
+/// ];
+///
+/// let record = norm::join(&header, &records)?;
+/// assert_eq!(record.to_string(), "sq0\t1\t.\tA\tC,G\t.\t.\tAC=1,2");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn join(header: &vcf::Header, records: &[Record]) -> io::Result<Record> {
+    let first = records
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records to join"))?;
+
+    let sample_count = first.genotypes().values().count();
+
+    for record in &records[1..] {
+        if record.chromosome() != first.chromosome()
+            || record.position() != first.position()
+            || record.reference_bases() != first.reference_bases()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "records are not at the same site",
+            ));
+        }
+
+        if record.genotypes().values().count() != sample_count {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "records have different numbers of samples",
+            ));
+        }
+    }
+
+    // The merged allele index of each record allele, including the reference allele.
+    let mut alternate_bases: Vec<Allele> = Vec::new();
+    let mut allele_maps = Vec::with_capacity(records.len());
+
+    for record in records {
+        let mut allele_map = vec![0];
+
+        for allele in record.alternate_bases().iter() {
+            let i = match alternate_bases.iter().position(|a| a == allele) {
+                Some(i) => i,
+                None => {
+                    alternate_bases.push(allele.clone());
+                    alternate_bases.len() - 1
+                }
+            };
+
+            allele_map.push(i + 1);
+        }
+
+        allele_maps.push(allele_map);
+    }
+
+    let allele_count = alternate_bases.len() + 1;
+
+    let mut joined_record = first.clone();
+    *joined_record.alternate_bases_mut() = AlternateBases::from(alternate_bases);
+
+    for record in &records[1..] {
+        for id in record.ids().iter() {
+            joined_record.ids_mut().insert(id.clone());
+        }
+    }
+
+    let mut info = vcf::record::Info::default();
+
+    for record in records {
+        for (key, value) in record.info().as_ref() {
+            if info.get(key).is_some() {
+                continue;
+            }
+
+            let value = match (header.infos().get(key), value) {
+                (Some(definition), Some(_)) => {
+                    let values: Vec<_> = records
+                        .iter()
+                        .map(|r| r.info().get(key).flatten().map(split_elements))
+                        .collect();
+
+                    match join_elements(definition.number(), allele_count, &allele_maps, &values) {
+                        Some(s) => Some(
+                            InfoValue::from_str_info(&s, definition)
+                                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                        ),
+                        None => value.clone(),
+                    }
+                }
+                _ => value.clone(),
+            };
+
+            info.insert(key.clone(), value);
+        }
+    }
+
+    *joined_record.info_mut() = info;
+
+    let mut keys = Vec::new();
+
+    for record in records {
+        for key in record.genotypes().keys().iter() {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+    }
+
+    let mut values = Vec::with_capacity(sample_count);
+
+    for i in 0..sample_count {
+        let samples: Vec<_> = records
+            .iter()
+            .map(|record| record.genotypes().get_index(i))
+            .collect();
+
+        let mut sample_values = Vec::with_capacity(keys.len());
+
+        for key in &keys {
+            let sample_record_values: Vec<_> = samples
+                .iter()
+                .map(|sample| sample.as_ref().and_then(|s| s.get(key)).flatten())
+                .collect();
+
+            let value = if key == &key::GENOTYPE {
+                join_genotypes(&allele_maps, &sample_record_values).map(SampleValue::String)
+            } else {
+                let first_value = sample_record_values.iter().flatten().next().copied();
+
+                match header.formats().get(key) {
+                    Some(format) => {
+                        let elements: Vec<_> = sample_record_values
+                            .iter()
+                            .map(|value| value.map(split_elements))
+                            .collect();
+
+                        match join_elements(format.number(), allele_count, &allele_maps, &elements)
+                        {
+                            Some(s) => parse_sample_value(&s, format)?,
+                            None => first_value.cloned(),
+                        }
+                    }
+                    None => first_value.cloned(),
+                }
+            };
+
+            sample_values.push(value);
+        }
+
+        values.push(sample_values);
+    }
+
+    let keys = Keys::try_from(keys).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    *joined_record.genotypes_mut() = Genotypes::new(keys, values);
+
+    Ok(joined_record)
+}
+
+fn get_base(sequence: &fasta::record::Sequence, position: usize) -> io::Result<Base> {
+    let base = Position::new(position)
+        .and_then(|p| sequence.get(p))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "position is outside of the reference sequence",
+            )
+        })?;
+
+    // Ambiguous reference bases are treated as any base.
+    Ok(Base::try_from(char::from(base.to_ascii_uppercase())).unwrap_or(Base::N))
+}
+
+// Trims and shifts alleles left, returning the new start position.
+fn normalize_alleles(
+    reference_sequence: &fasta::record::Sequence,
+    mut start: usize,
+    alleles: &mut [Vec<Base>],
+) -> io::Result<usize> {
+    // Identical alleles have no difference to align on.
+    if alleles[1..].iter().all(|allele| allele == &alleles[0]) {
+        return Ok(start);
+    }
+
+    loop {
+        let last_base = alleles[0].last().copied();
+        let is_trimmable = last_base.is_some()
+            && alleles
+                .iter()
+                .all(|allele| allele.last().copied() == last_base);
+
+        if !is_trimmable {
+            break;
+        }
+
+        if alleles.iter().any(|allele| allele.len() == 1) {
+            if start == 1 {
+                break;
+            }
+
+            start -= 1;
+            let base = get_base(reference_sequence, start)?;
+
+            for allele in alleles.iter_mut() {
+                allele.insert(0, base);
+            }
+        }
+
+        for allele in alleles.iter_mut() {
+            allele.pop();
+        }
+    }
+
+    loop {
+        let first_base = alleles[0].first().copied();
+        let is_trimmable = alleles
+            .iter()
+            .all(|allele| allele.len() >= 2 && allele.first().copied() == first_base);
+
+        if !is_trimmable {
+            break;
+        }
+
+        for allele in alleles.iter_mut() {
+            allele.remove(0);
+        }
+
+        start += 1;
+    }
+
+    Ok(start)
+}
+
+fn split_elements<V>(value: &V) -> Vec<String>
+where
+    V: fmt::Display,
+{
+    value
+        .to_string()
+        .split(DELIMITER)
+        .map(String::from)
+        .collect()
+}
+
+fn select(elements: &[String], indices: &[usize]) -> String {
+    indices
+        .iter()
+        .map(|&i| elements.get(i).map(|s| s.as_str()).unwrap_or(MISSING))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Returns the indices of the values of a field for the alternate allele `k`.
+fn subset_indices(
+    number: Number,
+    allele_count: usize,
+    value_count: usize,
+    k: usize,
+) -> Option<Vec<usize>> {
+    match number {
+        Number::A => Some(vec![k - 1]),
+        Number::R => Some(vec![0, k]),
+        Number::G => {
+            if value_count == allele_count {
+                Some(vec![0, k])
+            } else {
+                Some(vec![
+                    genotype_index(0, 0),
+                    genotype_index(0, k),
+                    genotype_index(k, k),
+                ])
+            }
+        }
+        _ => None,
+    }
+}
+
+// Returns the index of a diploid genotype in the VCF genotype ordering.
+fn genotype_index(a: usize, b: usize) -> usize {
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    b * (b + 1) / 2 + a
+}
+
+fn join_elements(
+    number: Number,
+    allele_count: usize,
+    allele_maps: &[Vec<usize>],
+    values: &[Option<Vec<String>>],
+) -> Option<String> {
+    let mut elements = match number {
+        Number::A => vec![None; allele_count - 1],
+        Number::R => vec![None; allele_count],
+        Number::G => {
+            // The ploidy is inferred from the first value, with haploid having one value per
+            // allele.
+            let (value, allele_map) = values
+                .iter()
+                .zip(allele_maps)
+                .find_map(|(v, m)| v.as_ref().map(|v| (v, m)))?;
+
+            if value.len() == allele_map.len() {
+                vec![None; allele_count]
+            } else {
+                vec![None; genotype_index(allele_count - 1, allele_count - 1) + 1]
+            }
+        }
+        _ => return None,
+    };
+
+    let is_haploid = elements.len() == allele_count;
+
+    for (value, allele_map) in values.iter().zip(allele_maps) {
+        let value = match value {
+            Some(value) => value,
+            None => continue,
+        };
+
+        for (i, element) in value.iter().enumerate() {
+            let j = match number {
+                Number::A => allele_map.get(i + 1).map(|&m| m - 1),
+                Number::R => allele_map.get(i).copied(),
+                Number::G if is_haploid => allele_map.get(i).copied(),
+                Number::G => genotype_alleles(i)
+                    .and_then(|(a, b)| Some((*allele_map.get(a)?, *allele_map.get(b)?)))
+                    .map(|(a, b)| genotype_index(a, b)),
+                _ => None,
+            };
+
+            if let Some(slot) = j.and_then(|j| elements.get_mut(j)) {
+                if slot.is_none() {
+                    *slot = Some(element.as_str());
+                }
+            }
+        }
+    }
+
+    Some(
+        elements
+            .into_iter()
+            .map(|element| element.unwrap_or(MISSING))
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+// Returns the alleles of a diploid genotype index.
+fn genotype_alleles(i: usize) -> Option<(usize, usize)> {
+    (0..=i).find_map(|b| {
+        let start = genotype_index(0, b);
+
+        if i < start + b + 1 {
+            Some((i - start, b))
+        } else {
+            None
+        }
+    })
+}
+
+fn parse_sample_value(
+    s: &str,
+    format: &vcf::header::record::value::Map<vcf::header::record::value::map::Format>,
+) -> io::Result<Option<SampleValue>> {
+    if s.split(DELIMITER).all(|t| t == MISSING) {
+        return Ok(None);
+    }
+
+    SampleValue::from_str_format(s, format)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn split_genotype_alleles(s: &str) -> (Vec<&str>, Vec<char>) {
+    let mut alleles = Vec::new();
+    let mut separators = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        if c == '/' || c == '|' {
+            alleles.push(&s[start..i]);
+            separators.push(c);
+            start = i + c.len_utf8();
+        }
+    }
+
+    alleles.push(&s[start..]);
+
+    (alleles, separators)
+}
+
+fn build_genotype<S>(alleles: &[S], separators: &[char]) -> String
+where
+    S: AsRef<str>,
+{
+    let mut s = String::new();
+
+    for (i, allele) in alleles.iter().enumerate() {
+        if let Some(separator) = i.checked_sub(1).and_then(|j| separators.get(j)) {
+            s.push(*separator);
+        }
+
+        s.push_str(allele.as_ref());
+    }
+
+    s
+}
+
+fn split_genotype(s: &str, k: usize) -> String {
+    let (alleles, separators) = split_genotype_alleles(s);
+
+    let alleles: Vec<_> = alleles
+        .into_iter()
+        .map(|allele| match allele.parse::<usize>() {
+            Ok(i) if i == k => "1",
+            Ok(_) => "0",
+            Err(_) => allele,
+        })
+        .collect();
+
+    build_genotype(&alleles, &separators)
+}
+
+fn join_genotypes(allele_maps: &[Vec<usize>], values: &[Option<&SampleValue>]) -> Option<String> {
+    let mut joined: Option<(Vec<String>, Vec<char>)> = None;
+
+    for (value, allele_map) in values.iter().zip(allele_maps) {
+        let s = match value {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+
+        let (alleles, separators) = split_genotype_alleles(&s);
+
+        let alleles: Vec<_> = alleles
+            .into_iter()
+            .map(|allele| match allele.parse::<usize>() {
+                Ok(i) => allele_map
+                    .get(i)
+                    .map(|j| j.to_string())
+                    .unwrap_or_else(|| MISSING.into()),
+                Err(_) => allele.into(),
+            })
+            .collect();
+
+        match joined.as_mut() {
+            Some((joined_alleles, _)) => {
+                // A reference or missing allele is replaced by an alternate allele.
+                for (joined_allele, allele) in joined_alleles.iter_mut().zip(alleles) {
+                    if matches!(joined_allele.as_str(), "0" | MISSING)
+                        && !matches!(allele.as_str(), "0" | MISSING)
+                    {
+                        *joined_allele = allele;
+                    }
+                }
+            }
+            None => joined = Some((alleles, separators)),
+        }
+    }
+
+    joined.map(|(alleles, separators)| build_genotype(&alleles, &separators))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_alleles() -> Result<(), Box<dyn std::error::Error>> {
+        fn t(
+            reference_sequence: &[u8],
+            start: usize,
+            alleles: &[&str],
+        ) -> Result<(usize, Vec<String>), Box<dyn std::error::Error>> {
+            let reference_sequence = fasta::record::Sequence::from(reference_sequence.to_vec());
+
+            let mut alleles: Vec<Vec<Base>> = alleles
+                .iter()
+                .map(|s| s.chars().map(Base::try_from).collect())
+                .collect::<Result<_, _>>()?;
+
+            let position = normalize_alleles(&reference_sequence, start, &mut alleles)?;
+
+            let alleles = alleles
+                .into_iter()
+                .map(|allele| allele.into_iter().map(char::from).collect())
+                .collect();
+
+            Ok((position, alleles))
+        }
+
+        // SNV
+        assert_eq!(
+            t(b"ACGT", 2, &["C", "T"])?,
+            (2, vec!["C".into(), "T".into()])
+        );
+
+        // MNV with shared suffix
+        assert_eq!(
+            t(b"ACGT", 2, &["CG", "TG"])?,
+            (2, vec!["C".into(), "T".into()])
+        );
+
+        // deletion in a repeat
+        assert_eq!(
+            t(b"GCACACAT", 5, &["ACA", "A"])?,
+            (1, vec!["GCA".into(), "G".into()])
+        );
+
+        // insertion in a homopolymer
+        assert_eq!(
+            t(b"GAAAT", 4, &["A", "AA"])?,
+            (1, vec!["G".into(), "GA".into()])
+        );
+
+        // shared prefix
+        assert_eq!(
+            t(b"ACGTT", 1, &["ACGT", "ACT"])?,
+            (2, vec!["CG".into(), "C".into()])
+        );
+
+        // deletion at the start of the reference sequence
+        assert_eq!(
+            t(b"AAT", 2, &["AT", "T"])?,
+            (1, vec!["AA".into(), "A".into()])
+        );
+
+        // identical alleles
+        assert_eq!(
+            t(b"GAAAT", 4, &["A", "A"])?,
+            (4, vec!["A".into(), "A".into()])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_left_align() -> Result<(), Box<dyn std::error::Error>> {
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"GAAAT".to_vec()),
+        )]);
+
+        let build_record = |reference_bases: &str, alternate_bases: &str| {
+            Record::builder()
+                .set_chromosome("sq0".parse()?)
+                .set_position(vcf::record::Position::from(4))
+                .set_reference_bases(reference_bases.parse()?)
+                .set_alternate_bases(alternate_bases.parse()?)
+                .build()
+                .map_err(Box::<dyn std::error::Error>::from)
+        };
+
+        let mut record = build_record("A", "AA")?;
+        assert!(left_align(&repository, &mut record)?);
+        assert_eq!(usize::from(record.position()), 1);
+
+        let mut record = build_record("A", "T")?;
+        assert!(!left_align(&repository, &mut record)?);
+
+        let mut record = build_record("A", "<DEL>")?;
+        assert!(!left_align(&repository, &mut record)?);
+
+        let mut record = build_record("A", "A")?;
+        assert!(!left_align(&repository, &mut record)?);
+        assert_eq!(usize::from(record.position()), 4);
+
+        let mut record = build_record("C", "CC")?;
+        assert!(matches!(
+            left_align(&repository, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_genotype_alleles() {
+        for b in 0..4 {
+            for a in 0..=b {
+                assert_eq!(genotype_alleles(genotype_index(a, b)), Some((a, b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_genotype() {
+        assert_eq!(split_genotype("0/2", 1), "0/0");
+        assert_eq!(split_genotype("0/2", 2), "0/1");
+        assert_eq!(split_genotype("1|2", 2), "0|1");
+        assert_eq!(split_genotype("./.", 1), "./.");
+        assert_eq!(split_genotype("2", 2), "1");
+    }
+
+    fn build_header() -> Result<vcf::Header, Box<dyn std::error::Error>> {
+        let header = r#"##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description="Allele count in genotypes">
+##INFO=<ID=AD,Number=R,Type=Integer,Description="Total read depth for each allele">
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples">
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description="Read depth for each allele">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description="Phred-scaled genotype likelihoods">
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO	FORMAT	sample0	sample1
+"#
+        .parse()?;
+
+        Ok(header)
+    }
+
+    #[test]
+    fn test_split() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = Record::try_from((&header, "sq0\t1\t.\tA\tC,G\t.\t.\tAC=1,2;AD=5,3,4;DP=12\tGT:AD:PL\t0/1:5,3,0:0,1,2,3,4,5\t1/2:0,3,4:."))?;
+
+        let records = split(&header, &record)?;
+
+        let actual: Vec<_> = records.iter().map(|record| record.to_string()).collect();
+
+        let expected = [
+            "sq0\t1\t.\tA\tC\t.\t.\tAC=1;AD=5,3;DP=12\tGT:AD:PL\t0/1:5,3:0,1,2\t1/0:0,3:.",
+            "sq0\t1\t.\tA\tG\t.\t.\tAC=2;AD=5,4;DP=12\tGT:AD:PL\t0/0:5,0:0,3,5\t0/1:0,4:.",
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_with_biallelic_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let record = Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\t.\tAC=1\tGT\t0/1\t1/1"))?;
+        assert_eq!(split(&header, &record)?, [record]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_join() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = Record::try_from((&header, "sq0\t1\t.\tA\tC,G\t.\t.\tAC=1,2;AD=5,3,4;DP=12\tGT:AD:PL\t0/1:5,3,0:0,1,2,3,4,5\t1/2:0,3,4:."))?;
+
+        let records = split(&header, &record)?;
+        let actual = join(&header, &records)?;
+
+        // The genotype likelihoods of heterozygous alternate alleles are not recoverable.
+        let expected = Record::try_from((&header, "sq0\t1\t.\tA\tC,G\t.\t.\tAC=1,2;AD=5,3,4;DP=12\tGT:AD:PL\t0/1:5,3,0:0,1,2,3,.,5\t1/2:0,3,4:."))?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_different_sites() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let records = [
+            Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\t.\t.\tGT\t0/1\t1/1"))?,
+            Record::try_from((&header, "sq0\t2\t.\tA\tG\t.\t.\t.\tGT\t0/1\t1/1"))?,
+        ];
+
+        assert!(matches!(
+            join(&header, &records),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            join(&header, &[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
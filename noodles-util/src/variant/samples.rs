@@ -0,0 +1,364 @@
+//! Variant sample selection.
+//!
+//! This subsets and reorders the samples of a header and its records, similar to `bcftools view
+//! --samples`.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io;
+
+use noodles_bcf as bcf;
+use noodles_vcf::{
+    self as vcf,
+    record::{info::field::key, info::field::Value, Genotypes},
+};
+
+/// A sample selection.
+///
+/// This is created by calling [`Builder::build`].
+#[derive(Debug)]
+pub struct Selection {
+    header: vcf::Header,
+    sample_indices: Vec<usize>,
+    remove_sites_without_alternate_calls: bool,
+    update_allele_counts: bool,
+}
+
+impl Selection {
+    /// Returns the header with the selected samples.
+    ///
+    /// If allele counts are updated, the header also defines the `AC`, `AN`, and `AF` info
+    /// fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::samples;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = samples::Builder::default()
+    ///     .set_samples(vec![String::from("sample1")])
+    ///     .build(&header)?;
+    ///
+    /// assert_eq!(selection.header().sample_names().len(), 1);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn header(&self) -> &vcf::Header {
+        &self.header
+    }
+
+    /// Returns the indices of the selected samples in the original header, in output order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::samples;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = samples::Builder::default()
+    ///     .set_samples(vec![String::from("sample1"), String::from("sample0")])
+    ///     .build(&header)?;
+    ///
+    /// assert_eq!(selection.sample_indices(), [1, 0]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn sample_indices(&self) -> &[usize] {
+        &self.sample_indices
+    }
+
+    /// Selects the samples of a record.
+    ///
+    /// The record is expected to have the samples of the original header. Its genotypes are
+    /// replaced with those of the selected samples, and, if enabled, the allele counts are
+    /// recomputed from their genotypes (`GT`).
+    ///
+    /// This returns whether the record should be kept, which is `false` only when sites without
+    /// alternate allele calls are removed and none of the selected samples have one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::samples;
+    /// use noodles_vcf::{self as vcf, record::info::field::{key, Value}};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = samples::Builder::default()
+    ///     .set_samples(vec![String::from("sample1")])
+    ///     .set_update_allele_counts(true)
+    ///     .build(&header)?;
+    ///
+    /// let mut record = vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/0\t0/1"))?;
+    /// assert!(selection.apply(&mut record)?);
+    ///
+    /// assert_eq!(record.genotypes().to_string(), "GT\t0/1");
+    /// assert_eq!(
+    ///     record.info().get(&key::ALLELE_COUNT),
+    ///     Some(Some(&Value::IntegerArray(vec![Some(1)]))),
+    /// );
+    /// assert_eq!(
+    ///     record.info().get(&key::TOTAL_ALLELE_COUNT),
+    ///     Some(Some(&Value::Integer(2))),
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn apply(&self, record: &mut vcf::Record) -> io::Result<bool> {
+        if !record.genotypes().is_empty() {
+            *record.genotypes_mut() = select_genotypes(record.genotypes(), &self.sample_indices)?;
+        }
+
+        if !self.update_allele_counts && !self.remove_sites_without_alternate_calls {
+            return Ok(true);
+        }
+
+        let allele_counts = match count_alleles(record)? {
+            Some(allele_counts) => allele_counts,
+            None => return Ok(true),
+        };
+
+        if self.update_allele_counts {
+            update_allele_counts(record, &allele_counts);
+        }
+
+        if self.remove_sites_without_alternate_calls {
+            Ok(allele_counts.iter().skip(1).any(|&n| n > 0))
+        } else {
+            Ok(true)
+        }
+    }
+
+    /// Selects the samples of a BCF record.
+    ///
+    /// The genotypes are subset without being decoded. The info fields are not changed, and the
+    /// record is always kept. To update allele counts or remove sites, convert the record to a
+    /// `vcf::Record` using [`Self::header`] and call [`Self::apply`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf as bcf;
+    /// use noodles_util::variant::samples;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::default();
+    /// let selection = samples::Builder::default().build(&header)?;
+    ///
+    /// let mut record = bcf::lazy::Record::default();
+    /// selection.apply_to_bcf_lazy_record(&mut record)?;
+    /// assert!(record.genotypes().is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn apply_to_bcf_lazy_record(&self, record: &mut bcf::lazy::Record) -> io::Result<()> {
+        if !record.genotypes().is_empty() {
+            *record.genotypes_mut() = record.genotypes().select(&self.sample_indices)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn select_genotypes(genotypes: &Genotypes, sample_indices: &[usize]) -> io::Result<Genotypes> {
+    let mut values = Vec::with_capacity(sample_indices.len());
+
+    for &i in sample_indices {
+        let sample = genotypes.get_index(i).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing sample at index {i}"),
+            )
+        })?;
+
+        values.push(sample.values().to_vec());
+    }
+
+    Ok(Genotypes::new(genotypes.keys().clone(), values))
+}
+
+// Returns the number of calls of each allele, starting with the reference allele, or `None` if
+// the record has no genotypes.
+fn count_alleles(record: &vcf::Record) -> io::Result<Option<Vec<usize>>> {
+    use noodles_vcf::record::genotypes::keys::key::GENOTYPE;
+
+    if !record.genotypes().keys().contains(&GENOTYPE) {
+        return Ok(None);
+    }
+
+    let genotypes = record
+        .genotypes()
+        .genotypes()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let mut allele_counts = vec![0; record.alternate_bases().len() + 1];
+
+    for genotype in genotypes.iter().flatten() {
+        for position in genotype.iter().filter_map(|allele| allele.position()) {
+            let count = allele_counts.get_mut(position).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid allele position: {position}"),
+                )
+            })?;
+
+            *count += 1;
+        }
+    }
+
+    Ok(Some(allele_counts))
+}
+
+fn update_allele_counts(record: &mut vcf::Record, allele_counts: &[usize]) {
+    let total_allele_count: usize = allele_counts.iter().sum();
+    let alternate_allele_counts = &allele_counts[1..];
+
+    let info = record.info_mut();
+
+    info.insert(
+        key::TOTAL_ALLELE_COUNT,
+        Some(Value::Integer(to_i32(total_allele_count))),
+    );
+
+    if alternate_allele_counts.is_empty() {
+        info.as_mut().shift_remove(&key::ALLELE_COUNT);
+        info.as_mut().shift_remove(&key::ALLELE_FREQUENCIES);
+        return;
+    }
+
+    let counts = alternate_allele_counts
+        .iter()
+        .map(|&n| Some(to_i32(n)))
+        .collect();
+
+    info.insert(key::ALLELE_COUNT, Some(Value::IntegerArray(counts)));
+
+    let frequencies = alternate_allele_counts
+        .iter()
+        .map(|&n| {
+            if total_allele_count > 0 {
+                Some(n as f32 / total_allele_count as f32)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    info.insert(
+        key::ALLELE_FREQUENCIES,
+        Some(Value::FloatArray(frequencies)),
+    );
+}
+
+fn to_i32(n: usize) -> i32 {
+    i32::try_from(n).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_header() -> vcf::Header {
+        vcf::Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build()
+    }
+
+    #[test]
+    fn test_apply() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let selection = Builder::default()
+            .set_samples(vec![String::from("sample2"), String::from("sample0")])
+            .set_update_allele_counts(true)
+            .build(&header)?;
+
+        let mut record = vcf::Record::try_from((
+            &header,
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=2,1;AN=6;AF=0.333,0.167;DP=8\tGT:GQ\t0/1:8\t1/2:13\t./2:21",
+        ))?;
+
+        assert!(selection.apply(&mut record)?);
+
+        assert_eq!(record.genotypes().to_string(), "GT:GQ\t./2:21\t0/1:8");
+
+        let info = record.info();
+        assert_eq!(
+            info.get(&key::ALLELE_COUNT),
+            Some(Some(&Value::IntegerArray(vec![Some(1), Some(1)])))
+        );
+        assert_eq!(
+            info.get(&key::TOTAL_ALLELE_COUNT),
+            Some(Some(&Value::Integer(3)))
+        );
+        assert_eq!(
+            info.get(&key::ALLELE_FREQUENCIES),
+            Some(Some(&Value::FloatArray(vec![
+                Some(1.0 / 3.0),
+                Some(1.0 / 3.0)
+            ])))
+        );
+        assert_eq!(info.get(&key::TOTAL_DEPTH), Some(Some(&Value::Integer(8))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_with_remove_sites_without_alternate_calls(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let selection = Builder::default()
+            .set_excluded_samples(vec![String::from("sample1")])
+            .set_remove_sites_without_alternate_calls(true)
+            .build(&header)?;
+
+        let mut record =
+            vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/0\t0/1\t./."))?;
+        assert!(!selection.apply(&mut record)?);
+        assert_eq!(record.genotypes().to_string(), "GT\t0/0\t./.");
+
+        let mut record =
+            vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/0\t0/1\t1/1"))?;
+        assert!(selection.apply(&mut record)?);
+
+        let mut record =
+            vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGQ\t8\t13\t21"))?;
+        assert!(selection.apply(&mut record)?);
+        assert_eq!(record.genotypes().to_string(), "GQ\t8\t21");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_allele_counts() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::default();
+
+        let mut record = vcf::Record::try_from((&header, "sq0\t1\t.\tA\t.\t.\tPASS\tAC=1;AF=0.5"))?;
+        update_allele_counts(&mut record, &[2]);
+        assert_eq!(record.info().to_string(), "AN=2");
+
+        let mut record = vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC\t.\tPASS\t."))?;
+        update_allele_counts(&mut record, &[0, 0]);
+        assert_eq!(record.info().to_string(), "AN=0;AC=0;AF=.");
+
+        Ok(())
+    }
+}
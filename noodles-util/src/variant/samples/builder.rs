@@ -0,0 +1,256 @@
+use std::{collections::HashSet, io};
+
+use noodles_vcf::{
+    self as vcf,
+    header::record::value::{map::Info, Map},
+    record::info::field::key,
+};
+
+use super::Selection;
+
+/// A sample selection builder.
+#[derive(Default)]
+pub struct Builder {
+    samples: Option<Vec<String>>,
+    excluded_samples: Vec<String>,
+    remove_sites_without_alternate_calls: bool,
+    update_allele_counts: bool,
+}
+
+impl Builder {
+    /// Sets the samples to include, in output order.
+    ///
+    /// By default, all samples in the header are included in their original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::samples;
+    ///
+    /// let builder = samples::Builder::default()
+    ///     .set_samples(vec![String::from("sample1"), String::from("sample0")]);
+    /// ```
+    pub fn set_samples(mut self, samples: Vec<String>) -> Self {
+        self.samples = Some(samples);
+        self
+    }
+
+    /// Sets the samples to exclude.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::samples;
+    /// let builder = samples::Builder::default().set_excluded_samples(vec![String::from("sample0")]);
+    /// ```
+    pub fn set_excluded_samples(mut self, excluded_samples: Vec<String>) -> Self {
+        self.excluded_samples = excluded_samples;
+        self
+    }
+
+    /// Sets whether to remove sites where no selected sample has an alternate allele call.
+    ///
+    /// By default, all sites are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::samples;
+    /// let builder = samples::Builder::default().set_remove_sites_without_alternate_calls(true);
+    /// ```
+    pub fn set_remove_sites_without_alternate_calls(
+        mut self,
+        remove_sites_without_alternate_calls: bool,
+    ) -> Self {
+        self.remove_sites_without_alternate_calls = remove_sites_without_alternate_calls;
+        self
+    }
+
+    /// Sets whether to recompute the allele count (`AC`), total allele count (`AN`), and allele
+    /// frequency (`AF`) info fields from the selected samples.
+    ///
+    /// By default, info fields are not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::samples;
+    /// let builder = samples::Builder::default().set_update_allele_counts(true);
+    /// ```
+    pub fn set_update_allele_counts(mut self, update_allele_counts: bool) -> Self {
+        self.update_allele_counts = update_allele_counts;
+        self
+    }
+
+    /// Builds a sample selection from a header.
+    ///
+    /// This fails if a sample is not in the header or is included more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::samples;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = samples::Builder::default()
+    ///     .set_excluded_samples(vec![String::from("sample0")])
+    ///     .build(&header)?;
+    ///
+    /// assert_eq!(selection.sample_indices(), [1]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build(self, header: &vcf::Header) -> io::Result<Selection> {
+        let sample_names = header.sample_names();
+
+        let mut sample_indices = match self.samples {
+            Some(samples) => {
+                let mut sample_indices = Vec::with_capacity(samples.len());
+                let mut visited_sample_indices = HashSet::with_capacity(samples.len());
+
+                for sample in &samples {
+                    let i = get_sample_index(header, sample)?;
+
+                    if !visited_sample_indices.insert(i) {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("duplicate sample: {sample}"),
+                        ));
+                    }
+
+                    sample_indices.push(i);
+                }
+
+                sample_indices
+            }
+            None => (0..sample_names.len()).collect(),
+        };
+
+        let mut excluded_sample_indices = HashSet::with_capacity(self.excluded_samples.len());
+
+        for sample in &self.excluded_samples {
+            let i = get_sample_index(header, sample)?;
+            excluded_sample_indices.insert(i);
+        }
+
+        sample_indices.retain(|i| !excluded_sample_indices.contains(i));
+
+        let mut selected_header = header.clone();
+
+        *selected_header.sample_names_mut() = sample_indices
+            .iter()
+            .filter_map(|&i| sample_names.get_index(i))
+            .cloned()
+            .collect();
+
+        if self.update_allele_counts {
+            let infos = selected_header.infos_mut();
+
+            for key in [
+                key::ALLELE_COUNT,
+                key::TOTAL_ALLELE_COUNT,
+                key::ALLELE_FREQUENCIES,
+            ] {
+                infos
+                    .entry(key.clone())
+                    .or_insert_with(|| Map::<Info>::from(&key));
+            }
+        }
+
+        Ok(Selection {
+            header: selected_header,
+            sample_indices,
+            remove_sites_without_alternate_calls: self.remove_sites_without_alternate_calls,
+            update_allele_counts: self.update_allele_counts,
+        })
+    }
+}
+
+fn get_sample_index(header: &vcf::Header, sample: &str) -> io::Result<usize> {
+    header.sample_names().get_index_of(sample).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("missing sample in header: {sample}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_header() -> vcf::Header {
+        vcf::Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build()
+    }
+
+    #[test]
+    fn test_build() -> io::Result<()> {
+        let header = build_header();
+
+        let selection = Builder::default().build(&header)?;
+        assert_eq!(selection.sample_indices(), [0, 1, 2]);
+        assert_eq!(selection.header(), &header);
+
+        let selection = Builder::default()
+            .set_samples(vec![
+                String::from("sample2"),
+                String::from("sample0"),
+                String::from("sample1"),
+            ])
+            .set_excluded_samples(vec![String::from("sample1")])
+            .set_update_allele_counts(true)
+            .build(&header)?;
+
+        assert_eq!(selection.sample_indices(), [2, 0]);
+
+        let sample_names: Vec<_> = selection
+            .header()
+            .sample_names()
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        assert_eq!(sample_names, ["sample2", "sample0"]);
+
+        let infos = selection.header().infos();
+        assert!(infos.contains_key(&key::ALLELE_COUNT));
+        assert!(infos.contains_key(&key::TOTAL_ALLELE_COUNT));
+        assert!(infos.contains_key(&key::ALLELE_FREQUENCIES));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_invalid_samples() {
+        let header = build_header();
+
+        assert!(matches!(
+            Builder::default()
+                .set_samples(vec![String::from("sample3")])
+                .build(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            Builder::default()
+                .set_samples(vec![String::from("sample0"), String::from("sample0")])
+                .build(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            Builder::default()
+                .set_excluded_samples(vec![String::from("sample3")])
+                .build(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+//! Compresses a VCF file using a multithreaded BGZF writer and builds a tabix index while writing.
+//!
+//! This writes the bgzipped VCF to `<dst>` and the tabix index to `<dst>.tbi`.
+//!
+//! The result is similar to the output of `bgzip --threads <worker-count> --stdout <src> > <dst>`
+//! followed by `tabix --preset vcf <dst>`.
+
+use std::{
+    collections::VecDeque,
+    env,
+    fs::File,
+    io::{self, BufReader},
+    num::NonZeroUsize,
+    thread,
+};
+
+use noodles_bgzf::{self as bgzf, multithreaded_writer::DeferredVirtualPosition};
+use noodles_core::Position;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_tabix as tabix;
+use noodles_vcf as vcf;
+
+struct PendingRecord {
+    reference_sequence_name: String,
+    start: Position,
+    end: Position,
+    start_position: DeferredVirtualPosition,
+    end_position: DeferredVirtualPosition,
+}
+
+fn add_resolved_records(
+    writer: &bgzf::MultithreadedWriter,
+    indexer: &mut tabix::index::Indexer,
+    pending_records: &mut VecDeque<PendingRecord>,
+) -> io::Result<()> {
+    while let Some(record) = pending_records.front() {
+        let end_position = match writer.resolve_virtual_position(record.end_position) {
+            Some(position) => position,
+            None => break,
+        };
+
+        // SAFETY: `start_position` <= `end_position`.
+        let start_position = writer
+            .resolve_virtual_position(record.start_position)
+            .unwrap();
+
+        let chunk = Chunk::new(start_position, end_position);
+
+        indexer.add_record(
+            &record.reference_sequence_name,
+            record.start,
+            record.end,
+            chunk,
+        )?;
+
+        pending_records.pop_front();
+    }
+
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let dst = args.next().expect("missing dst");
+    let worker_count = args
+        .next()
+        .map(|s| s.parse().expect("invalid worker_count"))
+        .unwrap_or_else(|| {
+            thread::available_parallelism().unwrap_or_else(|_| NonZeroUsize::new(1).unwrap())
+        });
+
+    let mut reader = File::open(src).map(BufReader::new).map(vcf::Reader::new)?;
+    let header = reader.read_header()?;
+
+    let mut writer = File::create(&dst)
+        .map(|file| {
+            bgzf::multithreaded_writer::Builder::default()
+                .set_worker_count(worker_count)
+                .build_with_writer(file)
+        })
+        .map(vcf::Writer::new)?;
+
+    writer.write_header(&header)?;
+
+    let mut indexer = tabix::index::Indexer::default();
+    indexer.set_header(csi::index::header::Builder::vcf().build());
+
+    let mut pending_records = VecDeque::new();
+    let mut record = vcf::Record::default();
+
+    while reader.read_record(&header, &mut record)? != 0 {
+        let start_position = writer.get_ref().virtual_position();
+        writer.write_record(&header, &record)?;
+        let end_position = writer.get_ref().virtual_position();
+
+        let start = Position::try_from(usize::from(record.position()))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let end = record
+            .end()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            .and_then(|position| {
+                Position::try_from(usize::from(position))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?;
+
+        pending_records.push_back(PendingRecord {
+            reference_sequence_name: record.chromosome().to_string(),
+            start,
+            end,
+            start_position,
+            end_position,
+        });
+
+        add_resolved_records(writer.get_ref(), &mut indexer, &mut pending_records)?;
+    }
+
+    let mut inner = writer.into_inner();
+    inner.finish()?;
+
+    add_resolved_records(&inner, &mut indexer, &mut pending_records)?;
+
+    let index = indexer.build();
+    tabix::write(format!("{dst}.tbi"), &index)?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! Validates a VCF file against its header.
+//!
+//! Each diagnostic is printed to stderr. The process exits with an error if any diagnostic is an
+//! error. Pass `--lenient` to report contigs, filters, and fields missing from the header as
+//! warnings.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+    process,
+};
+
+use noodles_vcf::{
+    self as vcf,
+    validator::{Mode, Validator},
+};
+
+fn main() -> io::Result<()> {
+    let mut mode = Mode::Strict;
+    let mut src = None;
+
+    for arg in env::args().skip(1) {
+        if arg == "--lenient" {
+            mode = Mode::Lenient;
+        } else {
+            src = Some(arg);
+        }
+    }
+
+    let src = src.expect("missing src");
+
+    let mut reader = File::open(src).map(BufReader::new).map(vcf::Reader::new)?;
+
+    let mut validator = Validator::new(mode);
+    let diagnostics = validator.validate(&mut reader)?;
+
+    for diagnostic in &diagnostics {
+        eprintln!("{diagnostic}");
+    }
+
+    if diagnostics.iter().any(|diagnostic| diagnostic.is_error()) {
+        process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,331 @@
+//! Lazily-evaluated VCF record and fields.
+
+mod genotypes;
+mod info;
+
+pub use self::{
+    genotypes::{Genotypes, Sample},
+    info::Info,
+};
+
+use std::{fmt, io, ops::Range};
+
+use crate::Header;
+
+const DELIMITER: char = '\t';
+const FIXED_FIELD_COUNT: usize = 8;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Bounds {
+    // The end positions of the fixed fields, i.e., CHROM, POS, ID, REF, ALT, QUAL, FILTER, and
+    // INFO.
+    ends: [usize; FIXED_FIELD_COUNT],
+}
+
+impl Bounds {
+    fn field_range(&self, i: usize) -> Range<usize> {
+        let start = match i.checked_sub(1) {
+            Some(j) => self.ends[j] + 1,
+            None => 0,
+        };
+
+        start..self.ends[i]
+    }
+}
+
+/// An immutable, lazily-evalulated VCF record.
+///
+/// This holds the raw record line. Fields are returned as raw strings and are _not_ memoized.
+/// They can be parsed individually as needed, e.g., `record.position().parse::<Position>()`.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Record {
+    pub(crate) buf: String,
+    bounds: Bounds,
+}
+
+impl Record {
+    /// Returns the raw chromosome (`CHROM`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.chromosome(), ".");
+    /// ```
+    pub fn chromosome(&self) -> &str {
+        self.field(0)
+    }
+
+    /// Returns the raw position (`POS`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::Position};
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.position(), "1");
+    /// assert_eq!(record.position().parse::<Position>().map(usize::from), Ok(1));
+    /// ```
+    pub fn position(&self) -> &str {
+        self.field(1)
+    }
+
+    /// Returns the raw IDs (`ID`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.ids(), ".");
+    /// ```
+    pub fn ids(&self) -> &str {
+        self.field(2)
+    }
+
+    /// Returns the raw reference bases (`REF`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.reference_bases(), "N");
+    /// ```
+    pub fn reference_bases(&self) -> &str {
+        self.field(3)
+    }
+
+    /// Returns the raw alternate bases (`ALT`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.alternate_bases(), ".");
+    /// ```
+    pub fn alternate_bases(&self) -> &str {
+        self.field(4)
+    }
+
+    /// Returns the raw quality score (`QUAL`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.quality_score(), ".");
+    /// ```
+    pub fn quality_score(&self) -> &str {
+        self.field(5)
+    }
+
+    /// Returns the raw filters (`FILTER`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.filters(), ".");
+    /// ```
+    pub fn filters(&self) -> &str {
+        self.field(6)
+    }
+
+    /// Returns the info (`INFO`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.info().is_empty());
+    /// ```
+    pub fn info(&self) -> Info<'_> {
+        Info::new(self.field(7))
+    }
+
+    /// Returns the genotypes, i.e., the format (`FORMAT`) and sample columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.genotypes().is_empty());
+    /// ```
+    pub fn genotypes(&self) -> Genotypes<'_> {
+        let info_end = self.bounds.ends[FIXED_FIELD_COUNT - 1];
+        let src = self.buf.get(info_end + 1..).unwrap_or_default();
+        Genotypes::new(src)
+    }
+
+    /// Converts this lazy record to a VCF record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::default();
+    ///
+    /// let actual = record.try_into_vcf_record(&header)?;
+    /// assert_eq!(actual, vcf::Record::default());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_into_vcf_record(&self, header: &Header) -> io::Result<crate::Record> {
+        use crate::reader::parse_record;
+
+        let mut record = crate::Record::default();
+
+        parse_record(&self.buf, header, &mut record)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(record)
+    }
+
+    fn field(&self, i: usize) -> &str {
+        &self.buf[self.bounds.field_range(i)]
+    }
+
+    // On failure, the record is reset so that the bounds remain valid for the buffer.
+    pub(crate) fn index(&mut self) -> io::Result<()> {
+        match index(&self.buf) {
+            Ok(bounds) => {
+                self.bounds = bounds;
+                Ok(())
+            }
+            Err(e) => {
+                *self = Self::default();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("chromosome", &self.chromosome())
+            .field("position", &self.position())
+            .field("ids", &self.ids())
+            .field("reference_bases", &self.reference_bases())
+            .field("alternate_bases", &self.alternate_bases())
+            .field("quality_score", &self.quality_score())
+            .field("filters", &self.filters())
+            .field("info", &self.info())
+            .field("genotypes", &self.genotypes())
+            .finish()
+    }
+}
+
+impl AsRef<str> for Record {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl TryFrom<String> for Record {
+    type Error = io::Error;
+
+    fn try_from(buf: String) -> Result<Self, Self::Error> {
+        let bounds = index(&buf)?;
+        Ok(Self { buf, bounds })
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        // SAFETY: The default line has all fixed fields.
+        Self::try_from(String::from(".\t1\t.\tN\t.\t.\t.\t.")).unwrap()
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+fn index(buf: &str) -> io::Result<Bounds> {
+    let mut bounds = Bounds {
+        ends: [0; FIXED_FIELD_COUNT],
+    };
+
+    let mut delimiter_positions = buf.match_indices(DELIMITER).map(|(i, _)| i);
+
+    for end in &mut bounds.ends[..FIXED_FIELD_COUNT - 1] {
+        *end = delimiter_positions
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing field"))?;
+    }
+
+    // INFO is the last field when there are no genotypes.
+    bounds.ends[FIXED_FIELD_COUNT - 1] = delimiter_positions.next().unwrap_or(buf.len());
+
+    Ok(bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_string_for_record() -> io::Result<()> {
+        let record = Record::try_from(String::from(
+            "sq0\t8\tid0\tA\tC,G\t13.0\tPASS\tDP=21;H2\tGT:GQ\t0|1:13\t1/1",
+        ))?;
+
+        assert_eq!(record.chromosome(), "sq0");
+        assert_eq!(record.position(), "8");
+        assert_eq!(record.ids(), "id0");
+        assert_eq!(record.reference_bases(), "A");
+        assert_eq!(record.alternate_bases(), "C,G");
+        assert_eq!(record.quality_score(), "13.0");
+        assert_eq!(record.filters(), "PASS");
+        assert_eq!(record.info().as_ref(), "DP=21;H2");
+        assert_eq!(record.genotypes().as_ref(), "GT:GQ\t0|1:13\t1/1");
+
+        let record = Record::try_from(String::from("sq0\t8\t.\tA\t.\t.\t.\tDP=21"))?;
+        assert_eq!(record.info().as_ref(), "DP=21");
+        assert!(record.genotypes().is_empty());
+
+        let result = Record::try_from(String::from("sq0\t8\t.\tA\t.\t.\t."));
+        assert!(matches!(result, Err(e) if e.kind() == io::ErrorKind::InvalidData));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_vcf_record() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::Position;
+
+        let header = "##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+
+        let record = Record::try_from(String::from("sq0\t8\t.\tA\tC\t.\tPASS\tDP=21"))?;
+
+        let actual = record.try_into_vcf_record(&header)?;
+
+        let expected = crate::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(8))
+            .set_reference_bases("A".parse()?)
+            .set_alternate_bases("C".parse()?)
+            .set_filters("PASS".parse()?)
+            .set_info("DP=21".parse()?)
+            .build()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
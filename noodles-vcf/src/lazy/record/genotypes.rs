@@ -0,0 +1,202 @@
+const DELIMITER: char = '\t';
+const KEY_DELIMITER: char = ':';
+
+/// Raw VCF record genotypes.
+///
+/// This is the format (`FORMAT`) column and the sample columns. Sample columns are only split
+/// when they are accessed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Genotypes<'a>(&'a str);
+
+impl<'a> Genotypes<'a> {
+    pub(super) fn new(src: &'a str) -> Self {
+        Self(src)
+    }
+
+    /// Returns whether there are any genotypes, i.e., a format column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.genotypes().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the raw keys, i.e., the format (`FORMAT`) column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\t.\t.\tGT:GQ\t0|0:13",
+    /// ))?;
+    ///
+    /// assert_eq!(record.genotypes().keys(), "GT:GQ");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn keys(&self) -> &'a str {
+        self.0.split(DELIMITER).next().unwrap_or_default()
+    }
+
+    /// Returns the number of samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\t.\t.\tGT:GQ\t0|0:13\t0/1:8",
+    /// ))?;
+    ///
+    /// assert_eq!(record.genotypes().len(), 2);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.matches(DELIMITER).count()
+    }
+
+    /// Returns the sample at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\t.\t.\tGT:GQ\t0|0:13\t0/1:8",
+    /// ))?;
+    ///
+    /// let genotypes = record.genotypes();
+    /// let sample = genotypes.get(1).expect("missing sample");
+    /// assert_eq!(sample.as_ref(), "0/1:8");
+    ///
+    /// assert!(genotypes.get(2).is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn get(&self, i: usize) -> Option<Sample<'a>> {
+        self.iter().nth(i)
+    }
+
+    /// Returns an iterator over samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\t.\t.\tGT:GQ\t0|0:13\t0/1:8",
+    /// ))?;
+    ///
+    /// let genotypes: Vec<_> = record
+    ///     .genotypes()
+    ///     .iter()
+    ///     .map(|sample| sample.get("GT"))
+    ///     .collect();
+    ///
+    /// assert_eq!(genotypes, [Some("0|0"), Some("0/1")]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Sample<'a>> {
+        let keys = self.keys();
+        self.0
+            .split(DELIMITER)
+            .skip(1)
+            .map(move |values| Sample::new(keys, values))
+    }
+}
+
+impl<'a> AsRef<str> for Genotypes<'a> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+/// A raw VCF record genotypes sample.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Sample<'a> {
+    keys: &'a str,
+    values: &'a str,
+}
+
+impl<'a> Sample<'a> {
+    fn new(keys: &'a str, values: &'a str) -> Self {
+        Self { keys, values }
+    }
+
+    /// Returns the raw value of the field with the given key.
+    ///
+    /// This returns `None` if the key is not in the format or the sample omits trailing fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\t.\t.\tGT:GQ\t0|0",
+    /// ))?;
+    ///
+    /// let genotypes = record.genotypes();
+    /// let sample = genotypes.get(0).expect("missing sample");
+    ///
+    /// assert_eq!(sample.get("GT"), Some("0|0"));
+    /// assert!(sample.get("GQ").is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        let i = self.keys.split(KEY_DELIMITER).position(|k| k == key)?;
+        self.values.split(KEY_DELIMITER).nth(i)
+    }
+
+    /// Returns an iterator over raw values.
+    pub fn values(&self) -> impl Iterator<Item = &'a str> {
+        self.values.split(KEY_DELIMITER)
+    }
+}
+
+impl<'a> AsRef<str> for Sample<'a> {
+    fn as_ref(&self) -> &str {
+        self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_len() {
+        assert_eq!(Genotypes::new("").len(), 0);
+        assert_eq!(Genotypes::new("GT").len(), 0);
+        assert_eq!(Genotypes::new("GT\t0|0").len(), 1);
+        assert_eq!(Genotypes::new("GT\t0|0\t1/1").len(), 2);
+    }
+
+    #[test]
+    fn test_iter() {
+        let genotypes = Genotypes::new("GT:GQ:DP\t0|0:13:8\t./.\t1/1:.:3");
+
+        let actual: Vec<_> = genotypes
+            .iter()
+            .map(|sample| (sample.get("GT"), sample.get("DP")))
+            .collect();
+
+        let expected = [
+            (Some("0|0"), Some("8")),
+            (Some("./."), None),
+            (Some("1/1"), Some("3")),
+        ];
+
+        assert_eq!(actual, expected);
+
+        assert_eq!(Genotypes::new("").iter().count(), 0);
+    }
+}
@@ -0,0 +1,98 @@
+const MISSING: &str = ".";
+const DELIMITER: char = ';';
+const SEPARATOR: char = '=';
+
+/// Raw VCF record info.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Info<'a>(&'a str);
+
+impl<'a> Info<'a> {
+    pub(super) fn new(src: &'a str) -> Self {
+        Self(src)
+    }
+
+    /// Returns whether there are any info fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.info().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0, "" | MISSING)
+    }
+
+    /// Returns the raw value of the field with the given key.
+    ///
+    /// This returns `Some(None)` if the field is present but has no value, e.g., a flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from("sq0\t1\t.\tA\t.\t.\t.\tDP=8;H2"))?;
+    /// let info = record.info();
+    ///
+    /// assert_eq!(info.get("DP"), Some(Some("8")));
+    /// assert_eq!(info.get("H2"), Some(None));
+    /// assert!(info.get("NS").is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn get(&self, key: &str) -> Option<Option<&'a str>> {
+        self.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over raw keys and values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from("sq0\t1\t.\tA\t.\t.\t.\tDP=8;H2"))?;
+    /// let fields: Vec<_> = record.info().iter().collect();
+    ///
+    /// assert_eq!(fields, [("DP", Some("8")), ("H2", None)]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, Option<&'a str>)> {
+        let src = if self.is_empty() { "" } else { self.0 };
+
+        src.split(DELIMITER)
+            .filter(|s| !s.is_empty())
+            .map(|s| match s.split_once(SEPARATOR) {
+                Some((k, v)) => (k, Some(v)),
+                None => (s, None),
+            })
+    }
+}
+
+impl<'a> AsRef<str> for Info<'a> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        assert_eq!(Info::new(".").iter().count(), 0);
+        assert_eq!(Info::new("").iter().count(), 0);
+
+        let info = Info::new("NS=2;DP=8;AF=0.333,0.667;DB");
+        let actual: Vec<_> = info.iter().collect();
+        let expected = [
+            ("NS", Some("2")),
+            ("DP", Some("8")),
+            ("AF", Some("0.333,0.667")),
+            ("DB", None),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
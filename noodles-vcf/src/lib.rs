@@ -24,8 +24,10 @@ mod r#async;
 
 pub mod header;
 pub mod indexed_reader;
+pub mod lazy;
 pub mod reader;
 pub mod record;
+pub mod validator;
 mod variant_reader;
 mod variant_writer;
 mod writer;
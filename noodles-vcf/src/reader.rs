@@ -1,13 +1,14 @@
 //! VCF reader and iterators.
 
 mod builder;
-mod header;
+pub(crate) mod header;
+mod lazy_records;
 pub(crate) mod query;
 pub mod record;
 mod records;
 
 pub(crate) use self::record::parse_record;
-pub use self::{builder::Builder, query::Query, records::Records};
+pub use self::{builder::Builder, lazy_records::LazyRecords, query::Query, records::Records};
 
 use std::io::{self, BufRead, Read, Seek};
 
@@ -16,7 +17,7 @@ use noodles_core::Region;
 use noodles_csi as csi;
 
 use self::header::read_header;
-use super::{Header, Record, VariantReader};
+use super::{lazy, Header, Record, VariantReader};
 
 /// A VCF reader.
 ///
@@ -209,6 +210,82 @@ where
     pub fn records<'r, 'h: 'r>(&'r mut self, header: &'h Header) -> Records<'r, 'h, R> {
         Records::new(self, header)
     }
+
+    /// Reads a single record without eagerly parsing its fields.
+    ///
+    /// This reads a line from the underlying stream until a newline is reached and only finds the
+    /// bounds of the fixed fields. No fields are parsed, meaning the record is not necessarily
+    /// valid. However, the record is guaranteed to have at least 8 tab-delimited fields. If the line
+    /// has fewer fields, an error is returned, and the record is reset to its default.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another record.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0, the
+    /// stream reached EOF, and the record is reset to its default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t1\t.\tA\t.\t.\tPASS\t.
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut record = vcf::lazy::Record::default();
+    /// reader.read_lazy_record(&mut record)?;
+    ///
+    /// assert_eq!(record.chromosome(), "sq0");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn read_lazy_record(&mut self, record: &mut lazy::Record) -> io::Result<usize> {
+        record.buf.clear();
+
+        match read_line(&mut self.inner, &mut record.buf) {
+            Ok(0) => {
+                *record = lazy::Record::default();
+                Ok(0)
+            }
+            Ok(n) => {
+                record.index()?;
+                Ok(n)
+            }
+            Err(e) => {
+                *record = lazy::Record::default();
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns an iterator over lazy records starting from the current stream position.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t1\t.\tA\t.\t.\tPASS\t.
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut records = reader.lazy_records();
+    /// assert!(records.next().is_some());
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn lazy_records(&mut self) -> LazyRecords<'_, R> {
+        LazyRecords::new(self)
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -328,7 +405,7 @@ where
 // Reads all bytes until a line feed ('\n') or EOF is reached.
 //
 // The buffer will not include the trailing newline ('\n' or '\r\n').
-fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
+pub(crate) fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
 where
     R: BufRead,
 {
@@ -399,6 +476,55 @@ sq0\t1\t.\tA\t.\t.\tPASS\t.
         Ok(())
     }
 
+    #[test]
+    fn test_read_lazy_record_with_malformed_line() -> io::Result<()> {
+        static DATA: &[u8] = b"\
+##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t1\t.\tA\t.\t.\tPASS\tDP=21;NS=3;AF=0.5
+sq0\t8
+";
+
+        let mut reader = Reader::new(DATA);
+        reader.read_header()?;
+
+        let mut record = lazy::Record::default();
+
+        reader.read_lazy_record(&mut record)?;
+        assert_eq!(record.info().as_ref(), "DP=21;NS=3;AF=0.5");
+
+        assert!(matches!(
+            reader.read_lazy_record(&mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert_eq!(record.chromosome(), ".");
+        assert!(record.info().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_lazy_record_at_eof() -> io::Result<()> {
+        static DATA: &[u8] = b"\
+##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t1\t.\tA\t.\t.\tPASS\tDP=21
+";
+
+        let mut reader = Reader::new(DATA);
+        reader.read_header()?;
+
+        let mut record = lazy::Record::default();
+
+        while reader.read_lazy_record(&mut record)? != 0 {}
+
+        assert_eq!(record.chromosome(), ".");
+        assert!(record.info().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         let mut buf = String::new();
@@ -12,7 +12,7 @@ where
     })
 }
 
-fn read_raw_header<R>(reader: &mut R) -> io::Result<String>
+pub(crate) fn read_raw_header<R>(reader: &mut R) -> io::Result<String>
 where
     R: BufRead,
 {
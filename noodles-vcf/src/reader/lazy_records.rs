@@ -0,0 +1,39 @@
+use std::io::{self, BufRead};
+
+use super::Reader;
+use crate::lazy;
+
+/// An iterator over lazily-evaluated records of a VCF reader.
+///
+/// This is created by calling [`Reader::lazy_records`].
+pub struct LazyRecords<'a, R> {
+    inner: &'a mut Reader<R>,
+    record: lazy::Record,
+}
+
+impl<'a, R> LazyRecords<'a, R>
+where
+    R: BufRead,
+{
+    pub(crate) fn new(inner: &'a mut Reader<R>) -> Self {
+        Self {
+            inner,
+            record: lazy::Record::default(),
+        }
+    }
+}
+
+impl<'a, R> Iterator for LazyRecords<'a, R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<lazy::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.read_lazy_record(&mut self.record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(self.record.clone())),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
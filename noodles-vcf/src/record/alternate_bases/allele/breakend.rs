@@ -0,0 +1,477 @@
+//! VCF record alternate bases allele breakend.
+
+pub mod mate;
+mod orientation;
+
+pub use self::{mate::Mate, orientation::Orientation};
+
+use std::{
+    collections::HashMap,
+    error,
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+use indexmap::IndexSet;
+
+use crate::{
+    record::{
+        info::field::{key, Value},
+        reference_bases::{base, Base},
+    },
+    Record,
+};
+
+/// A VCF alternate bases allele breakend.
+///
+/// A breakend is written as the replacement bases `t` and either a mate `p` (`t[p[`, `t]p]`,
+/// `]p]t`, `[p[t`) or, for single breakends, a missing mate (`t.`, `.t`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breakend {
+    bases: Vec<Base>,
+    orientation: Orientation,
+    mate: Option<Mate>,
+}
+
+impl Breakend {
+    /// Creates a breakend.
+    ///
+    /// The bases are expected to be nonempty. They include the reference base, which is the first
+    /// base when the orientation is [`Orientation::After`] and the last base when it is
+    /// [`Orientation::Before`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Breakend, Orientation},
+    ///     reference_bases::Base,
+    /// };
+    ///
+    /// let breakend = Breakend::new(vec![Base::G], Orientation::After, None);
+    /// assert_eq!(breakend.to_string(), "G.");
+    /// ```
+    pub fn new(bases: Vec<Base>, orientation: Orientation, mate: Option<Mate>) -> Self {
+        Self {
+            bases,
+            orientation,
+            mate,
+        }
+    }
+
+    /// Returns the replacement bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::Breakend,
+    ///     reference_bases::Base,
+    /// };
+    ///
+    /// let breakend: Breakend = "GT[sq0:8[".parse()?;
+    /// assert_eq!(breakend.bases(), [Base::G, Base::T]);
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn bases(&self) -> &[Base] {
+        &self.bases
+    }
+
+    /// Returns the orientation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::breakend::{Breakend, Orientation};
+    ///
+    /// let breakend: Breakend = "GT[sq0:8[".parse()?;
+    /// assert_eq!(breakend.orientation(), Orientation::After);
+    ///
+    /// let breakend: Breakend = "]sq0:8]GT".parse()?;
+    /// assert_eq!(breakend.orientation(), Orientation::Before);
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns the mate.
+    ///
+    /// This is `None` for single breakends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Breakend, Orientation},
+    ///     Position,
+    /// };
+    ///
+    /// let breakend: Breakend = "GT[sq0:8[".parse()?;
+    /// let mate = breakend.mate().expect("missing mate");
+    /// assert_eq!(mate.position(), Position::from(8));
+    /// assert_eq!(mate.orientation(), Orientation::Before);
+    ///
+    /// let breakend: Breakend = "G.".parse()?;
+    /// assert!(breakend.mate().is_none());
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn mate(&self) -> Option<&Mate> {
+        self.mate.as_ref()
+    }
+
+    /// Returns the reference base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::Breakend,
+    ///     reference_bases::Base,
+    /// };
+    ///
+    /// let breakend: Breakend = "GT[sq0:8[".parse()?;
+    /// assert_eq!(breakend.reference_base(), Some(Base::G));
+    ///
+    /// let breakend: Breakend = "]sq0:8]GT".parse()?;
+    /// assert_eq!(breakend.reference_base(), Some(Base::T));
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn reference_base(&self) -> Option<Base> {
+        match self.orientation {
+            Orientation::After => self.bases.first().copied(),
+            Orientation::Before => self.bases.last().copied(),
+        }
+    }
+
+    /// Returns the inserted bases.
+    ///
+    /// These are the replacement bases without the reference base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::Breakend,
+    ///     reference_bases::Base,
+    /// };
+    ///
+    /// let breakend: Breakend = "GTC[sq0:8[".parse()?;
+    /// assert_eq!(breakend.inserted_bases(), [Base::T, Base::C]);
+    ///
+    /// let breakend: Breakend = ".G".parse()?;
+    /// assert!(breakend.inserted_bases().is_empty());
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn inserted_bases(&self) -> &[Base] {
+        if self.bases.is_empty() {
+            return &[];
+        }
+
+        match self.orientation {
+            Orientation::After => &self.bases[1..],
+            Orientation::Before => &self.bases[..self.bases.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Breakend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MISSING: char = '.';
+
+        if self.orientation == Orientation::Before {
+            match &self.mate {
+                Some(mate) => write!(f, "{mate}")?,
+                None => f.write_char(MISSING)?,
+            }
+        }
+
+        for &base in &self.bases {
+            f.write_char(char::from(base))?;
+        }
+
+        if self.orientation == Orientation::After {
+            match &self.mate {
+                Some(mate) => write!(f, "{mate}")?,
+                None => f.write_char(MISSING)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw VCF record alternate bases allele breakend fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// A base is invalid.
+    InvalidBase(base::TryFromCharError),
+    /// The mate is invalid.
+    InvalidMate(mate::ParseError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidBase(e) => Some(e),
+            Self::InvalidMate(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::InvalidBase(_) => f.write_str("invalid base"),
+            Self::InvalidMate(_) => f.write_str("invalid mate"),
+        }
+    }
+}
+
+impl FromStr for Breakend {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const MISSING: char = '.';
+
+        fn is_bracket(c: char) -> bool {
+            c == '[' || c == ']'
+        }
+
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let (raw_bases, orientation, mate) = if let Some(t) = s.strip_prefix(MISSING) {
+            (t, Orientation::Before, None)
+        } else if let Some(t) = s.strip_suffix(MISSING) {
+            (t, Orientation::After, None)
+        } else if s.starts_with(is_bracket) {
+            let bracket = &s[..1];
+            let i = s[1..].find(bracket).ok_or(ParseError::Invalid)? + 2;
+            let (raw_mate, t) = s.split_at(i);
+            let mate = raw_mate.parse().map_err(ParseError::InvalidMate)?;
+            (t, Orientation::Before, Some(mate))
+        } else if let Some(i) = s.find(is_bracket) {
+            let (t, raw_mate) = s.split_at(i);
+            let mate = raw_mate.parse().map_err(ParseError::InvalidMate)?;
+            (t, Orientation::After, Some(mate))
+        } else {
+            return Err(ParseError::Invalid);
+        };
+
+        if raw_bases.is_empty() {
+            return Err(ParseError::Invalid);
+        }
+
+        let bases = raw_bases
+            .chars()
+            .map(|c| c.to_ascii_uppercase())
+            .map(Base::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(ParseError::InvalidBase)?;
+
+        Ok(Self::new(bases, orientation, mate))
+    }
+}
+
+/// Pairs breakend records with their mates.
+///
+/// Records are paired using their IDs and mate breakend IDs (`MATEID`) info fields. This returns
+/// the indices of each pair of mates, in the order they are first found. A record with no mate in
+/// the given records is not included.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, record::alternate_bases::allele::breakend};
+///
+/// let header = vcf::Header::default();
+///
+/// let records = [
+///     "sq0\t8\tbnd0\tG\tG[sq1:13[\t.\tPASS\tSVTYPE=BND;MATEID=bnd1",
+///     "sq0\t21\tbnd2\tA\tA.\t.\tPASS\tSVTYPE=BND",
+///     "sq1\t13\tbnd1\tT\t]sq0:8]T\t.\tPASS\tSVTYPE=BND;MATEID=bnd0",
+/// ]
+/// .into_iter()
+/// .map(|s| vcf::Record::try_from((&header, s)))
+/// .collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(breakend::pair_mates(&records), [(0, 2)]);
+/// # Ok::<_, vcf::reader::record::ParseError>(())
+/// ```
+pub fn pair_mates(records: &[Record]) -> Vec<(usize, usize)> {
+    let mut indices = HashMap::new();
+
+    for (i, record) in records.iter().enumerate() {
+        for id in record.ids().iter() {
+            indices.entry(&**id).or_insert(i);
+        }
+    }
+
+    let mut pairs = IndexSet::new();
+
+    for (i, record) in records.iter().enumerate() {
+        for mate_id in mate_ids(record) {
+            if let Some(&j) = indices.get(mate_id) {
+                if i != j {
+                    pairs.insert((i.min(j), i.max(j)));
+                }
+            }
+        }
+    }
+
+    pairs.into_iter().collect()
+}
+
+fn mate_ids(record: &Record) -> Vec<&str> {
+    match record.info().get(&key::MATE_BREAKEND_IDS) {
+        Some(Some(Value::String(id))) => vec![id],
+        Some(Some(Value::StringArray(ids))) => ids.iter().flatten().map(|id| &**id).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{Chromosome, Position};
+
+    #[test]
+    fn test_fmt() {
+        let mate = || {
+            Mate::new(
+                Chromosome::Name(String::from("sq0")),
+                Position::from(5),
+                Orientation::After,
+            )
+        };
+
+        let breakend = Breakend::new(vec![Base::A], Orientation::Before, Some(mate()));
+        assert_eq!(breakend.to_string(), "]sq0:5]A");
+
+        let breakend = Breakend::new(vec![Base::C, Base::T], Orientation::After, Some(mate()));
+        assert_eq!(breakend.to_string(), "CT]sq0:5]");
+
+        let breakend = Breakend::new(vec![Base::G], Orientation::After, None);
+        assert_eq!(breakend.to_string(), "G.");
+
+        let breakend = Breakend::new(vec![Base::G, Base::C], Orientation::Before, None);
+        assert_eq!(breakend.to_string(), ".GC");
+    }
+
+    #[test]
+    fn test_from_str() {
+        fn mate(name: &str, position: usize, orientation: Orientation) -> Option<Mate> {
+            Some(Mate::new(
+                Chromosome::Name(name.into()),
+                Position::from(position),
+                orientation,
+            ))
+        }
+
+        assert_eq!(
+            "C[sq1:13[".parse(),
+            Ok(Breakend::new(
+                vec![Base::C],
+                Orientation::After,
+                mate("sq1", 13, Orientation::Before)
+            ))
+        );
+
+        assert_eq!(
+            "CA]sq1:13]".parse(),
+            Ok(Breakend::new(
+                vec![Base::C, Base::A],
+                Orientation::After,
+                mate("sq1", 13, Orientation::After)
+            ))
+        );
+
+        assert_eq!(
+            "]sq0:5]A".parse(),
+            Ok(Breakend::new(
+                vec![Base::A],
+                Orientation::Before,
+                mate("sq0", 5, Orientation::After)
+            ))
+        );
+
+        assert_eq!(
+            "[sq0:5[gA".parse(),
+            Ok(Breakend::new(
+                vec![Base::G, Base::A],
+                Orientation::Before,
+                mate("sq0", 5, Orientation::Before)
+            ))
+        );
+
+        assert_eq!(
+            "CT.".parse(),
+            Ok(Breakend::new(
+                vec![Base::C, Base::T],
+                Orientation::After,
+                None
+            ))
+        );
+
+        assert_eq!(
+            ".A".parse(),
+            Ok(Breakend::new(vec![Base::A], Orientation::Before, None))
+        );
+
+        assert_eq!("".parse::<Breakend>(), Err(ParseError::Empty));
+        assert_eq!(".".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!("[sq0:5[".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!("[sq0:5".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert!(matches!(
+            "A[sq0:5]".parse::<Breakend>(),
+            Err(ParseError::InvalidMate(_))
+        ));
+        assert!(matches!(
+            "Z.".parse::<Breakend>(),
+            Err(ParseError::InvalidBase(_))
+        ));
+    }
+
+    #[test]
+    fn test_inserted_bases() -> Result<(), ParseError> {
+        let breakend: Breakend = "GTC]sq0:5]".parse()?;
+        assert_eq!(breakend.reference_base(), Some(Base::G));
+        assert_eq!(breakend.inserted_bases(), [Base::T, Base::C]);
+
+        let breakend: Breakend = "[sq0:5[GTC".parse()?;
+        assert_eq!(breakend.reference_base(), Some(Base::C));
+        assert_eq!(breakend.inserted_bases(), [Base::G, Base::T]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pair_mates() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::Header;
+
+        let header = Header::default();
+
+        let records = [
+            "sq0\t8\tbnd0\tG\tG[sq1:13[,G]sq2:21]\t.\tPASS\tMATEID=bnd1,bnd2",
+            "sq1\t13\tbnd1\tT\t]sq0:8]T\t.\tPASS\tMATEID=bnd0",
+            "sq2\t21\tbnd2\tA\tA]sq0:8]\t.\tPASS\tMATEID=bnd0",
+            "sq2\t34\tbnd3\tC\tC]sq3:55]\t.\tPASS\tMATEID=bnd4",
+        ]
+        .into_iter()
+        .map(|s| Record::try_from((&header, s)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(pair_mates(&records), [(0, 1), (0, 2)]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! VCF record alternate bases allele breakend mate.
+
+use std::{error, fmt, str::FromStr};
+
+use super::Orientation;
+use crate::record::{chromosome, position, Chromosome, Position};
+
+/// A VCF alternate bases allele breakend mate.
+///
+/// This is the position the breakend is joined to, i.e., `p` in `t[p[`, `t]p]`, `]p]t`, and
+/// `[p[t`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mate {
+    chromosome: Chromosome,
+    position: Position,
+    orientation: Orientation,
+}
+
+impl Mate {
+    /// Creates a breakend mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Mate, Orientation},
+    ///     Position,
+    /// };
+    ///
+    /// let mate = Mate::new("sq0".parse()?, Position::from(8), Orientation::Before);
+    /// # Ok::<_, noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn new(chromosome: Chromosome, position: Position, orientation: Orientation) -> Self {
+        Self {
+            chromosome,
+            position,
+            orientation,
+        }
+    }
+
+    /// Returns the chromosome of the mate.
+    ///
+    /// This is a symbol (e.g., `<ctg0>`) when the mate is on an assembled contig.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Mate, Orientation},
+    ///     Chromosome, Position,
+    /// };
+    ///
+    /// let mate = Mate::new("sq0".parse()?, Position::from(8), Orientation::Before);
+    /// assert_eq!(mate.chromosome(), &Chromosome::Name(String::from("sq0")));
+    /// # Ok::<_, noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    /// Returns the position of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Mate, Orientation},
+    ///     Position,
+    /// };
+    ///
+    /// let mate = Mate::new("sq0".parse()?, Position::from(8), Orientation::Before);
+    /// assert_eq!(mate.position(), Position::from(8));
+    /// # Ok::<_, noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the orientation of the mate.
+    ///
+    /// This is [`Orientation::Before`] when the joined sequence starts at and extends to the right
+    /// of the mate position (`[p[`) and [`Orientation::After`] when it ends at and extends to the
+    /// left of the mate position (`]p]`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Mate, Orientation},
+    ///     Position,
+    /// };
+    ///
+    /// let mate = Mate::new("sq0".parse()?, Position::from(8), Orientation::Before);
+    /// assert_eq!(mate.orientation(), Orientation::Before);
+    /// # Ok::<_, noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    fn bracket(&self) -> char {
+        match self.orientation {
+            Orientation::After => ']',
+            Orientation::Before => '[',
+        }
+    }
+}
+
+impl fmt::Display for Mate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bracket = self.bracket();
+        write!(f, "{bracket}{}:{}{bracket}", self.chromosome, self.position)
+    }
+}
+
+/// An error returned when a raw VCF alternate bases allele breakend mate fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// The chromosome is invalid.
+    InvalidChromosome(chromosome::ParseError),
+    /// The position is invalid.
+    InvalidPosition(position::ParseError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidChromosome(e) => Some(e),
+            Self::InvalidPosition(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::InvalidChromosome(_) => f.write_str("invalid chromosome"),
+            Self::InvalidPosition(_) => f.write_str("invalid position"),
+        }
+    }
+}
+
+impl FromStr for Mate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let orientation = if let Some(t) = s.strip_prefix(']') {
+            t.strip_suffix(']').map(|t| (t, Orientation::After))
+        } else if let Some(t) = s.strip_prefix('[') {
+            t.strip_suffix('[').map(|t| (t, Orientation::Before))
+        } else {
+            None
+        };
+
+        let (t, orientation) = orientation.ok_or(ParseError::Invalid)?;
+        let (raw_chromosome, raw_position) = t.rsplit_once(':').ok_or(ParseError::Invalid)?;
+
+        let chromosome = raw_chromosome
+            .parse()
+            .map_err(ParseError::InvalidChromosome)?;
+
+        let position = raw_position.parse().map_err(ParseError::InvalidPosition)?;
+
+        Ok(Self::new(chromosome, position, orientation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let mate = Mate::new(
+            Chromosome::Name(String::from("sq0")),
+            Position::from(8),
+            Orientation::After,
+        );
+        assert_eq!(mate.to_string(), "]sq0:8]");
+
+        let mate = Mate::new(
+            Chromosome::Symbol(String::from("ctg0")),
+            Position::from(13),
+            Orientation::Before,
+        );
+        assert_eq!(mate.to_string(), "[<ctg0>:13[");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "]sq0:8]".parse(),
+            Ok(Mate::new(
+                Chromosome::Name(String::from("sq0")),
+                Position::from(8),
+                Orientation::After,
+            ))
+        );
+
+        assert_eq!(
+            "[<ctg0>:13[".parse(),
+            Ok(Mate::new(
+                Chromosome::Symbol(String::from("ctg0")),
+                Position::from(13),
+                Orientation::Before,
+            ))
+        );
+
+        assert_eq!("".parse::<Mate>(), Err(ParseError::Empty));
+        assert_eq!("]sq0:8[".parse::<Mate>(), Err(ParseError::Invalid));
+        assert_eq!("]sq0]".parse::<Mate>(), Err(ParseError::Invalid));
+        assert!(matches!(
+            "]:8]".parse::<Mate>(),
+            Err(ParseError::InvalidChromosome(_))
+        ));
+        assert!(matches!(
+            "]sq0:ndls]".parse::<Mate>(),
+            Err(ParseError::InvalidPosition(_))
+        ));
+    }
+}
@@ -0,0 +1,16 @@
+/// A VCF alternate bases allele breakend orientation.
+///
+/// The orientation describes on which side of a breakend the join occurs. The same orientation
+/// is used for the mate: its position is either the last (`]p]`) or first (`[p[`) base of the
+/// joined sequence.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Orientation {
+    /// The join is after the breakend (e.g., `t[p[`, `t]p]`, `t.`, and `]p]`).
+    ///
+    /// The sequence to the left of the breakend is kept.
+    After,
+    /// The join is before the breakend (e.g., `]p]t`, `[p[t`, `.t`, and `[p[`).
+    ///
+    /// The sequence to the right of the breakend is kept.
+    Before,
+}
@@ -0,0 +1,543 @@
+//! VCF validator.
+//!
+//! The validator checks that records conform to the definitions in the header, i.e., that the
+//! chromosome is a defined contig, filters and fields are defined, the number of values in each
+//! info and genotypes field matches its header `Number`, and records are sorted.
+
+pub mod diagnostic;
+
+pub use self::diagnostic::{Diagnostic, Kind, Severity};
+
+use std::{
+    collections::HashSet,
+    io::{self, BufRead},
+};
+
+use crate::{
+    header::{
+        record::value::map::{format, info},
+        Number,
+    },
+    reader::{header::read_raw_header, parse_record, read_line},
+    record::{
+        genotypes::sample::Value as SampleValue, info::field::Value as InfoValue, Chromosome,
+        Filters, Position,
+    },
+    Header, Reader, Record,
+};
+
+const DEFAULT_PLOIDY: usize = 2;
+
+/// The validation mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// All diagnostics are errors.
+    #[default]
+    Strict,
+    /// Contigs, filters, and fields missing from the header are reported as warnings.
+    Lenient,
+}
+
+/// A VCF validator.
+///
+/// The validator is stateful, as it tracks the order of records. Use a new validator, or call
+/// [`Self::validate`], which resets the state, for each file.
+#[derive(Debug, Default)]
+pub struct Validator {
+    mode: Mode,
+    previous: Option<(String, Position)>,
+    visited_chromosomes: HashSet<String>,
+}
+
+impl Validator {
+    /// Creates a VCF validator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::validator::{Mode, Validator};
+    /// let validator = Validator::new(Mode::Lenient);
+    /// ```
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the validation mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::validator::{Mode, Validator};
+    /// let validator = Validator::default();
+    /// assert_eq!(validator.mode(), Mode::Strict);
+    /// ```
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Reads and validates all records from a reader.
+    ///
+    /// The position of the stream is expected to be at the start. The header is read and used to
+    /// validate the records that follow. Records that fail to parse are reported as diagnostics,
+    /// and validation continues with the next line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, validator::{Kind, Validator}};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// ###contig=<ID=sq0>
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t8\t.\tA\t.\t.\tPASS\t.
+    /// sq0\t5\t.\tA\t.\t.\tq10\t.
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// let mut validator = Validator::default();
+    /// let diagnostics = validator.validate(&mut reader)?;
+    ///
+    /// assert_eq!(diagnostics.len(), 2);
+    ///
+    /// assert_eq!(diagnostics[0].line_number(), 5);
+    /// assert_eq!(diagnostics[0].kind(), &Kind::Unsorted);
+    ///
+    /// assert_eq!(diagnostics[1].line_number(), 5);
+    /// assert_eq!(diagnostics[1].kind(), &Kind::UndefinedFilter(String::from("q10")));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn validate<R>(&mut self, reader: &mut Reader<R>) -> io::Result<Vec<Diagnostic>>
+    where
+        R: BufRead,
+    {
+        self.previous = None;
+        self.visited_chromosomes.clear();
+
+        let raw_header = read_raw_header(reader.get_mut())?;
+        let header: Header = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let mut line_number = raw_header.lines().count();
+
+        let mut buf = String::new();
+        let mut record = Record::default();
+        let mut diagnostics = Vec::new();
+
+        loop {
+            buf.clear();
+
+            if read_line(reader.get_mut(), &mut buf)? == 0 {
+                break;
+            }
+
+            line_number += 1;
+
+            match parse_record(&buf, &header, &mut record) {
+                Ok(()) => diagnostics.extend(self.validate_record(&header, line_number, &record)),
+                Err(e) => diagnostics.push(Diagnostic {
+                    line_number,
+                    severity: Severity::Error,
+                    kind: Kind::InvalidRecord(e),
+                }),
+            }
+        }
+
+        Ok(diagnostics)
+    }
+
+    /// Validates a record against a header.
+    ///
+    /// The line number is only used to label the returned diagnostics. Records are expected to be
+    /// validated in file order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::record::value::{map::Info, Map},
+    ///     record::info::field::key,
+    ///     validator::{Kind, Validator},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_info(key::ALLELE_COUNT, Map::<Info>::from(&key::ALLELE_COUNT))
+    ///     .build();
+    ///
+    /// let record = vcf::Record::try_from((&header, "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1"))?;
+    ///
+    /// let mut validator = Validator::default();
+    /// let diagnostics = validator.validate_record(&header, 3, &record);
+    ///
+    /// assert_eq!(diagnostics.len(), 2);
+    /// assert_eq!(diagnostics[0].kind(), &Kind::UndefinedContig(String::from("sq0")));
+    /// assert_eq!(
+    ///     diagnostics[1].kind(),
+    ///     &Kind::InvalidInfoNumber { key: key::ALLELE_COUNT, expected: 2, actual: 1 },
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate_record(
+        &mut self,
+        header: &Header,
+        line_number: usize,
+        record: &Record,
+    ) -> Vec<Diagnostic> {
+        let mut kinds = Vec::new();
+
+        self.validate_chromosome_and_position(header, record, &mut kinds);
+        validate_filters(header, record, &mut kinds);
+        validate_info(header, record, &mut kinds);
+        validate_genotypes(header, record, &mut kinds);
+
+        kinds
+            .into_iter()
+            .map(|kind| Diagnostic {
+                line_number,
+                severity: self.severity(&kind),
+                kind,
+            })
+            .collect()
+    }
+
+    fn severity(&self, kind: &Kind) -> Severity {
+        match (self.mode, kind) {
+            (
+                Mode::Lenient,
+                Kind::UndefinedContig(_)
+                | Kind::UndefinedFilter(_)
+                | Kind::UndefinedInfo(_)
+                | Kind::UndefinedFormat(_),
+            ) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    fn validate_chromosome_and_position(
+        &mut self,
+        header: &Header,
+        record: &Record,
+        kinds: &mut Vec<Kind>,
+    ) {
+        let name = match record.chromosome() {
+            Chromosome::Name(name) => name,
+            Chromosome::Symbol(_) => return,
+        };
+
+        let contigs = header.contigs();
+
+        if !contigs.contains_key(name.as_str()) {
+            kinds.push(Kind::UndefinedContig(name.into()));
+        }
+
+        let position = record.position();
+
+        let is_sorted = match &self.previous {
+            Some((previous_name, previous_position)) if previous_name == name => {
+                position >= *previous_position
+            }
+            Some((previous_name, _)) => {
+                let is_contig_order_valid = match (
+                    contigs.get_index_of(previous_name.as_str()),
+                    contigs.get_index_of(name.as_str()),
+                ) {
+                    (Some(i), Some(j)) => i < j,
+                    _ => true,
+                };
+
+                !self.visited_chromosomes.contains(name) && is_contig_order_valid
+            }
+            None => true,
+        };
+
+        if !is_sorted {
+            kinds.push(Kind::Unsorted);
+        }
+
+        if !self.visited_chromosomes.contains(name) {
+            self.visited_chromosomes.insert(name.into());
+        }
+
+        self.previous = Some((name.into(), position));
+    }
+}
+
+fn validate_filters(header: &Header, record: &Record, kinds: &mut Vec<Kind>) {
+    if let Some(Filters::Fail(ids)) = record.filters() {
+        for id in ids {
+            if !header.filters().contains_key(id) {
+                kinds.push(Kind::UndefinedFilter(id.into()));
+            }
+        }
+    }
+}
+
+fn validate_info(header: &Header, record: &Record, kinds: &mut Vec<Kind>) {
+    let alternate_allele_count = record.alternate_bases().len();
+
+    for (key, value) in record.info().as_ref() {
+        let number = match header.infos().get(key) {
+            Some(map) => Some(map.number()),
+            None => {
+                kinds.push(Kind::UndefinedInfo(key.clone()));
+
+                info::definition::definition(header.file_format(), key).map(|(number, _, _)| number)
+            }
+        };
+
+        let expected = match number
+            .and_then(|n| expected_value_count(n, alternate_allele_count, DEFAULT_PLOIDY))
+        {
+            Some(n) => n,
+            None => continue,
+        };
+
+        let actual = match value.as_ref().and_then(info_value_count) {
+            Some(n) => n,
+            None => continue,
+        };
+
+        if actual != expected {
+            kinds.push(Kind::InvalidInfoNumber {
+                key: key.clone(),
+                expected,
+                actual,
+            });
+        }
+    }
+}
+
+fn validate_genotypes(header: &Header, record: &Record, kinds: &mut Vec<Kind>) {
+    let genotypes = record.genotypes();
+    let alternate_allele_count = record.alternate_bases().len();
+
+    let mut numbers = Vec::with_capacity(genotypes.keys().len());
+
+    for key in genotypes.keys().iter() {
+        let number = match header.formats().get(key) {
+            Some(map) => Some(map.number()),
+            None => {
+                kinds.push(Kind::UndefinedFormat(key.clone()));
+
+                format::definition::definition(header.file_format(), key)
+                    .map(|(number, _, _)| number)
+            }
+        };
+
+        numbers.push(number);
+    }
+
+    for (sample_index, sample) in genotypes.values().enumerate() {
+        let ploidy = match sample.genotype() {
+            Some(Ok(genotype)) => genotype.len(),
+            _ => DEFAULT_PLOIDY,
+        };
+
+        for ((key, value), number) in sample.keys().iter().zip(sample.values()).zip(&numbers) {
+            let expected = match number
+                .and_then(|n| expected_value_count(n, alternate_allele_count, ploidy))
+            {
+                Some(n) => n,
+                None => continue,
+            };
+
+            let actual = match value.as_ref().map(sample_value_count) {
+                Some(n) => n,
+                None => continue,
+            };
+
+            if actual != expected {
+                kinds.push(Kind::InvalidFormatNumber {
+                    sample_index,
+                    key: key.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+}
+
+fn expected_value_count(
+    number: Number,
+    alternate_allele_count: usize,
+    ploidy: usize,
+) -> Option<usize> {
+    match number {
+        Number::Count(n) => Some(n),
+        Number::A => Some(alternate_allele_count),
+        Number::R => Some(alternate_allele_count + 1),
+        Number::G => Some(genotype_count(alternate_allele_count + 1, ploidy)),
+        Number::Unknown => None,
+    }
+}
+
+// The number of unordered genotypes, i.e., (n + p - 1) choose p.
+fn genotype_count(allele_count: usize, ploidy: usize) -> usize {
+    (1..=ploidy).fold(1, |acc, i| acc * (allele_count + i - 1) / i)
+}
+
+fn info_value_count(value: &InfoValue) -> Option<usize> {
+    match value {
+        InfoValue::Flag => None,
+        InfoValue::Integer(_)
+        | InfoValue::Float(_)
+        | InfoValue::Character(_)
+        | InfoValue::String(_) => Some(1),
+        InfoValue::IntegerArray(values) => Some(values.len()),
+        InfoValue::FloatArray(values) => Some(values.len()),
+        InfoValue::CharacterArray(values) => Some(values.len()),
+        InfoValue::StringArray(values) => Some(values.len()),
+    }
+}
+
+fn sample_value_count(value: &SampleValue) -> usize {
+    match value {
+        SampleValue::Integer(_)
+        | SampleValue::Float(_)
+        | SampleValue::Character(_)
+        | SampleValue::String(_) => 1,
+        SampleValue::IntegerArray(values) => values.len(),
+        SampleValue::FloatArray(values) => values.len(),
+        SampleValue::CharacterArray(values) => values.len(),
+        SampleValue::StringArray(values) => values.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##FILTER=<ID=q10,Description=\"Quality below 10\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t1\t.\tA\tC\t.\tPASS\tAC=1\tGT:PL\t0/1:10,0,20
+sq0\t8\t.\tA\tC,G\t.\tq10\tAC=1,0\tGT:PL\t0/1:10,0,20
+sq1\t5\t.\tA\tC\t.\tq20\tAC=1;DP=8\tGT:PL\t1:0,10
+sq0\t13\t.\tA\tC\t.\tPASS\tAC=ndls\tGT\t0/1
+sq2\t2\t.\tA\tC\t.\tPASS\t.\tGT:GQ\t0/1:13
+";
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut reader = Reader::new(DATA);
+        let mut validator = Validator::default();
+        let diagnostics = validator.validate(&mut reader)?;
+
+        let actual: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.line_number(), d.severity(), d.kind().clone()))
+            .collect();
+
+        assert_eq!(
+            actual[..3],
+            [
+                (
+                    10,
+                    Severity::Error,
+                    Kind::InvalidFormatNumber {
+                        sample_index: 0,
+                        key: "PL".parse()?,
+                        expected: 6,
+                        actual: 3,
+                    }
+                ),
+                (
+                    11,
+                    Severity::Error,
+                    Kind::UndefinedFilter(String::from("q20"))
+                ),
+                (11, Severity::Error, Kind::UndefinedInfo("DP".parse()?)),
+            ]
+        );
+
+        assert_eq!(actual[3].0, 12);
+        assert!(matches!(actual[3].2, Kind::InvalidRecord(_)));
+
+        assert_eq!(
+            actual[4..],
+            [
+                (
+                    13,
+                    Severity::Error,
+                    Kind::UndefinedContig(String::from("sq2"))
+                ),
+                (13, Severity::Error, Kind::UndefinedFormat("GQ".parse()?)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_lenient_mode() -> Result<(), Box<dyn std::error::Error>> {
+        let mut reader = Reader::new(DATA);
+        let mut validator = Validator::new(Mode::Lenient);
+        let diagnostics = validator.validate(&mut reader)?;
+
+        let severities: Vec<_> = diagnostics.iter().map(|d| d.severity()).collect();
+
+        assert_eq!(
+            severities,
+            [
+                Severity::Error,
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Error,
+                Severity::Warning,
+                Severity::Warning,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::header::record::value::{map::Contig, Map};
+
+        let header = Header::builder()
+            .add_contig("sq0".parse()?, Map::<Contig>::new())
+            .add_contig("sq1".parse()?, Map::<Contig>::new())
+            .build();
+
+        let mut validator = Validator::default();
+
+        let mut validate = |line_number, s| -> Result<_, Box<dyn std::error::Error>> {
+            let record = Record::try_from((&header, s))?;
+            Ok(validator.validate_record(&header, line_number, &record))
+        };
+
+        assert!(validate(1, "sq0\t8\t.\tA\t.\t.\tPASS\t.")?.is_empty());
+        assert!(validate(2, "sq0\t8\t.\tA\t.\t.\tPASS\t.")?.is_empty());
+
+        let diagnostics = validate(3, "sq0\t5\t.\tA\t.\t.\tPASS\t.")?;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind(), &Kind::Unsorted);
+
+        assert!(validate(4, "sq1\t1\t.\tA\t.\t.\tPASS\t.")?.is_empty());
+
+        let diagnostics = validate(5, "sq0\t13\t.\tA\t.\t.\tPASS\t.")?;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind(), &Kind::Unsorted);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_genotype_count() {
+        assert_eq!(genotype_count(2, 1), 2);
+        assert_eq!(genotype_count(2, 2), 3);
+        assert_eq!(genotype_count(3, 2), 6);
+        assert_eq!(genotype_count(2, 3), 4);
+    }
+}
@@ -0,0 +1,183 @@
+//! VCF validator diagnostic.
+
+use std::fmt;
+
+use crate::{
+    reader::record::ParseError,
+    record::{genotypes::keys::Key as FormatKey, info::field::Key as InfoKey},
+};
+
+/// The severity of a diagnostic.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Severity {
+    /// The record is invalid.
+    Error,
+    /// The record is readable but does not conform to the header.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => f.write_str("error"),
+            Self::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// The kind of a diagnostic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// The record failed to parse.
+    InvalidRecord(ParseError),
+    /// The chromosome is not defined in the header contigs.
+    UndefinedContig(String),
+    /// A filter is not defined in the header.
+    UndefinedFilter(String),
+    /// An info field key is not defined in the header.
+    UndefinedInfo(InfoKey),
+    /// A genotypes field key is not defined in the header.
+    UndefinedFormat(FormatKey),
+    /// The number of values in an info field does not match its header definition.
+    InvalidInfoNumber {
+        /// The info field key.
+        key: InfoKey,
+        /// The expected number of values.
+        expected: usize,
+        /// The actual number of values.
+        actual: usize,
+    },
+    /// The number of values in a genotypes field does not match its header definition.
+    InvalidFormatNumber {
+        /// The sample index.
+        sample_index: usize,
+        /// The genotypes field key.
+        key: FormatKey,
+        /// The expected number of values.
+        expected: usize,
+        /// The actual number of values.
+        actual: usize,
+    },
+    /// The record is out of order.
+    ///
+    /// Records are expected to be grouped by chromosome, ordered by position, and, when the
+    /// chromosomes are defined in the header, in the same order as the header contigs.
+    Unsorted,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(e) => {
+                write!(f, "invalid record: {e}")?;
+
+                let mut source = std::error::Error::source(e);
+
+                while let Some(e) = source {
+                    write!(f, ": {e}")?;
+                    source = e.source();
+                }
+
+                Ok(())
+            }
+            Self::UndefinedContig(name) => write!(f, "undefined contig: {name}"),
+            Self::UndefinedFilter(id) => write!(f, "undefined filter: {id}"),
+            Self::UndefinedInfo(key) => write!(f, "undefined info field: {key}"),
+            Self::UndefinedFormat(key) => write!(f, "undefined genotypes field: {key}"),
+            Self::InvalidInfoNumber {
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid number of values for info field {key}: expected {expected}, got {actual}"
+            ),
+            Self::InvalidFormatNumber {
+                sample_index,
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid number of values for genotypes field {key} in sample {sample_index}: expected {expected}, got {actual}"
+            ),
+            Self::Unsorted => f.write_str("record is out of order"),
+        }
+    }
+}
+
+/// A VCF validator diagnostic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub(super) line_number: usize,
+    pub(super) severity: Severity,
+    pub(super) kind: Kind,
+}
+
+impl Diagnostic {
+    /// Returns the 1-based line number of the record.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Returns the severity.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the kind.
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+
+    /// Returns whether the diagnostic is an error.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: line {}: {}",
+            self.severity, self.line_number, self.kind
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let diagnostic = Diagnostic {
+            line_number: 8,
+            severity: Severity::Warning,
+            kind: Kind::UndefinedContig(String::from("sq0")),
+        };
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "warning: line 8: undefined contig: sq0"
+        );
+
+        let diagnostic = Diagnostic {
+            line_number: 13,
+            severity: Severity::Error,
+            kind: Kind::InvalidInfoNumber {
+                key: "NS".parse()?,
+                expected: 2,
+                actual: 1,
+            },
+        };
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: line 13: invalid number of values for info field NS: expected 2, got 1"
+        );
+
+        Ok(())
+    }
+}